@@ -0,0 +1,6 @@
+fn main() {
+    fastformat_codegen::Generator::new()
+        .python(std::env::var_os("CARGO_FEATURE_PYO3").is_some())
+        .compile("datatypes/imu.ff")
+        .unwrap();
+}
@@ -0,0 +1,117 @@
+use fastformat_datatypes::header::Header;
+
+use std::borrow::Cow;
+
+include!(concat!(env!("OUT_DIR"), "/imu.rs"));
+
+fn imu_read(sequence: u64) -> Imu<'static> {
+    // Dummy sensor read
+
+    Imu {
+        acceleration: Cow::Owned(vec![0.0, 0.0, 9.81]),
+        angular_velocity: Cow::Owned(vec![0.01, 0.0, -0.02]),
+        temperature: Some(36.5),
+        model: "dummy-imu".to_string(),
+        header: Some(Header::new(1_700_000_000_000_000_000, "imu_link", sequence)),
+    }
+}
+
+fn send_output(arrow_array: arrow::array::ArrayData) {
+    // Dummy send output
+
+    let raw_data = Imu::raw_data(arrow_array).unwrap();
+    let imu = Imu::view_from_raw_data(&raw_data).unwrap();
+
+    println!(
+        "Received acceleration {:?} at address {:?}",
+        imu.acceleration,
+        imu.acceleration.as_ptr()
+    );
+}
+
+fn main() {
+    let imu = imu_read(0);
+
+    send_output(imu.into_arrow().unwrap());
+
+    let status = ImuStatus {
+        error_count: 0,
+        message: None,
+        flags: vec!["calibrated".to_string()],
+        header: None,
+    };
+
+    println!("{:?}", ImuStatus::from_arrow(status.into_arrow().unwrap()));
+}
+
+mod tests {
+    #[test]
+    fn test_imu_arrow() {
+        use crate::{imu_read, Imu, ImuStatus};
+        use fastformat_datatypes::{header::HasHeader, owned_view::Viewable};
+
+        use std::borrow::Cow;
+
+        let imu = imu_read(7);
+        let array_data = imu.clone().into_arrow().unwrap();
+
+        let raw_data = <Imu as Viewable>::raw_data(array_data.clone()).unwrap();
+        let view = <Imu as Viewable>::view_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(view, imu);
+        assert!(matches!(view.acceleration, Cow::Borrowed(_)));
+        assert_eq!(view.header().map(|header| header.sequence), Some(7));
+
+        let owned = Imu::from_arrow(array_data).unwrap();
+
+        assert_eq!(owned, imu);
+        assert!(matches!(owned.acceleration, Cow::Owned(_)));
+
+        let status = ImuStatus {
+            error_count: 3,
+            message: Some("saturated".to_string()),
+            flags: vec![],
+            header: None,
+        };
+
+        assert_eq!(
+            ImuStatus::from_arrow(status.clone().into_arrow().unwrap()).unwrap(),
+            status
+        );
+    }
+
+    #[cfg(feature = "pyo3")]
+    #[test]
+    fn test_imu_python() {
+        use crate::{imu_read, Imu};
+        use pyo3::{prelude::*, types::PyDict};
+
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let imu = Imu {
+                header: None,
+                ..imu_read(0)
+            };
+
+            let dict = imu.to_object(py);
+            let dict = dict.downcast_bound::<PyDict>(py).unwrap();
+
+            assert_eq!(
+                dict.get_item("model")
+                    .unwrap()
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "dummy-imu"
+            );
+            assert_eq!(dict.extract::<Imu>().unwrap(), imu);
+
+            dict.del_item("temperature").unwrap();
+            assert_eq!(dict.extract::<Imu>().unwrap().temperature, None);
+
+            dict.del_item("model").unwrap();
+            assert!(dict.extract::<Imu>().is_err());
+        });
+    }
+}
@@ -0,0 +1,18 @@
+#![no_main]
+
+use fastformat_datatypes::bbox::BBox;
+use fastformat_fuzz::{Message, BBOX_FIELDS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|message: Message| {
+    let array_data = message.into_array_data(BBOX_FIELDS);
+
+    if let Ok(bbox) = BBox::from_arrow(array_data.clone()) {
+        let _ = bbox.validate(None);
+        let _ = bbox.into_xywh();
+    }
+
+    if let Ok(raw_data) = BBox::raw_data(array_data) {
+        let _ = BBox::view_from_raw_data(&raw_data);
+    }
+});
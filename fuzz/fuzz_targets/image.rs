@@ -0,0 +1,18 @@
+#![no_main]
+
+use fastformat_datatypes::image::Image;
+use fastformat_fuzz::{Message, IMAGE_FIELDS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|message: Message| {
+    let array_data = message.into_array_data(IMAGE_FIELDS);
+
+    if let Ok(image) = Image::from_arrow(array_data.clone()) {
+        let _ = image.validate();
+        let _ = image.into_rgb8();
+    }
+
+    if let Ok(raw_data) = Image::raw_data(array_data) {
+        let _ = Image::view_from_raw_data(&raw_data);
+    }
+});
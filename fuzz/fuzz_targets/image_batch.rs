@@ -0,0 +1,11 @@
+#![no_main]
+
+use fastformat_datatypes::image::Image;
+use fastformat_fuzz::{Message, IMAGE_BATCH_FIELDS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|message: Message| {
+    let array_data = message.into_array_data(IMAGE_BATCH_FIELDS);
+
+    let _ = Image::from_arrow_batch(array_data);
+});
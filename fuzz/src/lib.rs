@@ -0,0 +1,162 @@
+//! Arbitrary Arrow messages for the fuzz targets.
+//!
+//! Messages are built without validation, like `ArrayData` assembled from a shared memory
+//! region by a receiving node, so that the targets exercise the checks of the datatypes rather
+//! than those of Arrow's builders.
+
+use arbitrary::Arbitrary;
+use arrow::{
+    array::ArrayData,
+    buffer::Buffer,
+    datatypes::{DataType, Field, UnionFields, UnionMode},
+};
+
+use std::{collections::HashMap, sync::Arc};
+
+/// Union fields of an `Image`.
+pub const IMAGE_FIELDS: &[&str] = &[
+    "width",
+    "height",
+    "encoding",
+    "name",
+    "data",
+    "timestamp_ns",
+    "frame_id",
+    "sequence",
+    "exposure_time_ns",
+    "gain_db",
+    "iso",
+    "lens",
+    "gps",
+];
+
+/// Union fields of a batch of `Image`s.
+pub const IMAGE_BATCH_FIELDS: &[&str] = &["width", "height", "encoding", "name", "offsets", "data"];
+
+/// Union fields of a `BBox`.
+pub const BBOX_FIELDS: &[&str] = &[
+    "data",
+    "confidence",
+    "label",
+    "class_id",
+    "encoding",
+    "timestamp_ns",
+    "frame_id",
+    "sequence",
+];
+
+#[derive(Debug, Arbitrary)]
+enum ChildType {
+    UInt8,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Utf8,
+}
+
+impl From<ChildType> for DataType {
+    fn from(child_type: ChildType) -> Self {
+        match child_type {
+            ChildType::UInt8 => DataType::UInt8,
+            ChildType::UInt32 => DataType::UInt32,
+            ChildType::UInt64 => DataType::UInt64,
+            ChildType::Float32 => DataType::Float32,
+            ChildType::Float64 => DataType::Float64,
+            ChildType::Utf8 => DataType::Utf8,
+        }
+    }
+}
+
+/// Field metadata keys read by fastformat, so that the targets also exercise compressed fields
+/// and units, whatever length or codec a message claims.
+#[derive(Debug, Arbitrary)]
+enum MetadataKey {
+    Codec,
+    DataType,
+    Len,
+    Unit,
+}
+
+impl MetadataKey {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetadataKey::Codec => "fastformat.compression.codec",
+            MetadataKey::DataType => "fastformat.compression.data_type",
+            MetadataKey::Len => "fastformat.compression.len",
+            MetadataKey::Unit => "fastformat.unit",
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+struct Child {
+    child_type: ChildType,
+    len: u8,
+    offset: u8,
+    buffers: Vec<Vec<u8>>,
+    metadata: Vec<(MetadataKey, String)>,
+}
+
+/// A union message with arbitrary children, named after the fields of a datatype.
+#[derive(Debug, Arbitrary)]
+pub struct Message {
+    children: Vec<Child>,
+    len: u8,
+    type_ids: Vec<u8>,
+    offsets: Option<Vec<u8>>,
+}
+
+impl Message {
+    /// Builds the message, naming its children after `fields` in order.
+    pub fn into_array_data(self, fields: &[&str]) -> ArrayData {
+        let (union_fields, children): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .zip(self.children)
+            .enumerate()
+            .map(|(index, (name, child))| {
+                let data_type = DataType::from(child.child_type);
+                let metadata = child
+                    .metadata
+                    .into_iter()
+                    .map(|(key, value)| (key.as_str().to_string(), value))
+                    .collect::<HashMap<_, _>>();
+                let field = Arc::new(
+                    Field::new(*name, data_type.clone(), false).with_metadata(metadata),
+                );
+
+                // SAFETY: not safe to read, checking it is the point of the fuzz targets.
+                let child = unsafe {
+                    ArrayData::builder(data_type)
+                        .len(child.len as usize)
+                        .offset(child.offset as usize)
+                        .buffers(child.buffers.into_iter().map(Buffer::from_vec).collect())
+                        .build_unchecked()
+                };
+
+                ((index as i8, field), child)
+            })
+            .unzip();
+
+        let mode = match self.offsets {
+            Some(_) => UnionMode::Dense,
+            None => UnionMode::Sparse,
+        };
+        let buffers = std::iter::once(self.type_ids)
+            .chain(self.offsets)
+            .map(Buffer::from_vec)
+            .collect();
+
+        // SAFETY: see above.
+        unsafe {
+            ArrayData::builder(DataType::Union(
+                union_fields.into_iter().collect::<UnionFields>(),
+                mode,
+            ))
+            .len(self.len as usize)
+            .buffers(buffers)
+            .child_data(children)
+            .build_unchecked()
+        }
+    }
+}
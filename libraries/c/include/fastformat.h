@@ -0,0 +1,112 @@
+#ifndef FASTFORMAT_H
+#define FASTFORMAT_H
+
+/* Keep in sync with src/lib.rs: regenerate with cbindgen, see cbindgen.toml. */
+
+#include <stddef.h>
+#include <stdint.h>
+#include "arrow_c_data_interface.h"
+
+// Bounding boxes, see `fastformat_datatypes::bbox::BBox`.
+typedef struct FfBBox FfBBox;
+
+// An image, see `fastformat_datatypes::image::Image`.
+typedef struct FfImage FfImage;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the message of the last error that happened on the calling thread, or null if there
+// was none. The message is valid until the next failing call on the same thread.
+const char *ff_last_error(void);
+
+// Creates an image from a copy of `len` bytes of pixel data.
+FfImage *ff_image_new(const uint8_t *data,
+                      size_t len,
+                      uint32_t width,
+                      uint32_t height,
+                      const char *encoding,
+                      const char *name);
+
+// Creates an image that borrows `len` bytes of pixel data without copying them. The data must
+// stay valid and unchanged until the image is freed or exported with `ff_image_into_arrow`.
+FfImage *ff_image_wrap(const uint8_t *data,
+                       size_t len,
+                       uint32_t width,
+                       uint32_t height,
+                       const char *encoding,
+                       const char *name);
+
+// Frees an image. Does nothing if `image` is null.
+void ff_image_free(FfImage *image);
+
+// Returns the width of the image, or 0 if `image` is null.
+uint32_t ff_image_width(const FfImage *image);
+
+// Returns the height of the image, or 0 if `image` is null.
+uint32_t ff_image_height(const FfImage *image);
+
+// Returns the encoding of the image, e.g. `"RGB8"`, or null if `image` is null. The string is
+// static.
+const char *ff_image_encoding(const FfImage *image);
+
+// Returns the name of the image, or null if it has none or if `image` is null. The string
+// lives as long as the image.
+const char *ff_image_name(const FfImage *image);
+
+// Returns the pixel data of the image and writes its length to `len`. The data lives as long
+// as the image. Returns null, writing 0 to `len` unless it is null, if an argument is null.
+const uint8_t *ff_image_data(const FfImage *image, size_t *len);
+
+// Exports an image into `array` and `schema`, which must be released by the consumer. The image
+// is consumed, even on failure. Returns 0 on success and -1 on failure, e.g. if an argument is
+// null.
+int ff_image_into_arrow(FfImage *image, struct ArrowArray *array, struct ArrowSchema *schema);
+
+// Imports an image from `array` and `schema`, without copying the pixel data. Ownership of
+// `array` is taken, even on failure: it is left released.
+FfImage *ff_image_from_arrow(struct ArrowArray *array, const struct ArrowSchema *schema);
+
+// Creates `count` bounding boxes from a copy of their `4 * count` coordinates, `count`
+// confidences and `count` labels. `encoding` is one of `"XYXY"`, `"XYWH"`, `"XYXYN"` or
+// `"XYWHN"`.
+FfBBox *ff_bbox_new(const float *data,
+                    const float *confidence,
+                    const char *const *label,
+                    size_t count,
+                    const char *encoding);
+
+// Frees bounding boxes. Does nothing if `bbox` is null.
+void ff_bbox_free(FfBBox *bbox);
+
+// Returns the number of boxes, or 0 if `bbox` is null.
+size_t ff_bbox_len(const FfBBox *bbox);
+
+// Returns the `4 * ff_bbox_len` coordinates of the boxes, or null if `bbox` is null. They live
+// as long as `bbox`.
+const float *ff_bbox_data(const FfBBox *bbox);
+
+// Returns the `ff_bbox_len` confidences of the boxes, or null if `bbox` is null. They live as
+// long as `bbox`.
+const float *ff_bbox_confidence(const FfBBox *bbox);
+
+// Returns the label of the box at `index`, or null if it is out of range or if `bbox` is null.
+// The string lives as long as `bbox`.
+const char *ff_bbox_label(const FfBBox *bbox, size_t index);
+
+// Returns the encoding of the boxes, e.g. `"XYXY"`, or null if `bbox` is null. The string is
+// static.
+const char *ff_bbox_encoding(const FfBBox *bbox);
+
+// Exports bounding boxes into `array` and `schema`, like `ff_image_into_arrow`.
+int ff_bbox_into_arrow(FfBBox *bbox, struct ArrowArray *array, struct ArrowSchema *schema);
+
+// Imports bounding boxes from `array` and `schema`, like `ff_image_from_arrow`.
+FfBBox *ff_bbox_from_arrow(struct ArrowArray *array, const struct ArrowSchema *schema);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif // FASTFORMAT_H
@@ -0,0 +1,219 @@
+// C++17 wrapper of the fastformat C API (fastformat.h).
+//
+// Datatypes are move-only RAII classes that free their handle on destruction. Errors are
+// reported as `ff::Error` exceptions, and pixel and box data is accessed through read-only spans
+// pointing into the datatype, without copying.
+
+#ifndef FASTFORMAT_HPP
+#define FASTFORMAT_HPP
+
+#include "fastformat.h"
+
+#include <cstddef>
+#include <cstdint>
+#include <memory>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <string_view>
+#include <utility>
+#include <vector>
+
+#if __cplusplus >= 202002L
+#include <span>
+#endif
+
+namespace ff {
+
+#if __cplusplus >= 202002L
+template <class T>
+using span = std::span<const T>;
+#else
+// Minimal stand-in for `std::span<const T>` before C++20.
+template <class T>
+class span {
+ public:
+  constexpr span() noexcept = default;
+  constexpr span(const T* data, std::size_t size) noexcept : data_(data), size_(size) {}
+
+  constexpr const T* data() const noexcept { return data_; }
+  constexpr std::size_t size() const noexcept { return size_; }
+  constexpr bool empty() const noexcept { return size_ == 0; }
+
+  constexpr const T* begin() const noexcept { return data_; }
+  constexpr const T* end() const noexcept { return data_ + size_; }
+
+  constexpr const T& operator[](std::size_t index) const { return data_[index]; }
+
+ private:
+  const T* data_ = nullptr;
+  std::size_t size_ = 0;
+};
+#endif
+
+// Error raised by a failing fastformat call, with the message of `ff_last_error`.
+class Error : public std::runtime_error {
+ public:
+  using std::runtime_error::runtime_error;
+};
+
+namespace detail {
+
+[[noreturn]] inline void throw_last_error() {
+  const char* message = ff_last_error();
+
+  throw Error(message != nullptr ? message : "unknown fastformat error");
+}
+
+template <class T>
+T* check(T* handle) {
+  if (handle == nullptr) {
+    throw_last_error();
+  }
+
+  return handle;
+}
+
+inline void check(int status) {
+  if (status != 0) {
+    throw_last_error();
+  }
+}
+
+inline const char* c_str_or_null(const std::string& value) {
+  return value.empty() ? nullptr : value.c_str();
+}
+
+struct ImageDeleter {
+  void operator()(FfImage* image) const noexcept { ff_image_free(image); }
+};
+
+struct BBoxDeleter {
+  void operator()(FfBBox* bbox) const noexcept { ff_bbox_free(bbox); }
+};
+
+}  // namespace detail
+
+// An image, see `fastformat_datatypes::image::Image`.
+class Image {
+ public:
+  // Creates an image from a copy of the pixel data. An empty name means no name.
+  static Image copy(span<std::uint8_t> data, std::uint32_t width, std::uint32_t height,
+                    const std::string& encoding, const std::string& name = "") {
+    return Image(detail::check(ff_image_new(data.data(), data.size(), width, height,
+                                            encoding.c_str(), detail::c_str_or_null(name))));
+  }
+
+  // Creates an image borrowing the pixel data, which must outlive the image or its export with
+  // `into_arrow`.
+  static Image wrap(span<std::uint8_t> data, std::uint32_t width, std::uint32_t height,
+                    const std::string& encoding, const std::string& name = "") {
+    return Image(detail::check(ff_image_wrap(data.data(), data.size(), width, height,
+                                             encoding.c_str(), detail::c_str_or_null(name))));
+  }
+
+  // Imports an image without copying its pixel data. Ownership of `array` is taken.
+  static Image from_arrow(ArrowArray* array, const ArrowSchema* schema) {
+    return Image(detail::check(ff_image_from_arrow(array, schema)));
+  }
+
+  // Exports the image into `array` and `schema`, which the consumer must release. The image is
+  // left empty.
+  void into_arrow(ArrowArray* array, ArrowSchema* schema) && {
+    detail::check(ff_image_into_arrow(handle_.release(), array, schema));
+  }
+
+  std::uint32_t width() const { return ff_image_width(handle_.get()); }
+  std::uint32_t height() const { return ff_image_height(handle_.get()); }
+
+  std::string_view encoding() const { return ff_image_encoding(handle_.get()); }
+
+  std::optional<std::string_view> name() const {
+    const char* name = ff_image_name(handle_.get());
+
+    return name != nullptr ? std::optional<std::string_view>(name) : std::nullopt;
+  }
+
+  // The pixel data, valid as long as the image.
+  span<std::uint8_t> data() const {
+    std::size_t len = 0;
+    const std::uint8_t* data = detail::check(ff_image_data(handle_.get(), &len));
+
+    return span<std::uint8_t>(data, len);
+  }
+
+  FfImage* get() const noexcept { return handle_.get(); }
+
+ private:
+  explicit Image(FfImage* handle) : handle_(handle) {}
+
+  std::unique_ptr<FfImage, detail::ImageDeleter> handle_;
+};
+
+// Bounding boxes, see `fastformat_datatypes::bbox::BBox`.
+class BBox {
+ public:
+  // Creates boxes from a copy of their `4 * labels.size()` coordinates, confidences and labels.
+  // `encoding` is one of "XYXY", "XYWH", "XYXYN" or "XYWHN".
+  BBox(span<float> data, span<float> confidence, const std::vector<std::string>& labels,
+       const std::string& encoding) {
+    if (data.size() != 4 * labels.size() || confidence.size() != labels.size()) {
+      throw Error("Confidence, Label and Data doesn't match length");
+    }
+
+    std::vector<const char*> label_pointers;
+    label_pointers.reserve(labels.size());
+    for (const std::string& label : labels) {
+      label_pointers.push_back(label.c_str());
+    }
+
+    handle_.reset(detail::check(ff_bbox_new(data.data(), confidence.data(),
+                                            label_pointers.data(), labels.size(),
+                                            encoding.c_str())));
+  }
+
+  // Imports boxes from Arrow. Ownership of `array` is taken.
+  static BBox from_arrow(ArrowArray* array, const ArrowSchema* schema) {
+    return BBox(detail::check(ff_bbox_from_arrow(array, schema)));
+  }
+
+  // Exports the boxes into `array` and `schema`, which the consumer must release. The boxes are
+  // left empty.
+  void into_arrow(ArrowArray* array, ArrowSchema* schema) && {
+    detail::check(ff_bbox_into_arrow(handle_.release(), array, schema));
+  }
+
+  // Number of boxes.
+  std::size_t size() const { return ff_bbox_len(handle_.get()); }
+
+  // The `4 * size()` coordinates, valid as long as the boxes.
+  span<float> data() const { return span<float>(ff_bbox_data(handle_.get()), 4 * size()); }
+
+  // The `size()` confidences, valid as long as the boxes.
+  span<float> confidence() const {
+    return span<float>(ff_bbox_confidence(handle_.get()), size());
+  }
+
+  std::string_view label(std::size_t index) const {
+    const char* label = ff_bbox_label(handle_.get(), index);
+
+    if (label == nullptr) {
+      throw std::out_of_range("BBox label index out of range");
+    }
+
+    return label;
+  }
+
+  std::string_view encoding() const { return ff_bbox_encoding(handle_.get()); }
+
+  FfBBox* get() const noexcept { return handle_.get(); }
+
+ private:
+  explicit BBox(FfBBox* handle) : handle_(handle) {}
+
+  std::unique_ptr<FfBBox, detail::BBoxDeleter> handle_;
+};
+
+}  // namespace ff
+
+#endif  // FASTFORMAT_HPP
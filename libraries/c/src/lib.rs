@@ -0,0 +1,607 @@
+//! C bindings of fastformat, for C and C++ dora nodes.
+//!
+//! Datatypes are handed out as opaque pointers, released with their `ff_*_free` function.
+//! Functions that can fail return a null pointer or `-1`, and the error message can then be read
+//! with [`ff_last_error`]. Accessors given a null handle record an error too, and return 0 or
+//! null. Messages are exchanged with other nodes through the Arrow C Data
+//! Interface: `ff_*_into_arrow` exports a datatype into `ArrowArray`/`ArrowSchema` structs and
+//! `ff_*_from_arrow` imports it back, both without copying the data.
+//!
+//! The C header is `include/fastformat.h`, regenerated with cbindgen (see `cbindgen.toml`).
+
+#![allow(clippy::missing_safety_doc)] // Safety requirements are documented in the C header.
+
+use fastformat_datatypes::{
+    bbox::{self, BBox},
+    image::{Encoding, Image, ImageData},
+};
+
+use arrow::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+use eyre::{Context, Report, Result};
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, CStr, CString},
+    ptr,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: Report) {
+    let message = CString::new(format!("{:#}", error).replace('\0', " ")).unwrap_or_default();
+
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+/// Boxes a successful result for C, or records the error and returns null.
+fn into_raw_or_null<T>(result: Result<T>) -> *mut T {
+    match result {
+        Ok(value) => Box::into_raw(Box::new(value)),
+        Err(error) => {
+            set_last_error(error);
+
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Reads an optional C string argument: null is `None`.
+unsafe fn str_arg<'a>(argument: &'static str, value: *const c_char) -> Result<Option<&'a str>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    CStr::from_ptr(value)
+        .to_str()
+        .map(Some)
+        .wrap_err(format!("{} is not valid UTF-8", argument))
+}
+
+/// Reads a required C string argument.
+unsafe fn required_str_arg<'a>(argument: &'static str, value: *const c_char) -> Result<&'a str> {
+    str_arg(argument, value)?.ok_or_else(|| Report::msg(format!("{} is null", argument)))
+}
+
+/// Reads a C array argument, which may be null if it is empty.
+unsafe fn slice_arg<'a, T>(argument: &'static str, data: *const T, len: usize) -> Result<&'a [T]> {
+    match (data.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(Report::msg(format!("{} is null", argument))),
+        (false, len) => Ok(std::slice::from_raw_parts(data, len)),
+    }
+}
+
+/// Reads a handle argument, recording an error and returning `None` if it is null.
+unsafe fn handle_arg<'a, T>(argument: &'static str, value: *const T) -> Option<&'a T> {
+    if value.is_null() {
+        set_last_error(Report::msg(format!("{} is null", argument)));
+    }
+
+    value.as_ref()
+}
+
+fn c_string(value: &str) -> CString {
+    CString::new(value.replace('\0', " ")).unwrap_or_default()
+}
+
+/// Returns the message of the last error that happened on the calling thread, or null if there
+/// was none. The message is valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn ff_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// An image, see `fastformat_datatypes::image::Image`.
+pub struct FfImage {
+    image: Image<'static>,
+    name: Option<CString>,
+}
+
+impl FfImage {
+    fn new(image: Image<'static>) -> Self {
+        let name = image.name.as_deref().map(c_string);
+
+        Self { image, name }
+    }
+}
+
+unsafe fn new_image(
+    data: ImageData<'static>,
+    width: u32,
+    height: u32,
+    encoding: *const c_char,
+    name: *const c_char,
+) -> Result<FfImage> {
+    let encoding = required_str_arg("encoding", encoding)?.parse::<Encoding>()?;
+    let name = str_arg("name", name)?;
+
+    let image = Image {
+        data,
+        width,
+        height,
+        encoding,
+        name: name.map(str::to_string),
+        header: None,
+        capture: None,
+    };
+
+    if let Some(issue) = image.validate().into_iter().next() {
+        return Err(Report::msg(format!("Invalid image: {}", issue)));
+    }
+
+    Ok(FfImage::new(image))
+}
+
+/// Creates an image from a copy of `len` bytes of pixel data.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_new(
+    data: *const u8,
+    len: usize,
+    width: u32,
+    height: u32,
+    encoding: *const c_char,
+    name: *const c_char,
+) -> *mut FfImage {
+    into_raw_or_null(slice_arg("data", data, len).and_then(|data| {
+        new_image(
+            ImageData::from_vec_u8(data.to_vec()),
+            width,
+            height,
+            encoding,
+            name,
+        )
+    }))
+}
+
+/// Creates an image that borrows `len` bytes of pixel data without copying them. The data must
+/// stay valid and unchanged until the image is freed or exported with `ff_image_into_arrow`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_wrap(
+    data: *const u8,
+    len: usize,
+    width: u32,
+    height: u32,
+    encoding: *const c_char,
+    name: *const c_char,
+) -> *mut FfImage {
+    into_raw_or_null(slice_arg("data", data, len).and_then(|data| {
+        new_image(
+            ImageData::from_slice_u8(data),
+            width,
+            height,
+            encoding,
+            name,
+        )
+    }))
+}
+
+/// Frees an image. Does nothing if `image` is null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_free(image: *mut FfImage) {
+    if !image.is_null() {
+        drop(Box::from_raw(image));
+    }
+}
+
+/// Returns the width of the image, or 0 if `image` is null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_width(image: *const FfImage) -> u32 {
+    handle_arg("image", image).map_or(0, |image| image.image.width)
+}
+
+/// Returns the height of the image, or 0 if `image` is null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_height(image: *const FfImage) -> u32 {
+    handle_arg("image", image).map_or(0, |image| image.image.height)
+}
+
+/// Returns the encoding of the image, e.g. `"RGB8"`, or null if `image` is null. The string is
+/// static.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_encoding(image: *const FfImage) -> *const c_char {
+    let Some(image) = handle_arg("image", image) else {
+        return ptr::null();
+    };
+
+    match image.image.encoding {
+        Encoding::RGB8 => c"RGB8".as_ptr(),
+        Encoding::BGR8 => c"BGR8".as_ptr(),
+        Encoding::GRAY8 => c"GRAY8".as_ptr(),
+    }
+}
+
+/// Returns the name of the image, or null if it has none or if `image` is null. The string
+/// lives as long as the image.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_name(image: *const FfImage) -> *const c_char {
+    handle_arg("image", image)
+        .and_then(|image| image.name.as_ref())
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// Returns the pixel data of the image and writes its length to `len`. The data lives as long
+/// as the image. Returns null, writing 0 to `len` unless it is null, if an argument is null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_data(image: *const FfImage, len: *mut usize) -> *const u8 {
+    let Some(len) = len.as_mut() else {
+        set_last_error(Report::msg("len is null"));
+
+        return ptr::null();
+    };
+
+    let data = handle_arg("image", image).map(|image| image.image.data.as_u8());
+
+    match data {
+        Some(Ok(data)) => {
+            *len = data.len();
+
+            data.as_ptr()
+        }
+        Some(Err(error)) => {
+            set_last_error(error);
+            *len = 0;
+
+            ptr::null()
+        }
+        None => {
+            *len = 0;
+
+            ptr::null()
+        }
+    }
+}
+
+/// Exports an image into `array` and `schema`, which must be released by the consumer. The image
+/// is consumed, even on failure. Returns 0 on success and -1 on failure, e.g. if an argument is
+/// null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_into_arrow(
+    image: *mut FfImage,
+    array: *mut FFI_ArrowArray,
+    schema: *mut FFI_ArrowSchema,
+) -> c_int {
+    if image.is_null() {
+        set_last_error(Report::msg("image is null"));
+
+        return -1;
+    }
+
+    let image = Box::from_raw(image).image;
+
+    match image
+        .into_arrow()
+        .and_then(|data| export(data, array, schema))
+    {
+        Ok(()) => 0,
+        Err(error) => {
+            set_last_error(error);
+
+            -1
+        }
+    }
+}
+
+/// Imports an image from `array` and `schema`, without copying the pixel data. Ownership of
+/// `array` is taken, even on failure: it is left released.
+#[no_mangle]
+pub unsafe extern "C" fn ff_image_from_arrow(
+    array: *mut FFI_ArrowArray,
+    schema: *const FFI_ArrowSchema,
+) -> *mut FfImage {
+    into_raw_or_null(
+        import(array, schema)
+            .and_then(Image::from_arrow)
+            .map(FfImage::new),
+    )
+}
+
+/// Bounding boxes, see `fastformat_datatypes::bbox::BBox`.
+pub struct FfBBox {
+    bbox: BBox<'static>,
+    labels: Vec<CString>,
+}
+
+impl FfBBox {
+    fn new(bbox: BBox<'static>) -> Self {
+        let labels = bbox.label.iter().map(|label| c_string(label)).collect();
+
+        Self { bbox, labels }
+    }
+}
+
+/// Creates `count` bounding boxes from a copy of their `4 * count` coordinates, `count`
+/// confidences and `count` labels. `encoding` is one of `"XYXY"`, `"XYWH"`, `"XYXYN"` or
+/// `"XYWHN"`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_new(
+    data: *const f32,
+    confidence: *const f32,
+    label: *const *const c_char,
+    count: usize,
+    encoding: *const c_char,
+) -> *mut FfBBox {
+    let bbox = || -> Result<FfBBox> {
+        let data_len = count
+            .checked_mul(4)
+            .ok_or_else(|| Report::msg(format!("Too many boxes: {}", count)))?;
+        let data = slice_arg("data", data, data_len)?.to_vec();
+        let confidence = slice_arg("confidence", confidence, count)?.to_vec();
+        let label = slice_arg("label", label, count)?
+            .iter()
+            .map(|&label| required_str_arg("label", label).map(str::to_string))
+            .collect::<Result<Vec<_>>>()?;
+
+        let bbox = match required_str_arg("encoding", encoding)? {
+            "XYXY" => BBox::new_xyxy(data, confidence, label),
+            "XYWH" => BBox::new_xywh(data, confidence, label),
+            "XYXYN" => BBox::new_xyxyn(data, confidence, label),
+            "XYWHN" => BBox::new_xywhn(data, confidence, label),
+            encoding => Err(Report::msg(format!("Invalid BBox encoding {}", encoding))),
+        }?;
+
+        Ok(FfBBox::new(bbox))
+    };
+
+    into_raw_or_null(bbox())
+}
+
+/// Frees bounding boxes. Does nothing if `bbox` is null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_free(bbox: *mut FfBBox) {
+    if !bbox.is_null() {
+        drop(Box::from_raw(bbox));
+    }
+}
+
+/// Returns the number of boxes, or 0 if `bbox` is null.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_len(bbox: *const FfBBox) -> usize {
+    handle_arg("bbox", bbox).map_or(0, |bbox| bbox.bbox.len())
+}
+
+/// Returns the `4 * ff_bbox_len` coordinates of the boxes, or null if `bbox` is null. They live
+/// as long as `bbox`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_data(bbox: *const FfBBox) -> *const f32 {
+    handle_arg("bbox", bbox).map_or(ptr::null(), |bbox| bbox.bbox.data.as_ptr())
+}
+
+/// Returns the `ff_bbox_len` confidences of the boxes, or null if `bbox` is null. They live as
+/// long as `bbox`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_confidence(bbox: *const FfBBox) -> *const f32 {
+    handle_arg("bbox", bbox).map_or(ptr::null(), |bbox| bbox.bbox.confidence.as_ptr())
+}
+
+/// Returns the label of the box at `index`, or null if it is out of range or if `bbox` is null.
+/// The string lives as long as `bbox`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_label(bbox: *const FfBBox, index: usize) -> *const c_char {
+    handle_arg("bbox", bbox)
+        .and_then(|bbox| bbox.labels.get(index))
+        .map_or(ptr::null(), |label| label.as_ptr())
+}
+
+/// Returns the encoding of the boxes, e.g. `"XYXY"`, or null if `bbox` is null. The string is
+/// static.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_encoding(bbox: *const FfBBox) -> *const c_char {
+    let Some(bbox) = handle_arg("bbox", bbox) else {
+        return ptr::null();
+    };
+
+    match bbox.bbox.encoding {
+        bbox::Encoding::XYXY => c"XYXY".as_ptr(),
+        bbox::Encoding::XYWH => c"XYWH".as_ptr(),
+        bbox::Encoding::XYXYN => c"XYXYN".as_ptr(),
+        bbox::Encoding::XYWHN => c"XYWHN".as_ptr(),
+    }
+}
+
+/// Exports bounding boxes into `array` and `schema`, like `ff_image_into_arrow`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_into_arrow(
+    bbox: *mut FfBBox,
+    array: *mut FFI_ArrowArray,
+    schema: *mut FFI_ArrowSchema,
+) -> c_int {
+    if bbox.is_null() {
+        set_last_error(Report::msg("bbox is null"));
+
+        return -1;
+    }
+
+    let bbox = Box::from_raw(bbox).bbox;
+
+    match bbox
+        .into_arrow()
+        .and_then(|data| export(data, array, schema))
+    {
+        Ok(()) => 0,
+        Err(error) => {
+            set_last_error(error);
+
+            -1
+        }
+    }
+}
+
+/// Imports bounding boxes from `array` and `schema`, like `ff_image_from_arrow`.
+#[no_mangle]
+pub unsafe extern "C" fn ff_bbox_from_arrow(
+    array: *mut FFI_ArrowArray,
+    schema: *const FFI_ArrowSchema,
+) -> *mut FfBBox {
+    into_raw_or_null(
+        import(array, schema)
+            .and_then(BBox::from_arrow)
+            .map(FfBBox::new),
+    )
+}
+
+unsafe fn export(
+    array_data: arrow::array::ArrayData,
+    array: *mut FFI_ArrowArray,
+    schema: *mut FFI_ArrowSchema,
+) -> Result<()> {
+    if array.is_null() || schema.is_null() {
+        return Err(Report::msg("array or schema is null"));
+    }
+
+    let ffi_schema = FFI_ArrowSchema::try_from(array_data.data_type())
+        .wrap_err("Failed to export the Arrow schema")?;
+
+    ptr::write(array, FFI_ArrowArray::new(&array_data));
+    ptr::write(schema, ffi_schema);
+
+    Ok(())
+}
+
+unsafe fn import(
+    array: *mut FFI_ArrowArray,
+    schema: *const FFI_ArrowSchema,
+) -> Result<arrow::array::ArrayData> {
+    if array.is_null() || schema.is_null() {
+        return Err(Report::msg("array or schema is null"));
+    }
+
+    let array = ptr::replace(array, FFI_ArrowArray::empty());
+
+    arrow::ffi::from_ffi(array, &*schema).wrap_err("Failed to import the Arrow array")
+}
+
+mod tests {
+    #[test]
+    fn test_image_arrow_round_trip() {
+        use crate::*;
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+
+        unsafe {
+            let image = ff_image_wrap(
+                flat_image.as_ptr(),
+                flat_image.len(),
+                3,
+                3,
+                c"RGB8".as_ptr(),
+                c"camera.test".as_ptr(),
+            );
+            assert!(!image.is_null());
+
+            let mut array = FFI_ArrowArray::empty();
+            let mut schema = FFI_ArrowSchema::empty();
+            assert_eq!(ff_image_into_arrow(image, &mut array, &mut schema), 0);
+
+            let image = ff_image_from_arrow(&mut array, &schema);
+            assert!(!image.is_null());
+
+            let mut len = 0;
+            let data = ff_image_data(image, &mut len);
+
+            assert_eq!(std::slice::from_raw_parts(data, len), &flat_image);
+            assert_eq!(ff_image_width(image), 3);
+            assert_eq!(CStr::from_ptr(ff_image_encoding(image)), c"RGB8");
+            assert_eq!(CStr::from_ptr(ff_image_name(image)), c"camera.test");
+
+            ff_image_free(image);
+        }
+    }
+
+    #[test]
+    fn test_image_error() {
+        use crate::*;
+
+        unsafe {
+            let image = ff_image_new([0u8; 26].as_ptr(), 26, 3, 3, c"RGB8".as_ptr(), ptr::null());
+
+            assert!(image.is_null());
+            assert!(!ff_last_error().is_null());
+
+            let mut array = FFI_ArrowArray::empty();
+            let mut schema = FFI_ArrowSchema::empty();
+            assert_eq!(
+                ff_image_into_arrow(ptr::null_mut(), &mut array, &mut schema),
+                -1
+            );
+            assert_eq!(
+                ff_bbox_into_arrow(ptr::null_mut(), &mut array, &mut schema),
+                -1
+            );
+
+            let bbox = ff_bbox_new(
+                [0.0].as_ptr(),
+                [0.0].as_ptr(),
+                [c"cat".as_ptr()].as_ptr(),
+                usize::MAX,
+                c"XYXY".as_ptr(),
+            );
+            assert!(bbox.is_null());
+
+            let mut len = 1;
+            assert!(ff_image_data(ptr::null(), &mut len).is_null());
+            assert_eq!(len, 0);
+            assert_eq!(
+                CStr::from_ptr(ff_last_error()).to_str().unwrap(),
+                "image is null"
+            );
+            assert_eq!(ff_image_width(ptr::null()), 0);
+            assert!(ff_image_encoding(ptr::null()).is_null());
+            assert!(ff_image_name(ptr::null()).is_null());
+            assert_eq!(ff_bbox_len(ptr::null()), 0);
+            assert!(ff_bbox_data(ptr::null()).is_null());
+            assert!(ff_bbox_label(ptr::null(), 0).is_null());
+            assert!(ff_bbox_encoding(ptr::null()).is_null());
+
+            let image = ff_image_new([0u8; 4].as_ptr(), 4, 2, 2, c"GRAY8".as_ptr(), ptr::null());
+            assert!(ff_image_data(image, ptr::null_mut()).is_null());
+            assert_eq!(
+                CStr::from_ptr(ff_last_error()).to_str().unwrap(),
+                "len is null"
+            );
+
+            ff_image_free(image);
+        }
+    }
+
+    #[test]
+    fn test_bbox_arrow_round_trip() {
+        use crate::*;
+
+        let data = [1.0, 1.0, 2.0, 2.0];
+        let confidence = [0.98];
+        let label = [c"cat".as_ptr()];
+
+        unsafe {
+            let bbox = ff_bbox_new(
+                data.as_ptr(),
+                confidence.as_ptr(),
+                label.as_ptr(),
+                1,
+                c"XYXY".as_ptr(),
+            );
+            assert!(!bbox.is_null());
+
+            let mut array = FFI_ArrowArray::empty();
+            let mut schema = FFI_ArrowSchema::empty();
+            assert_eq!(ff_bbox_into_arrow(bbox, &mut array, &mut schema), 0);
+
+            let bbox = ff_bbox_from_arrow(&mut array, &schema);
+            assert!(!bbox.is_null());
+
+            assert_eq!(ff_bbox_len(bbox), 1);
+            assert_eq!(std::slice::from_raw_parts(ff_bbox_data(bbox), 4), &data);
+            assert_eq!(CStr::from_ptr(ff_bbox_label(bbox, 0)), c"cat");
+            assert!(ff_bbox_label(bbox, 1).is_null());
+            assert_eq!(CStr::from_ptr(ff_bbox_encoding(bbox)), c"XYXY");
+
+            ff_bbox_free(bbox);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Generates fastformat datatypes from a declarative definition, to call from a `build.rs`:
+//!
+//! ```no_run
+//! // In `fn main` of build.rs:
+//! fastformat_codegen::Generator::new()
+//!     .compile("datatypes/imu.ff")
+//!     .unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/main.rs
+//! include!(concat!(env!("OUT_DIR"), "/imu.rs"));
+//! ```
+//!
+//! Each `datatype` becomes a struct with a `header`, the `raw_data`, `view_from_raw_data`,
+//! `from_arrow` and `into_arrow` methods of the built-in datatypes, and implementations of
+//! `Viewable` and `HasHeader`. See [`parse`] for the definition language.
+
+pub mod parse;
+mod python;
+pub mod rust;
+
+use eyre::{Context, Report, Result};
+
+use std::path::{Path, PathBuf};
+
+/// Generator of the Rust code of datatype definitions.
+#[derive(Debug, Clone, Default)]
+pub struct Generator {
+    python: bool,
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also generates pyo3 conversions of every datatype to and from a Python `dict`.
+    pub fn python(mut self, python: bool) -> Self {
+        self.python = python;
+
+        self
+    }
+
+    /// Generates the Rust code of the datatypes defined in `source`.
+    pub fn generate(&self, source: &str) -> Result<String> {
+        Ok(rust::generate(&parse::parse(source)?, self.python))
+    }
+
+    /// Generates the Rust code of the definition file at `path` into `$OUT_DIR`, in a file
+    /// named after it, e.g. `imu.rs` for `imu.ff`. Returns the path of the generated file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `OUT_DIR` isn't set, i.e. outside of a build script, or if the file
+    /// can't be read or parsed.
+    pub fn compile(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let out_dir = std::env::var_os("OUT_DIR")
+            .ok_or_else(|| Report::msg("OUT_DIR isn't set, compile must run in a build script"))?;
+
+        self.compile_to(path, out_dir)
+    }
+
+    /// Like [`Generator::compile`], into `out_dir`.
+    pub fn compile_to(&self, path: impl AsRef<Path>, out_dir: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref();
+
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let source = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let code = self
+            .generate(&source)
+            .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+
+        let stem = path
+            .file_stem()
+            .ok_or_else(|| Report::msg(format!("Invalid definition file {}", path.display())))?;
+        let output = out_dir.as_ref().join(stem).with_extension("rs");
+
+        std::fs::write(&output, code)
+            .wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+
+        Ok(output)
+    }
+}
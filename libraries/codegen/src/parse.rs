@@ -0,0 +1,507 @@
+//! Parser of the datatype definition language:
+//!
+//! ```text
+//! /// Inertial measurement of an IMU.
+//! datatype Imu {
+//!     /// Linear acceleration on the x, y and z axes, in m/s².
+//!     acceleration: [f32],
+//!     temperature: f32?,
+//!     frame: string,
+//! }
+//! ```
+//!
+//! A field is a scalar (`f32`), an optional scalar (`f32?`) or an array (`[f32]`) of one of the
+//! primitive types `u8` to `u64`, `i8` to `i64`, `f32`, `f64`, or `string`. `///` comments
+//! document the next datatype or field, `//` comments are ignored.
+
+use eyre::{Report, Result};
+
+/// Primitive type of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    String,
+}
+
+impl Primitive {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "u8" => Some(Self::U8),
+            "u16" => Some(Self::U16),
+            "u32" => Some(Self::U32),
+            "u64" => Some(Self::U64),
+            "i8" => Some(Self::I8),
+            "i16" => Some(Self::I16),
+            "i32" => Some(Self::I32),
+            "i64" => Some(Self::I64),
+            "f32" => Some(Self::F32),
+            "f64" => Some(Self::F64),
+            "string" => Some(Self::String),
+            _ => None,
+        }
+    }
+
+    /// Rust type of one value.
+    pub fn rust(&self) -> &'static str {
+        match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::String => "String",
+        }
+    }
+
+    /// Name of the Arrow `DataType` variant.
+    pub fn arrow(&self) -> &'static str {
+        match self {
+            Self::U8 => "UInt8",
+            Self::U16 => "UInt16",
+            Self::U32 => "UInt32",
+            Self::U64 => "UInt64",
+            Self::I8 => "Int8",
+            Self::I16 => "Int16",
+            Self::I32 => "Int32",
+            Self::I64 => "Int64",
+            Self::F32 => "Float32",
+            Self::F64 => "Float64",
+            Self::String => "Utf8",
+        }
+    }
+}
+
+/// Shape of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Exactly one value, e.g. `f32`.
+    Scalar,
+    /// Zero or one value, e.g. `f32?`.
+    Optional,
+    /// Any number of values, e.g. `[f32]`.
+    Array,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub primitive: Primitive,
+    pub kind: Kind,
+    pub doc: Vec<String>,
+}
+
+impl Field {
+    /// Whether the field borrows from the Arrow buffers in a view, i.e. is an array of numbers.
+    pub fn is_borrowed(&self) -> bool {
+        self.kind == Kind::Array && self.primitive != Primitive::String
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Datatype {
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub doc: Vec<String>,
+}
+
+impl Datatype {
+    /// Whether the generated struct has a lifetime, i.e. whether a field borrows in a view.
+    pub fn is_borrowed(&self) -> bool {
+        self.fields.iter().any(Field::is_borrowed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Doc(String),
+    Symbol(char),
+}
+
+/// Splits `source` into tokens, each with its line number.
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let mut rest = line.trim_start();
+
+        while !rest.is_empty() {
+            if let Some(doc) = rest.strip_prefix("///") {
+                tokens.push((number, Token::Doc(doc.trim().to_string())));
+                break;
+            } else if rest.starts_with("//") {
+                break;
+            }
+
+            let character = rest.chars().next().unwrap_or_default();
+
+            if character.is_ascii_alphanumeric() || character == '_' {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+
+                tokens.push((number, Token::Ident(rest[..end].to_string())));
+                rest = &rest[end..];
+            } else if "{}[]:?,".contains(character) {
+                tokens.push((number, Token::Symbol(character)));
+                rest = &rest[1..];
+            } else {
+                return Err(Report::msg(format!(
+                    "line {}: unexpected character {:?}",
+                    number, character
+                )));
+            }
+
+            rest = rest.trim_start();
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Words that can't be field names, as they aren't valid Rust identifiers: the strict and
+/// reserved keywords, including `gen`, reserved since the 2024 edition.
+const KEYWORDS: [&str; 52] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Names of the prelude that can't be datatype names, as the generated code uses them
+/// unqualified.
+const PRELUDE: [&str; 39] = [
+    "AsMut",
+    "AsRef",
+    "Box",
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "Err",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Hash",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "None",
+    "Ok",
+    "Option",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Result",
+    "Send",
+    "Sized",
+    "Some",
+    "String",
+    "Sync",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Vec",
+];
+
+/// Field names taken by the `Header` every datatype carries.
+const RESERVED: [&str; 4] = ["header", "timestamp_ns", "frame_id", "sequence"];
+
+struct Parser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<(usize, Token)>>,
+    line: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let (line, token) = self.tokens.next()?;
+        self.line = line;
+
+        Some(token)
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> Report {
+        Report::msg(format!("line {}: {}", self.line, message))
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<()> {
+        match self.next() {
+            Some(Token::Symbol(found)) if found == symbol => Ok(()),
+            found => Err(self.error(format!("expected `{}`, found {:?}", symbol, found))),
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Ident(ident)) => Ok(ident),
+            found => Err(self.error(format!("expected a name, found {:?}", found))),
+        }
+    }
+
+    fn docs(&mut self) -> Vec<String> {
+        let mut docs = Vec::new();
+
+        while let Some((_, Token::Doc(_))) = self.tokens.peek() {
+            if let Some(Token::Doc(doc)) = self.next() {
+                docs.push(doc);
+            }
+        }
+
+        docs
+    }
+
+    fn primitive(&mut self) -> Result<Primitive> {
+        let name = self.expect_ident()?;
+
+        Primitive::from_name(&name).ok_or_else(|| self.error(format!("unknown type {}", name)))
+    }
+
+    fn field(&mut self, doc: Vec<String>) -> Result<Field> {
+        let name = self.expect_ident()?;
+
+        if KEYWORDS.contains(&name.as_str()) || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.error(format!("invalid field name {}", name)));
+        } else if RESERVED.contains(&name.as_str()) {
+            return Err(self.error(format!("field name {} is reserved for the header", name)));
+        }
+
+        self.expect_symbol(':')?;
+
+        let (primitive, kind) = if let Some((_, Token::Symbol('['))) = self.tokens.peek() {
+            self.next();
+            let primitive = self.primitive()?;
+            self.expect_symbol(']')?;
+
+            (primitive, Kind::Array)
+        } else {
+            let primitive = self.primitive()?;
+
+            match self.tokens.peek() {
+                Some((_, Token::Symbol('?'))) => {
+                    self.next();
+
+                    (primitive, Kind::Optional)
+                }
+                _ => (primitive, Kind::Scalar),
+            }
+        };
+
+        if let Some((_, Token::Symbol(','))) = self.tokens.peek() {
+            self.next();
+        }
+
+        Ok(Field {
+            name,
+            primitive,
+            kind,
+            doc,
+        })
+    }
+
+    fn datatype(&mut self, doc: Vec<String>) -> Result<Datatype> {
+        match self.next() {
+            Some(Token::Ident(keyword)) if keyword == "datatype" => {}
+            found => return Err(self.error(format!("expected `datatype`, found {:?}", found))),
+        }
+
+        let name = self.expect_ident()?;
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Err(self.error(format!(
+                "datatype name {} must start with an uppercase letter",
+                name
+            )));
+        } else if KEYWORDS.contains(&name.as_str()) || PRELUDE.contains(&name.as_str()) {
+            return Err(self.error(format!(
+                "datatype name {} collides with a Rust keyword or prelude name",
+                name
+            )));
+        }
+
+        self.expect_symbol('{')?;
+
+        let mut fields = Vec::<Field>::new();
+        loop {
+            let doc = self.docs();
+
+            if let Some((_, Token::Symbol('}'))) = self.tokens.peek() {
+                self.next();
+                break;
+            }
+
+            let field = self.field(doc)?;
+            if fields.iter().any(|other| other.name == field.name) {
+                return Err(self.error(format!("duplicate field {} in {}", field.name, name)));
+            }
+
+            fields.push(field);
+        }
+
+        if fields.is_empty() {
+            return Err(self.error(format!("datatype {} has no fields", name)));
+        }
+
+        Ok(Datatype { name, fields, doc })
+    }
+}
+
+/// Parses the datatype definitions of `source`.
+///
+/// # Errors
+///
+/// Returns an error with the line number of the first syntax error, unknown type, invalid or
+/// duplicate name.
+pub fn parse(source: &str) -> Result<Vec<Datatype>> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?.into_iter().peekable(),
+        line: 0,
+    };
+
+    let mut datatypes = Vec::<Datatype>::new();
+    loop {
+        let doc = parser.docs();
+
+        if parser.tokens.peek().is_none() {
+            break;
+        }
+
+        let datatype = parser.datatype(doc)?;
+        if datatypes.iter().any(|other| other.name == datatype.name) {
+            return Err(parser.error(format!("duplicate datatype {}", datatype.name)));
+        }
+
+        datatypes.push(datatype);
+    }
+
+    Ok(datatypes)
+}
+
+mod tests {
+    #[test]
+    fn test_parse() {
+        use crate::parse::{parse, Datatype, Field, Kind, Primitive};
+
+        let datatypes = parse(
+            "// Sensors of the robot.
+
+            /// Inertial measurement.
+            datatype Imu {
+                /// In m/s².
+                acceleration: [f32],
+                temperature: f32?
+                labels: [string]
+            }
+
+            datatype Status { code: u16 }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            datatypes,
+            vec![
+                Datatype {
+                    name: "Imu".to_string(),
+                    fields: vec![
+                        Field {
+                            name: "acceleration".to_string(),
+                            primitive: Primitive::F32,
+                            kind: Kind::Array,
+                            doc: vec!["In m/s².".to_string()],
+                        },
+                        Field {
+                            name: "temperature".to_string(),
+                            primitive: Primitive::F32,
+                            kind: Kind::Optional,
+                            doc: vec![],
+                        },
+                        Field {
+                            name: "labels".to_string(),
+                            primitive: Primitive::String,
+                            kind: Kind::Array,
+                            doc: vec![],
+                        },
+                    ],
+                    doc: vec!["Inertial measurement.".to_string()],
+                },
+                Datatype {
+                    name: "Status".to_string(),
+                    fields: vec![Field {
+                        name: "code".to_string(),
+                        primitive: Primitive::U16,
+                        kind: Kind::Scalar,
+                        doc: vec![],
+                    }],
+                    doc: vec![],
+                }
+            ]
+        );
+        assert!(datatypes[0].is_borrowed());
+        assert!(!datatypes[1].is_borrowed());
+
+        let error = |source| parse(source).unwrap_err().to_string();
+
+        assert_eq!(
+            error("datatype Imu {\n x: f16 }"),
+            "line 2: unknown type f16"
+        );
+        assert_eq!(
+            error("datatype Imu { x: f32, x: f64 }"),
+            "line 1: duplicate field x in Imu"
+        );
+        assert_eq!(
+            error("datatype Imu { type: f32 }"),
+            "line 1: invalid field name type"
+        );
+        assert_eq!(
+            error("datatype Imu { frame_id: string }"),
+            "line 1: field name frame_id is reserved for the header"
+        );
+        assert_eq!(
+            error("datatype Imu {\n}"),
+            "line 2: datatype Imu has no fields"
+        );
+        assert_eq!(
+            error("datatype imu {}"),
+            "line 1: datatype name imu must start with an uppercase letter"
+        );
+        assert_eq!(
+            error("datatype Imu { box: f32 }"),
+            "line 1: invalid field name box"
+        );
+        assert_eq!(
+            error("datatype Option { x: f32 }"),
+            "line 1: datatype name Option collides with a Rust keyword or prelude name"
+        );
+        assert_eq!(
+            error("datatype Self { x: f32 }"),
+            "line 1: datatype name Self collides with a Rust keyword or prelude name"
+        );
+        assert_eq!(
+            error("datatype Imu { x = 1 }"),
+            "line 1: unexpected character '='"
+        );
+    }
+}
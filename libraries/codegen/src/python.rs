@@ -0,0 +1,140 @@
+//! pyo3 conversions of a parsed [`Datatype`] to and from a Python `dict` with one key per
+//! field, like `KeyValueMap`. Arrays become lists, and missing optional fields `None`. The
+//! header isn't part of the `dict`.
+
+use crate::{
+    parse::{Datatype, Kind, Primitive},
+    rust::{lifetime, rust_type},
+};
+
+use std::fmt::Write;
+
+pub fn generate(datatype: &Datatype) -> String {
+    let name = &datatype.name;
+    let mut code = String::new();
+
+    let _ = writeln!(
+        code,
+        "impl ::pyo3::ToPyObject for {}{} {{",
+        name,
+        lifetime(datatype, "'_")
+    );
+    let _ = writeln!(
+        code,
+        "    fn to_object(&self, py: ::pyo3::Python<'_>) -> ::pyo3::PyObject {{"
+    );
+    let _ = writeln!(
+        code,
+        "        use ::pyo3::{{types::PyDictMethods, IntoPy}};\n"
+    );
+    let _ = writeln!(
+        code,
+        "        let dict = ::pyo3::types::PyDict::new_bound(py);"
+    );
+    let _ = writeln!(
+        code,
+        "\n        // Inserting a `str` key in a new `dict` can't fail."
+    );
+    for field in &datatype.fields {
+        let value = match (field.kind, field.primitive) {
+            (_, Primitive::String) => {
+                format!("&self.{}", field.name)
+            }
+            (Kind::Array, _) => format!("self.{}.as_ref()", field.name),
+            _ => format!("self.{}", field.name),
+        };
+
+        let _ = writeln!(
+            code,
+            "        let _ = dict.set_item({:?}, {});",
+            field.name, value
+        );
+    }
+    let _ = writeln!(code, "\n        dict.into_py(py)");
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}\n");
+
+    let _ = writeln!(
+        code,
+        "impl ::pyo3::IntoPy<::pyo3::PyObject> for {}{} {{",
+        name,
+        lifetime(datatype, "'_")
+    );
+    let _ = writeln!(
+        code,
+        "    fn into_py(self, py: ::pyo3::Python<'_>) -> ::pyo3::PyObject {{"
+    );
+    let _ = writeln!(code, "        ::pyo3::ToPyObject::to_object(&self, py)");
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}\n");
+
+    let _ = writeln!(
+        code,
+        "impl<'py> ::pyo3::FromPyObject<'py> for {}{} {{",
+        name,
+        lifetime(datatype, "'static")
+    );
+    let _ = writeln!(
+        code,
+        "    fn extract_bound(dict: &::pyo3::Bound<'py, ::pyo3::PyAny>) -> ::pyo3::PyResult<Self> {{"
+    );
+    let _ = writeln!(
+        code,
+        "        use ::pyo3::types::{{PyAnyMethods, PyDictMethods}};\n"
+    );
+    let _ = writeln!(
+        code,
+        "        let dict = dict.downcast::<::pyo3::types::PyDict>()?;\n"
+    );
+    let _ = writeln!(code, "        Ok(Self {{");
+    for field in &datatype.fields {
+        let item = format!("dict.get_item({:?})?", field.name);
+
+        let value = match field.kind {
+            Kind::Optional => format!(
+                "{}\n                .map(|value| value.extract::<{}>())\n                .transpose()?\n                .flatten()",
+                item,
+                rust_type(field)
+            ),
+            _ => {
+                let extract = if field.is_borrowed() {
+                    format!(
+                        "::std::borrow::Cow::Owned(value.extract::<Vec<{}>>()?)",
+                        field.primitive.rust()
+                    )
+                } else {
+                    format!("value.extract::<{}>()?", rust_type(field))
+                };
+
+                format!(
+                    "match {} {{\n                Some(value) => {},\n                None => return Err(::pyo3::exceptions::PyKeyError::new_err({:?})),\n            }}",
+                    item, extract, field.name
+                )
+            }
+        };
+
+        let _ = writeln!(code, "            {}: {},", field.name, value);
+    }
+    let _ = writeln!(code, "            header: None,");
+    let _ = writeln!(code, "        }})");
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}");
+
+    code
+}
+
+mod tests {
+    #[test]
+    fn test_generate_python() {
+        use crate::{parse::parse, rust::generate};
+
+        let datatypes = parse("datatype Imu { acceleration: [f32], temperature: f32? }").unwrap();
+        let code = generate(&datatypes, true);
+
+        assert!(code.contains("impl ::pyo3::ToPyObject for Imu<'_> {"));
+        assert!(code.contains("impl<'py> ::pyo3::FromPyObject<'py> for Imu<'static> {"));
+        assert!(code
+            .contains("Some(value) => ::std::borrow::Cow::Owned(value.extract::<Vec<f32>>()?),"));
+        assert!(code.contains("PyKeyError::new_err(\"acceleration\")"));
+    }
+}
@@ -0,0 +1,487 @@
+//! Rust code of a parsed [`Datatype`]: the struct, its Arrow conversions, and its `Viewable`,
+//! `HasHeader` and, optionally, pyo3 implementations.
+//!
+//! The generated code names every item by its full path, so that it can be `include!`d anywhere
+//! in a crate depending on `arrow`, `eyre`, `fastformat-converter` and `fastformat-datatypes`
+//! (with its `arrow` feature), plus `pyo3` for the Python conversions.
+
+use crate::parse::{Datatype, Field, Kind, Primitive};
+
+use std::fmt::Write;
+
+const BUILDER: &str = "::fastformat_converter::arrow::FastFormatArrowBuilder";
+const RAW_DATA: &str = "::fastformat_converter::arrow::FastFormatArrowRawData";
+const HEADER: &str = "::fastformat_datatypes::header";
+
+/// Generates the code of `datatypes`, with pyo3 `dict` conversions if `python` is set.
+pub fn generate(datatypes: &[Datatype], python: bool) -> String {
+    let mut code = String::from("// Generated by fastformat-codegen, do not edit.\n");
+
+    for datatype in datatypes {
+        code.push('\n');
+        code.push_str(&structure(datatype));
+        code.push('\n');
+        code.push_str(&arrow(datatype));
+        code.push('\n');
+        code.push_str(&traits(datatype));
+
+        if python {
+            code.push('\n');
+            code.push_str(&crate::python::generate(datatype));
+        }
+    }
+
+    code
+}
+
+/// Generic parameters of the struct, `<'a>` if it borrows from the Arrow buffers.
+pub(crate) fn lifetime(datatype: &Datatype, lifetime: &str) -> String {
+    if datatype.is_borrowed() {
+        format!("<{}>", lifetime)
+    } else {
+        String::new()
+    }
+}
+
+fn docs(code: &mut String, docs: &[String], indent: &str) {
+    for doc in docs {
+        if doc.is_empty() {
+            let _ = writeln!(code, "{}///", indent);
+        } else {
+            let _ = writeln!(code, "{}/// {}", indent, doc);
+        }
+    }
+}
+
+/// Rust type of a field of the struct.
+pub(crate) fn rust_type(field: &Field) -> String {
+    let primitive = field.primitive.rust();
+
+    match field.kind {
+        Kind::Scalar => primitive.to_string(),
+        Kind::Optional => format!("Option<{}>", primitive),
+        Kind::Array if field.is_borrowed() => format!("::std::borrow::Cow<'a, [{}]>", primitive),
+        Kind::Array => format!("Vec<{}>", primitive),
+    }
+}
+
+/// Arrow type of a primitive, e.g. `::arrow::datatypes::Float32Type`.
+fn arrow_type(primitive: Primitive) -> String {
+    format!("::arrow::datatypes::{}Type", primitive.arrow())
+}
+
+/// Arrow `DataType` of a primitive, e.g. `::arrow::datatypes::DataType::Float32`.
+fn data_type(primitive: Primitive) -> String {
+    format!("::arrow::datatypes::DataType::{}", primitive.arrow())
+}
+
+fn structure(datatype: &Datatype) -> String {
+    let mut code = String::new();
+
+    docs(&mut code, &datatype.doc, "");
+    let _ = writeln!(code, "#[derive(Debug, Clone, Default, PartialEq)]");
+    let _ = writeln!(
+        code,
+        "pub struct {}{} {{",
+        datatype.name,
+        lifetime(datatype, "'a")
+    );
+
+    for field in &datatype.fields {
+        docs(&mut code, &field.doc, "    ");
+        let _ = writeln!(code, "    pub {}: {},", field.name, rust_type(field));
+    }
+
+    let _ = writeln!(code, "    pub header: Option<{}::Header>,", HEADER);
+    let _ = writeln!(code, "}}");
+
+    code
+}
+
+/// Expression reading `field` from `raw_data`, borrowing arrays of numbers if `borrow` is set,
+/// taking their buffer otherwise.
+fn read(field: &Field, borrow: bool) -> String {
+    let name = &field.name;
+
+    match (field.kind, field.primitive) {
+        (Kind::Scalar, Primitive::String) => format!("raw_data.utf8_singleton({:?})?", name),
+        (Kind::Scalar, primitive) => format!(
+            "raw_data.primitive_singleton::<{}>({:?})?",
+            arrow_type(primitive),
+            name
+        ),
+        (Kind::Optional, Primitive::String) => {
+            format!("raw_data.utf8_array({:?})?.into_iter().next()", name)
+        }
+        (Kind::Optional, primitive) => format!(
+            "raw_data.primitive_array_view::<{}>({:?})?.first().copied()",
+            arrow_type(primitive),
+            name
+        ),
+        (Kind::Array, Primitive::String) => format!("raw_data.utf8_array({:?})?", name),
+        (Kind::Array, primitive) if borrow => format!(
+            "::std::borrow::Cow::Borrowed(raw_data.primitive_array_view::<{}>({:?})?)",
+            arrow_type(primitive),
+            name
+        ),
+        (Kind::Array, primitive) => format!(
+            "::std::borrow::Cow::Owned(Self::take_or_copy::<{}>(&mut raw_data, {:?})?)",
+            arrow_type(primitive),
+            name
+        ),
+    }
+}
+
+/// Builder call pushing `field` of `self`.
+fn push(field: &Field) -> String {
+    let name = &field.name;
+
+    match (field.kind, field.primitive) {
+        (Kind::Scalar, Primitive::String) => format!(
+            ".push_utf_singleton({:?}, &self.{}, {}, false)",
+            name,
+            name,
+            data_type(Primitive::String)
+        ),
+        (Kind::Scalar, primitive) => format!(
+            ".push_primitive_singleton::<{}>({:?}, self.{}, {}, false)",
+            arrow_type(primitive),
+            name,
+            name,
+            data_type(primitive)
+        ),
+        (Kind::Optional, Primitive::String) => format!(
+            ".push_utf_array({:?}, self.{}.into_iter().collect(), {}, false)",
+            name,
+            name,
+            data_type(Primitive::String)
+        ),
+        (Kind::Optional, primitive) => format!(
+            ".push_primitive_array::<{}>({:?}, self.{}.into_iter().collect(), {}, false)",
+            arrow_type(primitive),
+            name,
+            name,
+            data_type(primitive)
+        ),
+        (Kind::Array, Primitive::String) => format!(
+            ".push_utf_array({:?}, self.{}, {}, false)",
+            name,
+            name,
+            data_type(Primitive::String)
+        ),
+        (Kind::Array, primitive) => format!(
+            ".push_primitive_array::<{}>({:?}, self.{}.into_owned(), {}, false)",
+            arrow_type(primitive),
+            name,
+            name,
+            data_type(primitive)
+        ),
+    }
+}
+
+fn arrow(datatype: &Datatype) -> String {
+    let name = &datatype.name;
+    let borrowed = datatype.is_borrowed();
+    let mut code = String::new();
+
+    let _ = writeln!(
+        code,
+        "impl{} {}{} {{",
+        lifetime(datatype, "'a"),
+        name,
+        lifetime(datatype, "'a")
+    );
+
+    // Takes the buffer of an array if the raw data owns it, copies it otherwise.
+    if borrowed {
+        let _ = writeln!(
+            code,
+            "    fn take_or_copy<T: ::arrow::datatypes::ArrowPrimitiveType>(\n        raw_data: &mut {},\n        field: &str,\n    ) -> ::eyre::Result<Vec<T::Native>> {{",
+            RAW_DATA
+        );
+        let _ = writeln!(
+            code,
+            "        if let Ok(data) = raw_data.primitive_array::<T>(field) {{"
+        );
+        let _ = writeln!(
+            code,
+            "            ::fastformat_converter::accounting::record({:?}, ::std::mem::size_of_val(&data[..]), false);\n",
+            format!("{}::from_raw_data", name)
+        );
+        let _ = writeln!(code, "            return Ok(data);");
+        let _ = writeln!(code, "        }}\n");
+        let _ = writeln!(
+            code,
+            "        let data = raw_data.primitive_array_view::<T>(field)?;\n"
+        );
+        let _ = writeln!(
+            code,
+            "        ::fastformat_converter::policy::check({:?}, ::std::mem::size_of_val(data))?;",
+            format!("{}::from_raw_data", name)
+        );
+        let _ = writeln!(
+            code,
+            "        ::fastformat_converter::accounting::record({:?}, ::std::mem::size_of_val(data), true);\n",
+            format!("{}::from_raw_data", name)
+        );
+        let _ = writeln!(code, "        Ok(data.to_vec())");
+        let _ = writeln!(code, "    }}\n");
+    }
+
+    // Schema, built once and shared by every `into_arrow` call.
+    let _ = writeln!(
+        code,
+        "    fn union_fields() -> ::arrow::datatypes::UnionFields {{"
+    );
+    let _ = writeln!(
+        code,
+        "        static UNION_FIELDS: ::std::sync::OnceLock<::arrow::datatypes::UnionFields> =\n            ::std::sync::OnceLock::new();\n"
+    );
+    let _ = writeln!(code, "        UNION_FIELDS");
+    let _ = writeln!(code, "            .get_or_init(|| {{");
+    let _ = writeln!(code, "                {}::schema(", BUILDER);
+    let _ = writeln!(code, "                    &[");
+    let _ = writeln!(code, "                        &[");
+    for field in &datatype.fields {
+        let _ = writeln!(
+            code,
+            "                            ({:?}, {}, false),",
+            field.name,
+            data_type(field.primitive)
+        );
+    }
+    let _ = writeln!(code, "                        ][..],");
+    let _ = writeln!(code, "                        &{}::fields(),", HEADER);
+    let _ = writeln!(code, "                    ]");
+    let _ = writeln!(code, "                    .concat(),");
+    let _ = writeln!(code, "                )");
+    let _ = writeln!(code, "            }})");
+    let _ = writeln!(code, "            .clone()");
+    let _ = writeln!(code, "    }}\n");
+
+    // Raw data.
+    let _ = writeln!(
+        code,
+        "    /// Extracts raw data from an Arrow `ArrayData` produced by [`{}::into_arrow`].",
+        name
+    );
+    let _ = writeln!(
+        code,
+        "    pub fn raw_data(array_data: ::arrow::array::ArrayData) -> ::eyre::Result<{}> {{",
+        RAW_DATA
+    );
+    let _ = write!(
+        code,
+        "        let raw_data = {}::new(array_data)?",
+        RAW_DATA
+    );
+    for field in &datatype.fields {
+        match field.primitive {
+            Primitive::String => {
+                let _ = write!(code, "\n            .load_utf({:?})?", field.name);
+            }
+            primitive => {
+                let _ = write!(
+                    code,
+                    "\n            .load_primitive::<{}>({:?})?",
+                    arrow_type(primitive),
+                    field.name
+                );
+            }
+        }
+    }
+    let _ = writeln!(code, ";\n");
+    let _ = writeln!(code, "        {}::load(raw_data)", HEADER);
+    let _ = writeln!(code, "    }}\n");
+
+    // View.
+    if borrowed {
+        let _ = writeln!(
+            code,
+            "    /// Creates a read-only view of a `{}` from `FastFormatArrowRawData`, borrowing its\n    /// arrays of numbers from the Arrow buffers.",
+            name
+        );
+        let _ = writeln!(
+            code,
+            "    pub fn view_from_raw_data(raw_data: &'a {}) -> ::eyre::Result<Self> {{",
+            RAW_DATA
+        );
+    } else {
+        let _ = writeln!(
+            code,
+            "    /// Reads a `{}` from `FastFormatArrowRawData`.",
+            name
+        );
+        let _ = writeln!(
+            code,
+            "    pub fn view_from_raw_data(raw_data: &{}) -> ::eyre::Result<Self> {{",
+            RAW_DATA
+        );
+    }
+    let _ = writeln!(code, "        Ok(Self {{");
+    for field in &datatype.fields {
+        let _ = writeln!(code, "            {}: {},", field.name, read(field, true));
+    }
+    let _ = writeln!(code, "            header: {}::read(raw_data)?,", HEADER);
+    let _ = writeln!(code, "        }})");
+    let _ = writeln!(code, "    }}\n");
+
+    // Owned.
+    let owned = format!("{}{}", name, lifetime(datatype, "'static"));
+    if borrowed {
+        let _ = writeln!(
+            code,
+            "    /// Converts `FastFormatArrowRawData` into an owned `{}`, taking the buffers of its\n    /// arrays of numbers.",
+            name
+        );
+        let _ = writeln!(
+            code,
+            "    pub fn from_raw_data(mut raw_data: {}) -> ::eyre::Result<{}> {{",
+            RAW_DATA, owned
+        );
+        let _ = writeln!(code, "        Ok({} {{", name);
+        for field in &datatype.fields {
+            let _ = writeln!(code, "            {}: {},", field.name, read(field, false));
+        }
+        let _ = writeln!(code, "            header: {}::read(&raw_data)?,", HEADER);
+        let _ = writeln!(code, "        }})");
+    } else {
+        let _ = writeln!(
+            code,
+            "    pub fn from_raw_data(raw_data: {}) -> ::eyre::Result<Self> {{",
+            RAW_DATA
+        );
+        let _ = writeln!(code, "        Self::view_from_raw_data(&raw_data)");
+    }
+    let _ = writeln!(code, "    }}\n");
+
+    let _ = writeln!(
+        code,
+        "    pub fn from_arrow(array_data: ::arrow::array::ArrayData) -> ::eyre::Result<{}> {{",
+        owned
+    );
+    let _ = writeln!(
+        code,
+        "        {}::from_raw_data(Self::raw_data(array_data)?)",
+        name
+    );
+    let _ = writeln!(code, "    }}\n");
+
+    // Into Arrow.
+    let _ = writeln!(
+        code,
+        "    pub fn into_arrow(self) -> ::eyre::Result<::arrow::array::ArrayData> {{"
+    );
+    let _ = write!(
+        code,
+        "        let builder = {}::with_schema(Self::union_fields())",
+        BUILDER
+    );
+    for field in &datatype.fields {
+        let _ = write!(code, "\n            {}", push(field));
+    }
+    let _ = writeln!(code, ";\n");
+    let _ = writeln!(
+        code,
+        "        {}::push(builder, self.header.as_ref()).into_arrow()",
+        HEADER
+    );
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}");
+
+    code
+}
+
+fn traits(datatype: &Datatype) -> String {
+    let name = &datatype.name;
+    let anonymous = format!("{}{}", name, lifetime(datatype, "'_"));
+    let view = |lifetime_name| format!("{}{}", name, lifetime(datatype, lifetime_name));
+    let mut code = String::new();
+
+    let _ = writeln!(
+        code,
+        "impl ::fastformat_datatypes::owned_view::Viewable for {} {{",
+        anonymous
+    );
+    let _ = writeln!(code, "    type View<'a> = {};\n", view("'a"));
+    let _ = writeln!(
+        code,
+        "    fn raw_data(array_data: ::arrow::array::ArrayData) -> ::eyre::Result<{}> {{",
+        RAW_DATA
+    );
+    let _ = writeln!(code, "        {}::raw_data(array_data)", name);
+    let _ = writeln!(code, "    }}\n");
+    let _ = writeln!(
+        code,
+        "    fn view_from_raw_data(raw_data: &{}) -> ::eyre::Result<{}> {{",
+        RAW_DATA, anonymous
+    );
+    let _ = writeln!(code, "        {}::view_from_raw_data(raw_data)", name);
+    let _ = writeln!(code, "    }}\n");
+    let _ = writeln!(
+        code,
+        "    fn shorten<'a, 'b: 'a>(view: &'a {}) -> &'a {} {{",
+        view("'b"),
+        view("'a")
+    );
+    let _ = writeln!(code, "        view");
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}\n");
+
+    let _ = writeln!(code, "impl {}::HasHeader for {} {{", HEADER, anonymous);
+    let _ = writeln!(
+        code,
+        "    fn header(&self) -> Option<&{}::Header> {{",
+        HEADER
+    );
+    let _ = writeln!(code, "        self.header.as_ref()");
+    let _ = writeln!(code, "    }}\n");
+    let _ = writeln!(
+        code,
+        "    fn header_mut(&mut self) -> &mut Option<{}::Header> {{",
+        HEADER
+    );
+    let _ = writeln!(code, "        &mut self.header");
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}");
+
+    code
+}
+
+mod tests {
+    #[test]
+    fn test_generate() {
+        use crate::{parse::parse, rust::generate};
+
+        let datatypes = parse(
+            "/// Inertial measurement.
+            datatype Imu {
+                acceleration: [f32],
+                temperature: f32?,
+                frame: string,
+            }
+
+            datatype Status { code: u16, message: string? }",
+        )
+        .unwrap();
+
+        let code = generate(&datatypes, false);
+
+        assert!(code.contains("/// Inertial measurement.\n#[derive"));
+        assert!(code.contains("pub struct Imu<'a> {"));
+        assert!(code.contains("pub acceleration: ::std::borrow::Cow<'a, [f32]>,"));
+        assert!(code.contains("pub temperature: Option<f32>,"));
+        assert!(code.contains("pub struct Status {"));
+        assert!(code.contains("pub message: Option<String>,"));
+        assert!(code.contains("pub fn from_arrow(array_data: ::arrow::array::ArrayData) -> ::eyre::Result<Imu<'static>> {"));
+        assert!(code.contains(".push_primitive_singleton::<::arrow::datatypes::UInt16Type>(\"code\", self.code, ::arrow::datatypes::DataType::UInt16, false)"));
+        assert!(code.contains("fn shorten<'a, 'b: 'a>(view: &'a Imu<'b>) -> &'a Imu<'a> {"));
+        assert!(!code.contains("pyo3"));
+
+        assert_eq!(
+            code.matches('{').count(),
+            code.matches('}').count(),
+            "generated code is balanced"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+
+fn image_like_array() -> arrow::array::ArrayData {
+    use arrow::datatypes::{
+        DataType::{UInt32, UInt8, Utf8},
+        UInt32Type, UInt8Type,
+    };
+
+    FastFormatArrowBuilder::new()
+        .push_primitive_singleton::<UInt32Type>("width", 1280, UInt32, false)
+        .push_primitive_singleton::<UInt32Type>("height", 720, UInt32, false)
+        .push_utf_singleton("encoding", "BGR8", Utf8, false)
+        .push_utf_singleton("name", "camera.front", Utf8, false)
+        .push_primitive_array::<UInt8Type>("data", vec![0; 1280 * 720 * 3], UInt8, false)
+        .into_arrow()
+        .unwrap()
+}
+
+fn view_fields(c: &mut Criterion) {
+    use arrow::datatypes::{UInt32Type, UInt8Type};
+
+    let array_data = image_like_array();
+
+    c.bench_function("raw_data_view_image_fields", |b| {
+        b.iter(|| {
+            let raw_data = FastFormatArrowRawData::new(black_box(array_data.clone()))
+                .unwrap()
+                .load_primitive::<UInt32Type>("width")
+                .unwrap()
+                .load_primitive::<UInt32Type>("height")
+                .unwrap()
+                .load_utf("encoding")
+                .unwrap()
+                .load_utf("name")
+                .unwrap()
+                .load_primitive::<UInt8Type>("data")
+                .unwrap();
+
+            let width = raw_data.primitive_singleton::<UInt32Type>("width").unwrap();
+            let height = raw_data
+                .primitive_singleton::<UInt32Type>("height")
+                .unwrap();
+            let encoding = raw_data.utf8_singleton_view("encoding").unwrap();
+            let data = raw_data.primitive_array_view::<UInt8Type>("data").unwrap();
+
+            black_box((width, height, encoding.len(), data.len()));
+        })
+    });
+}
+
+criterion_group!(benches, view_fields);
+criterion_main!(benches);
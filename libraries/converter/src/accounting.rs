@@ -0,0 +1,131 @@
+//! Copy accounting for conversions.
+//!
+//! With the `accounting` feature enabled, every conversion that goes through a copy-on-write
+//! buffer records how many bytes it copied and how many it reused without copying. The counters
+//! are global and can be queried at any time, which makes it easy to check that a dataflow is
+//! actually zero-copy end to end:
+//!
+//! ```
+//! use fastformat_converter::accounting;
+//!
+//! accounting::reset();
+//!
+//! // ... run conversions ...
+//!
+//! let stats = accounting::total();
+//! println!("{} bytes copied, {} bytes reused", stats.copied_bytes, stats.borrowed_bytes);
+//! ```
+//!
+//! Without the feature, recording is a no-op and all counters stay at zero.
+
+#[cfg(feature = "accounting")]
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+/// Bytes copied vs. reused by one or more conversions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CopyStats {
+    /// Number of conversions that had to copy their data.
+    pub copies: u64,
+    /// Total number of bytes copied.
+    pub copied_bytes: u64,
+
+    /// Number of conversions that borrowed or moved their data without copying.
+    pub borrows: u64,
+    /// Total number of bytes borrowed or moved without copying.
+    pub borrowed_bytes: u64,
+}
+
+#[cfg(feature = "accounting")]
+impl CopyStats {
+    fn add(&mut self, bytes: usize, copied: bool) {
+        if copied {
+            self.copies += 1;
+            self.copied_bytes += bytes as u64;
+        } else {
+            self.borrows += 1;
+            self.borrowed_bytes += bytes as u64;
+        }
+    }
+}
+
+/// Records a conversion that turns `data` into an owned buffer: borrowed data gets copied,
+/// owned data is moved as is.
+#[inline]
+#[allow(clippy::ptr_arg)] // Whether the data is borrowed is exactly what gets recorded.
+pub fn record_cow<T: Clone>(conversion: &'static str, data: &std::borrow::Cow<'_, [T]>) {
+    record(
+        conversion,
+        std::mem::size_of_val::<[T]>(data),
+        matches!(data, std::borrow::Cow::Borrowed(_)),
+    );
+}
+
+#[cfg(feature = "accounting")]
+fn registry() -> std::sync::MutexGuard<'static, HashMap<&'static str, CopyStats>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<&'static str, CopyStats>>> = OnceLock::new();
+
+    REGISTRY
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Records that `conversion` copied (`copied == true`) or reused `bytes` bytes.
+#[inline]
+pub fn record(conversion: &'static str, bytes: usize, copied: bool) {
+    #[cfg(feature = "accounting")]
+    registry().entry(conversion).or_default().add(bytes, copied);
+
+    #[cfg(not(feature = "accounting"))]
+    let _ = (conversion, bytes, copied);
+}
+
+/// Returns the counters of a single conversion, e.g. `"ImageData::into_u8"`.
+pub fn stats(conversion: &str) -> CopyStats {
+    #[cfg(feature = "accounting")]
+    return registry().get(conversion).copied().unwrap_or_default();
+
+    #[cfg(not(feature = "accounting"))]
+    {
+        let _ = conversion;
+        CopyStats::default()
+    }
+}
+
+/// Returns the counters of every conversion that recorded something, sorted by name.
+pub fn all() -> Vec<(&'static str, CopyStats)> {
+    #[cfg(feature = "accounting")]
+    {
+        let mut all = registry()
+            .iter()
+            .map(|(conversion, stats)| (*conversion, *stats))
+            .collect::<Vec<_>>();
+        all.sort_by_key(|(conversion, _)| *conversion);
+
+        all
+    }
+
+    #[cfg(not(feature = "accounting"))]
+    Vec::new()
+}
+
+/// Returns the counters summed over all conversions.
+pub fn total() -> CopyStats {
+    all()
+        .into_iter()
+        .fold(CopyStats::default(), |total, (_, stats)| CopyStats {
+            copies: total.copies + stats.copies,
+            copied_bytes: total.copied_bytes + stats.copied_bytes,
+            borrows: total.borrows + stats.borrows,
+            borrowed_bytes: total.borrowed_bytes + stats.borrowed_bytes,
+        })
+}
+
+/// Resets all counters to zero.
+pub fn reset() {
+    #[cfg(feature = "accounting")]
+    registry().clear();
+}
@@ -1,150 +1,307 @@
 use eyre::{Context, OptionExt, Report, Result};
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
+
+/// Alignment, in bytes, that [`FastFormatArrowRawData::ensure_aligned`] guarantees for a buffer.
+///
+/// 64 bytes covers AVX-512 loads and the usual GPU upload requirements.
+pub const ALIGNMENT: usize = 64;
+
+/// Field metadata key holding the unit of a numeric field, e.g. `"m"` or `"rad"`.
+pub const UNIT_KEY: &str = "fastformat.unit";
 
 pub struct FastFormatArrowRawData {
-    buffers: HashMap<String, arrow::buffer::Buffer>,
-    offset_buffers: HashMap<String, arrow::buffer::OffsetBuffer<i32>>,
+    fields: Vec<FieldEntry>,
+}
 
-    array_data: HashMap<String, arrow::array::ArrayData>,
+/// Everything known about one union field.
+///
+/// Datatypes only have a handful of fields, so they are kept in a flat `Vec` and found by
+/// comparing names: this is faster than hashing and keying by the `FieldRef` avoids allocating
+/// a `String` per field and per message.
+struct FieldEntry {
+    field: arrow::datatypes::FieldRef,
+
+    array_data: Option<arrow::array::ArrayData>,
+    buffer: Option<arrow::buffer::Buffer>,
+    offset_buffer: Option<arrow::buffer::OffsetBuffer<i32>>,
 }
 
 pub struct FastFormatArrowBuilder {
     union_children: Vec<arrow::array::ArrayRef>,
     union_fields: Vec<(i8, arrow::datatypes::FieldRef)>,
+    schema: Option<arrow::datatypes::UnionFields>,
 }
 
 impl FastFormatArrowRawData {
+    /// Splits a fastformat message into its union fields, to be loaded by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `array_data` isn't a valid union array, e.g. a malformed message
+    /// received from another process.
     pub fn new(array_data: arrow::array::ArrayData) -> Result<Self> {
         use arrow::array::Array;
 
-        let array = arrow::array::UnionArray::from(array_data);
+        if !matches!(
+            array_data.data_type(),
+            arrow::datatypes::DataType::Union(_, _)
+        ) {
+            return Err(Report::msg(format!(
+                "Expected a union array, got {}",
+                array_data.data_type()
+            )));
+        }
+        // Messages can come from untrusted peers: check the layout of every buffer, so that
+        // reading the fields below can't go out of bounds.
+        check_buffers(&array_data)?;
+        array_data.validate_full().wrap_err("Invalid union array")?;
 
-        let mut result = HashMap::new();
+        let array = arrow::array::UnionArray::from(array_data);
 
         let (union_fields, _, _, children) = array.into_parts();
 
-        for (a, b) in union_fields.iter() {
-            let child = children
-                .get(a as usize)
-                .ok_or_eyre(Report::msg(
-                    format!(
-                        "Invalid union array field {}'s index (= {}). Must be >= 0 and correspond to children index in the array",
-                        b, a
-                    ),
-                ))?
-                .clone()
-                .into_data();
-
-            result.insert(b.name().to_string(), child);
-        }
+        let fields = union_fields
+            .iter()
+            .map(|(a, b)| {
+                let child = children
+                    .get(a as usize)
+                    .ok_or_eyre(Report::msg(
+                        format!(
+                            "Invalid union array field {}'s index (= {}). Must be >= 0 and correspond to children index in the array",
+                            b, a
+                        ),
+                    ))?
+                    .clone()
+                    .into_data();
+
+                let (field, child) = if crate::compression::is_compressed(b) {
+                    crate::compression::decompress_child(b, child)?
+                } else {
+                    (b.clone(), child)
+                };
+
+                Ok(FieldEntry {
+                    field,
+                    array_data: Some(child),
+                    buffer: None,
+                    offset_buffer: None,
+                })
+            })
+            .collect::<Result<Vec<FieldEntry>>>()?;
+
+        Ok(Self { fields })
+    }
 
-        Ok(Self {
-            buffers: HashMap::new(),
-            offset_buffers: HashMap::new(),
-            array_data: result,
-        })
+    /// Returns whether the union has a child named `field`.
+    pub fn has_field(&self, field: &str) -> bool {
+        self.fields.iter().any(|entry| entry.field.name() == field)
+    }
+
+    /// Returns the metadata of the union child named `field`.
+    pub fn field_metadata(
+        &self,
+        field: &str,
+    ) -> Result<&std::collections::HashMap<String, String>> {
+        Ok(self.entry(field)?.field.metadata())
+    }
+
+    /// Returns the unit the union child named `field` is annotated with, if any. Messages from
+    /// older versions have no units.
+    pub fn unit(&self, field: &str) -> Result<Option<&str>> {
+        Ok(self
+            .field_metadata(field)?
+            .get(UNIT_KEY)
+            .map(String::as_str))
+    }
+
+    fn entry(&self, field: &str) -> Result<&FieldEntry> {
+        self.fields
+            .iter()
+            .find(|entry| entry.field.name() == field)
+            .ok_or_eyre(Report::msg(format!(
+                "Invalid field {} for this map of data",
+                field
+            )))
+    }
+
+    fn entry_mut(&mut self, field: &str) -> Result<&mut FieldEntry> {
+        self.fields
+            .iter_mut()
+            .find(|entry| entry.field.name() == field)
+            .ok_or_eyre(Report::msg(format!(
+                "Invalid field {} for this map of data",
+                field
+            )))
+    }
+
+    fn take_array_data(&mut self, field: &str) -> Result<arrow::array::ArrayData> {
+        self.entry_mut(field)?
+            .array_data
+            .take()
+            .ok_or_eyre(Report::msg(format!(
+                "Invalid field {} for this map of data",
+                field
+            )))
+    }
+
+    fn buffer(&self, field: &str) -> Result<&arrow::buffer::Buffer> {
+        self.entry(field)?
+            .buffer
+            .as_ref()
+            .ok_or_eyre(Report::msg(format!(
+                "Invalid field {} for this map of data",
+                field
+            )))
+    }
+
+    fn offset_buffer(&self, field: &str) -> Result<&arrow::buffer::OffsetBuffer<i32>> {
+        self.entry(field)?
+            .offset_buffer
+            .as_ref()
+            .ok_or_eyre(Report::msg(format!(
+                "Invalid field {} for this map of data",
+                field
+            )))
     }
 
     pub fn load_primitive<T: arrow::datatypes::ArrowPrimitiveType>(
-        self,
+        mut self,
         field: &str,
     ) -> Result<Self> {
-        let mut array_data = self.array_data;
-        let mut buffers = self.buffers;
-        let offset_buffers = self.offset_buffers;
-
-        let data = array_data.remove(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
+        let data = self.take_array_data(field)?;
+
+        if data.data_type() != &T::DATA_TYPE {
+            return Err(Report::msg(format!(
+                "Field {} holds {} values, expected {}",
+                field,
+                data.data_type(),
+                T::DATA_TYPE
+            )));
+        }
 
-        let array = arrow::array::PrimitiveArray::<T>::from(data);
-        let (_, buffer, _) = array.into_parts();
+        // Slice the values buffer of the child directly instead of building a `PrimitiveArray`
+        // only to take it apart again.
+        let size = std::mem::size_of::<T::Native>();
+        let buffer = data
+            .buffers()
+            .first()
+            .ok_or_eyre(Report::msg(format!("No values buffer for field {}", field)))?;
+
+        let (start, len) = (data.offset() * size, data.len() * size);
+        if start + len > buffer.len() {
+            return Err(Report::msg(format!(
+                "Values buffer of field {} is too short for its length",
+                field
+            )));
+        }
 
-        buffers.insert(field.to_string(), buffer.into_inner());
+        self.entry_mut(field)?.buffer = Some(buffer.slice_with_length(start, len));
 
-        Ok(Self {
-            buffers,
-            offset_buffers,
-            array_data,
-        })
+        Ok(self)
     }
 
-    pub fn load_utf(self, field: &str) -> Result<Self> {
-        let mut array_data = self.array_data;
-        let mut buffers = self.buffers;
-        let mut offset_buffers = self.offset_buffers;
+    pub fn load_utf(mut self, field: &str) -> Result<Self> {
+        let data = self.take_array_data(field)?;
 
-        let data = array_data.remove(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
+        if data.data_type() != &arrow::datatypes::DataType::Utf8 {
+            return Err(Report::msg(format!(
+                "Field {} holds {} values, expected Utf8",
+                field,
+                data.data_type()
+            )));
+        }
 
         let array = arrow::array::StringArray::from(data);
         let (offset_buffer, buffer, _) = array.into_parts();
 
-        buffers.insert(field.to_string(), buffer);
-        offset_buffers.insert(field.to_string(), offset_buffer);
+        let entry = self.entry_mut(field)?;
+        entry.buffer = Some(buffer);
+        entry.offset_buffer = Some(offset_buffer);
 
-        Ok(Self {
-            buffers,
-            offset_buffers,
-            array_data,
-        })
+        Ok(self)
     }
 
-    pub fn utf8_singleton(&self, field: &str) -> Result<String> {
-        let buffer = self.buffers.get(field).ok_or_eyre(Report::msg(format!(
+    /// Makes sure the buffer of `field` starts on an [`ALIGNMENT`]-byte boundary.
+    ///
+    /// Buffers received from another process or built from a `Vec` only carry the alignment of
+    /// their element type. If the buffer is misaligned, it is copied once into freshly allocated
+    /// aligned memory; otherwise it is left untouched. A realigned buffer is no longer backed by a
+    /// `Vec`, so read it with `primitive_array_view` rather than `primitive_array`.
+    pub fn ensure_aligned(mut self, field: &str) -> Result<Self> {
+        let entry = self.entry_mut(field)?;
+
+        let buffer = entry.buffer.take().ok_or_eyre(Report::msg(format!(
             "Invalid field {} for this map of data",
             field
         )))?;
 
-        let offset_buffer = self
-            .offset_buffers
-            .get(field)
-            .ok_or_eyre(Report::msg(format!(
-                "Invalid field {} for this map of data",
-                field
-            )))?;
+        let buffer = if buffer.as_ptr().align_offset(ALIGNMENT) == 0 {
+            buffer
+        } else {
+            let len = buffer.len();
+            crate::accounting::record("FastFormatArrowRawData::ensure_aligned", len, true);
 
-        let slice = buffer.as_slice();
-        let mut iterator = offset_buffer.iter();
-        iterator.next();
+            let mut aligned = arrow::buffer::MutableBuffer::from_len_zeroed(len + ALIGNMENT);
+            let offset = aligned.as_ptr().align_offset(ALIGNMENT);
+            aligned.as_slice_mut()[offset..offset + len].copy_from_slice(buffer.as_slice());
 
-        let last_offset = iterator.next().cloned().ok_or_eyre(Report::msg(format!(
-            "No offset associated with field {}",
-            field
-        )))? as usize;
+            arrow::buffer::Buffer::from(aligned).slice_with_length(offset, len)
+        };
 
-        let slice = &slice[0..last_offset];
+        entry.buffer = Some(buffer);
 
-        String::from_utf8(slice.to_vec()).wrap_err(Report::msg("Invalid UTF-8 string"))
+        Ok(self)
     }
 
-    pub fn utf16_singleton(&self, field: &str) -> Result<String> {
-        let buffer = self.buffers.get(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
+    /// Returns whether the buffer of `field` starts on an [`ALIGNMENT`]-byte boundary.
+    pub fn is_aligned(&self, field: &str) -> Result<bool> {
+        let buffer = self.buffer(field)?;
+
+        Ok(buffer.as_ptr().align_offset(ALIGNMENT) == 0)
+    }
+
+    pub fn utf8_singleton(&self, field: &str) -> Result<String> {
+        self.utf8_singleton_view(field).map(str::to_string)
+    }
 
-        let offset_buffer = self
-            .offset_buffers
-            .get(field)
+    /// Returns the values of the string field `field` as slices of `values`, which are its
+    /// values buffer reinterpreted as `T`.
+    fn utf_slices<'a, T>(
+        &'a self,
+        field: &str,
+        values: &'a [T],
+    ) -> Result<impl Iterator<Item = Result<&'a [T]>> + 'a> {
+        Ok(self.offset_buffer(field)?.windows(2).map(|window| {
+            values
+                .get(window[0] as usize..window[1] as usize)
+                .ok_or_eyre(Report::msg("String offsets out of the values buffer"))
+        }))
+    }
+
+    pub fn utf8_singleton_view<'a>(&'a self, field: &str) -> Result<&'a str> {
+        let buffer = self.buffer(field)?;
+
+        let slice = self
+            .utf_slices(field, buffer.as_slice())?
+            .next()
             .ok_or_eyre(Report::msg(format!(
-                "Invalid field {} for this map of data",
+                "No offset associated with field {}",
                 field
-            )))?;
+            )))??;
 
-        let slice = buffer.typed_data::<u16>();
-        let mut iterator = offset_buffer.iter();
-        iterator.next();
+        std::str::from_utf8(slice).wrap_err(Report::msg("Invalid UTF-8 string"))
+    }
 
-        let last_offset = iterator.next().cloned().ok_or_eyre(Report::msg(format!(
-            "No offset associated with field {}",
-            field
-        )))? as usize;
+    pub fn utf16_singleton(&self, field: &str) -> Result<String> {
+        let buffer = self.buffer(field)?;
 
-        let slice = &slice[0..last_offset];
+        let slice = self
+            .utf_slices(field, typed_slice::<u16>(buffer, field)?)?
+            .next()
+            .ok_or_eyre(Report::msg(format!(
+                "No offset associated with field {}",
+                field
+            )))??;
 
         String::from_utf16(slice).wrap_err(Report::msg("Invalid UTF-16 string"))
     }
@@ -153,75 +310,31 @@ impl FastFormatArrowRawData {
         &self,
         field: &str,
     ) -> Result<T::Native> {
-        let buffer = self.buffers.get(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
+        let buffer = self.buffer(field)?;
 
-        let slice = buffer.typed_data::<T::Native>();
-
-        Ok(slice[0])
+        typed_slice::<T::Native>(buffer, field)?
+            .first()
+            .copied()
+            .ok_or_eyre(Report::msg(format!("No value for field {}", field)))
     }
 
     pub fn utf8_array(&self, field: &str) -> Result<Vec<String>> {
-        let buffer = self.buffers.get(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
-
-        let offset_buffer = self
-            .offset_buffers
-            .get(field)
-            .ok_or_eyre(Report::msg(format!(
-                "Invalid field {} for this map of data",
-                field
-            )))?;
+        let buffer = self.buffer(field)?;
 
-        let slice = buffer.as_slice();
-        let mut iterator = offset_buffer.iter();
-        iterator.next();
-
-        let mut last_offset = 0;
-
-        iterator
-            .map(|&offset| {
-                let offset = offset as usize;
-                let slice = &slice[last_offset..offset];
-                last_offset = offset;
-
-                String::from_utf8(slice.to_vec())
+        self.utf_slices(field, buffer.as_slice())?
+            .map(|slice| {
+                String::from_utf8(slice?.to_vec())
                     .wrap_err(Report::msg("Array is not UTF-8 encoded."))
             })
             .collect::<Result<Vec<String>>>()
     }
 
     pub fn utf16_array(&self, field: &str) -> Result<Vec<String>> {
-        let buffer = self.buffers.get(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
-
-        let offset_buffer = self
-            .offset_buffers
-            .get(field)
-            .ok_or_eyre(Report::msg(format!(
-                "Invalid field {} for this map of data",
-                field
-            )))?;
-
-        let slice = buffer.typed_data::<u16>();
-        let mut iterator = offset_buffer.iter();
-        iterator.next();
-
-        let mut last_offset = 0;
+        let buffer = self.buffer(field)?;
 
-        iterator
-            .map(|&offset| {
-                let offset = offset as usize;
-                let slice = &slice[last_offset..offset];
-                last_offset = offset;
-
-                String::from_utf16(slice).wrap_err(Report::msg("Array is not UTF-16 encoded."))
+        self.utf_slices(field, typed_slice::<u16>(buffer, field)?)?
+            .map(|slice| {
+                String::from_utf16(slice?).wrap_err(Report::msg("Array is not UTF-16 encoded."))
             })
             .collect::<Result<Vec<String>>>()
     }
@@ -230,21 +343,32 @@ impl FastFormatArrowRawData {
         &'a self,
         field: &str,
     ) -> Result<&'a [T::Native]> {
-        let buffer = self.buffers.get(field).ok_or_eyre(Report::msg(format!(
-            "Invalid field {} for this map of data",
-            field
-        )))?;
+        let buffer = self.buffer(field)?;
 
-        let slice = buffer.typed_data::<T::Native>();
+        typed_slice::<T::Native>(buffer, field)
+    }
 
-        Ok(slice)
+    /// Takes the values buffer of a primitive field out of the raw data, without copying it.
+    ///
+    /// Unlike [`FastFormatArrowRawData::primitive_array`], this also works when the buffer is
+    /// shared with other arrays.
+    pub fn primitive_buffer(&mut self, field: &str) -> Result<arrow::buffer::Buffer> {
+        self.entry_mut(field)?
+            .buffer
+            .take()
+            .ok_or_eyre(Report::msg(format!(
+                "Invalid field {} for this map of data",
+                field
+            )))
     }
 
     pub fn primitive_array<T: arrow::datatypes::ArrowPrimitiveType>(
         &mut self,
         field: &str,
     ) -> Result<Vec<T::Native>> {
-        let buffer = self.buffers.remove(field).ok_or_eyre(Report::msg(format!(
+        let entry = self.entry_mut(field)?;
+
+        let buffer = entry.buffer.take().ok_or_eyre(Report::msg(format!(
             "Invalid field {} for this map of data",
             field
         )))?;
@@ -252,7 +376,7 @@ impl FastFormatArrowRawData {
         match buffer.into_vec::<T::Native>() {
             Ok(vec) => Ok(vec),
             Err(buffer) => {
-                self.buffers.insert(field.to_string(), buffer);
+                entry.buffer = Some(buffer);
 
                 Err(Report::msg("Invalid primitive array type. Or the buffer is shared. If you're not sure that the buffer is owned, use primitive_array_view instead."))
             }
@@ -260,18 +384,125 @@ impl FastFormatArrowRawData {
     }
 }
 
+/// Checks that every buffer of `array_data` and of its children holds whole, aligned values:
+/// Arrow's own validation assumes it, and panics otherwise.
+fn check_buffers(array_data: &arrow::array::ArrayData) -> Result<()> {
+    let layout = arrow::array::layout(array_data.data_type());
+
+    for (buffer, spec) in array_data.buffers().iter().zip(&layout.buffers) {
+        if let arrow::array::BufferSpec::FixedWidth {
+            byte_width,
+            alignment,
+        } = spec
+        {
+            if buffer.as_ptr().align_offset(*alignment) != 0 || buffer.len() % byte_width != 0 {
+                return Err(Report::msg(format!(
+                    "Misaligned or truncated buffer in array of type {}",
+                    array_data.data_type()
+                )));
+            }
+        }
+    }
+
+    array_data.child_data().iter().try_for_each(check_buffers)
+}
+
+/// Reinterprets `buffer` as a slice of `T`, like `Buffer::typed_data` but returning an error
+/// instead of panicking if the buffer is misaligned or its length isn't a multiple of `T`'s.
+fn typed_slice<'a, T: arrow::datatypes::ArrowNativeType>(
+    buffer: &'a arrow::buffer::Buffer,
+    field: &str,
+) -> Result<&'a [T]> {
+    // SAFETY: `ArrowNativeType` is only implemented for plain old data types, valid for any bit
+    // pattern.
+    match unsafe { buffer.as_slice().align_to::<T>() } {
+        ([], slice, []) => Ok(slice),
+        _ => Err(Report::msg(format!(
+            "Buffer of field {} isn't a valid {} buffer",
+            field,
+            std::any::type_name::<T>()
+        ))),
+    }
+}
+
+impl Default for FastFormatArrowBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FastFormatArrowBuilder {
     pub fn new() -> Self {
         Self {
             union_children: Vec::new(),
             union_fields: Vec::new(),
+            schema: None,
         }
     }
 
-    pub fn push_primitive_singleton<T: arrow::datatypes::ArrowPrimitiveType>(
+    /// Creates a builder that reuses an already built `UnionFields` instead of creating a new
+    /// `Field` for every pushed value.
+    ///
+    /// Datatypes with a fixed layout can build their schema once with [`Self::schema`], cache
+    /// it, and pass it here for every message. Fields must then be pushed in the order of the
+    /// schema, with matching types.
+    pub fn with_schema(schema: arrow::datatypes::UnionFields) -> Self {
+        Self {
+            union_children: Vec::new(),
+            union_fields: Vec::new(),
+            schema: Some(schema),
+        }
+    }
+
+    /// Builds the `UnionFields` for a list of `(name, data_type, nullable)` fields, in push order.
+    pub fn schema(
+        fields: &[(&str, arrow::datatypes::DataType, bool)],
+    ) -> arrow::datatypes::UnionFields {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(index, (field, data_type, nullable))| {
+                (
+                    index as i8,
+                    Arc::new(arrow::datatypes::Field::new(
+                        *field,
+                        data_type.clone(),
+                        *nullable,
+                    )),
+                )
+            })
+            .collect()
+    }
+
+    /// Annotates the fields of a schema built with [`Self::schema`] with their unit, given as
+    /// `(field, unit)` pairs and stored in the field metadata under [`UNIT_KEY`].
+    pub fn annotate_units(
+        schema: arrow::datatypes::UnionFields,
+        units: &[(&str, &str)],
+    ) -> arrow::datatypes::UnionFields {
+        schema
+            .iter()
+            .map(
+                |(type_id, field)| match units.iter().find(|(name, _)| name == field.name()) {
+                    Some((_, unit)) => {
+                        let mut metadata = field.metadata().clone();
+                        metadata.insert(UNIT_KEY.to_string(), unit.to_string());
+
+                        (
+                            type_id,
+                            Arc::new(field.as_ref().clone().with_metadata(metadata)),
+                        )
+                    }
+                    None => (type_id, field.clone()),
+                },
+            )
+            .collect()
+    }
+
+    fn push_child(
         self,
         field: &str,
-        value: T::Native,
+        child: arrow::array::ArrayRef,
         data_type: arrow::datatypes::DataType,
         nullable: bool,
     ) -> Self {
@@ -280,73 +511,57 @@ impl FastFormatArrowBuilder {
 
         let index = union_children.len();
 
-        let data = Arc::new(arrow::array::PrimitiveArray::<T>::from_value(value, 1));
-        union_children.push(data);
+        union_children.push(child);
 
-        let field = (
-            index as i8,
-            Arc::new(arrow::datatypes::Field::new(field, data_type, nullable)),
-        );
-        union_fields.push(field);
+        if self.schema.is_none() {
+            let field = (
+                index as i8,
+                Arc::new(arrow::datatypes::Field::new(field, data_type, nullable)),
+            );
+            union_fields.push(field);
+        }
 
         Self {
             union_children,
             union_fields,
+            schema: self.schema,
         }
     }
 
-    pub fn push_primitive_array<T: arrow::datatypes::ArrowPrimitiveType>(
+    pub fn push_primitive_singleton<T: arrow::datatypes::ArrowPrimitiveType>(
         self,
         field: &str,
-        value: Vec<T::Native>,
+        value: T::Native,
         data_type: arrow::datatypes::DataType,
         nullable: bool,
     ) -> Self {
-        let mut union_children = self.union_children;
-        let mut union_fields = self.union_fields;
+        let data = Arc::new(arrow::array::PrimitiveArray::<T>::from_value(value, 1));
 
-        let index = union_children.len();
+        self.push_child(field, data, data_type, nullable)
+    }
 
+    pub fn push_primitive_array<T: arrow::datatypes::ArrowPrimitiveType>(
+        self,
+        field: &str,
+        value: Vec<T::Native>,
+        data_type: arrow::datatypes::DataType,
+        nullable: bool,
+    ) -> Self {
         let data = Arc::new(arrow::array::PrimitiveArray::<T>::from_iter_values(value));
-        union_children.push(data);
-
-        let field = (
-            index as i8,
-            Arc::new(arrow::datatypes::Field::new(field, data_type, nullable)),
-        );
-        union_fields.push(field);
 
-        Self {
-            union_children,
-            union_fields,
-        }
+        self.push_child(field, data, data_type, nullable)
     }
 
     pub fn push_utf_singleton(
         self,
         field: &str,
-        value: String,
+        value: &str,
         data_type: arrow::datatypes::DataType,
         nullable: bool,
     ) -> Self {
-        let mut union_children = self.union_children;
-        let mut union_fields = self.union_fields;
-
-        let index = union_children.len();
-
         let data = Arc::new(arrow::array::StringArray::from(vec![value]));
-        union_children.push(data);
-
-        let field = (
-            index as i8,
-            Arc::new(arrow::datatypes::Field::new(field, data_type, nullable)),
-        );
-        union_fields.push(field);
 
-        Self {
-            union_children,
-            union_fields,
-        }
+        self.push_child(field, data, data_type, nullable)
     }
 
     pub fn push_utf_array(
@@ -356,24 +571,9 @@ impl FastFormatArrowBuilder {
         data_type: arrow::datatypes::DataType,
         nullable: bool,
     ) -> Self {
-        let mut union_children = self.union_children;
-        let mut union_fields = self.union_fields;
-
-        let index = union_children.len();
-
         let data = Arc::new(arrow::array::StringArray::from(value));
-        union_children.push(data);
 
-        let field = (
-            index as i8,
-            Arc::new(arrow::datatypes::Field::new(field, data_type, nullable)),
-        );
-        union_fields.push(field);
-
-        Self {
-            union_children,
-            union_fields,
-        }
+        self.push_child(field, data, data_type, nullable)
     }
 
     pub fn into_arrow(self) -> Result<arrow::array::ArrayData> {
@@ -382,10 +582,13 @@ impl FastFormatArrowBuilder {
         let type_ids = [].into_iter().collect::<arrow::buffer::ScalarBuffer<i8>>();
         let offsets = [].into_iter().collect::<arrow::buffer::ScalarBuffer<i32>>();
 
-        let union_fields = self
-            .union_fields
-            .into_iter()
-            .collect::<arrow::datatypes::UnionFields>();
+        let union_fields = match self.schema {
+            Some(schema) => schema,
+            None => self
+                .union_fields
+                .into_iter()
+                .collect::<arrow::datatypes::UnionFields>(),
+        };
 
         Ok(arrow::array::UnionArray::try_new(
             union_fields,
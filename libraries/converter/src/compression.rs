@@ -0,0 +1,556 @@
+//! Compression of large primitive fields, e.g. image data, for bandwidth-constrained links.
+//!
+//! [`compress`] replaces every primitive field of a fastformat message larger than a threshold
+//! by its compressed bytes, recording the codec, type and length of the original values in the
+//! field metadata. [`FastFormatArrowRawData::new`](crate::arrow::FastFormatArrowRawData::new)
+//! decompresses such fields transparently, so every datatype reads compressed messages as is:
+//!
+//! ```
+//! # #[cfg(feature = "zstd")]
+//! # {
+//! use arrow::datatypes::{DataType::UInt8, UInt8Type};
+//! use fastformat_converter::{
+//!     arrow::{FastFormatArrowBuilder, FastFormatArrowRawData},
+//!     compression::{self, Codec},
+//! };
+//!
+//! let array_data = FastFormatArrowBuilder::new()
+//!     .push_primitive_array::<UInt8Type>("data", vec![0; 4096], UInt8, false)
+//!     .into_arrow()
+//!     .unwrap();
+//!
+//! let compressed = compression::compress(array_data, Codec::Zstd { level: 3 }, 1024).unwrap();
+//! assert!(compressed.get_slice_memory_size().unwrap() < 4096);
+//!
+//! let raw_data = FastFormatArrowRawData::new(compressed)
+//!     .unwrap()
+//!     .load_primitive::<UInt8Type>("data")
+//!     .unwrap();
+//! assert_eq!(raw_data.primitive_array_view::<UInt8Type>("data").unwrap(), &[0; 4096]);
+//! # }
+//! ```
+//!
+//! Codecs are enabled by the `lz4` and `zstd` features. Decompressing is an implicit copy,
+//! subject to the [copy policy](crate::policy), and fields can't decompress to more than
+//! [`max_decompressed_bytes`].
+
+use eyre::{Context, OptionExt, Report, Result};
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Field metadata key holding the codec of a compressed field.
+pub const CODEC_KEY: &str = "fastformat.compression.codec";
+
+/// Field metadata key holding the Arrow data type of a compressed field's values.
+pub const DATA_TYPE_KEY: &str = "fastformat.compression.data_type";
+
+/// Field metadata key holding the number of values of a compressed field.
+pub const LEN_KEY: &str = "fastformat.compression.len";
+
+/// Compression algorithm of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// Fast compression with a lower ratio, for links where latency matters most.
+    Lz4,
+    /// Better ratio at a higher CPU cost, `level` ranging from 1 to 22.
+    Zstd { level: i32 },
+}
+
+impl Codec {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Codec::Lz4 => "lz4",
+            Codec::Zstd { .. } => "zstd",
+        }
+    }
+
+    #[cfg_attr(not(all(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+    fn compress(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Codec::Lz4 => {
+                lz4::block::compress(bytes, None, false).wrap_err("lz4 compression failed")
+            }
+            #[cfg(feature = "zstd")]
+            Codec::Zstd { level } => {
+                zstd::bulk::compress(bytes, *level).wrap_err("zstd compression failed")
+            }
+            #[allow(unreachable_patterns)]
+            codec => Err(missing_feature(codec.as_str())),
+        }
+    }
+}
+
+fn missing_feature(codec: &str) -> Report {
+    Report::msg(format!(
+        "The {} codec is not available, enable the {} feature of fastformat-converter",
+        codec, codec
+    ))
+}
+
+/// Maximum ratio between the decompressed and compressed sizes of an lz4 block: a byte of a
+/// block encodes at most 255 bytes of a match.
+#[cfg(feature = "lz4")]
+const LZ4_MAX_RATIO: usize = 255;
+
+/// Maximum ratio between the decompressed and compressed sizes of a zstd frame: an RLE block of
+/// up to 128 KiB takes 4 bytes, its 3 bytes header and the repeated byte.
+#[cfg(feature = "zstd")]
+const ZSTD_MAX_RATIO: usize = 128 * 1024 / 4;
+
+/// Default of [`set_max_decompressed_bytes`], 1 GiB.
+pub const DEFAULT_MAX_DECOMPRESSED_BYTES: usize = 1024 * 1024 * 1024;
+
+static MAX_DECOMPRESSED_BYTES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_DECOMPRESSED_BYTES);
+
+/// Sets the largest number of bytes a compressed field may decompress to, in the whole process.
+/// Fields claiming more are rejected before allocating anything.
+pub fn set_max_decompressed_bytes(bytes: usize) {
+    MAX_DECOMPRESSED_BYTES.store(bytes, Ordering::Relaxed);
+}
+
+/// Returns the largest number of bytes a compressed field may decompress to, see
+/// [`set_max_decompressed_bytes`].
+pub fn max_decompressed_bytes() -> usize {
+    MAX_DECOMPRESSED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Returns the largest number of bytes `compressed` can decompress to, so that a message can't
+/// claim a length it doesn't hold and make the reader allocate it. The content size a zstd frame
+/// declares is chosen by the sender, so it is bounded by the size of the frame as well.
+#[cfg_attr(not(all(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+fn max_decompressed_len(codec: &str, compressed: &[u8]) -> Result<usize> {
+    match codec {
+        #[cfg(feature = "lz4")]
+        "lz4" => Ok(compressed.len().saturating_mul(LZ4_MAX_RATIO)),
+        #[cfg(feature = "zstd")]
+        "zstd" => match zstd::zstd_safe::get_frame_content_size(compressed) {
+            Ok(Some(len)) => Ok(usize::try_from(len)
+                .unwrap_or(usize::MAX)
+                .min(compressed.len().saturating_mul(ZSTD_MAX_RATIO))),
+            _ => Err(Report::msg("zstd frame doesn't declare its content size")),
+        },
+        #[allow(unreachable_patterns)]
+        "lz4" | "zstd" => Err(missing_feature(codec)),
+        codec => Err(Report::msg(format!("Unknown compression codec {}", codec))),
+    }
+}
+
+#[cfg_attr(not(all(feature = "lz4", feature = "zstd")), allow(unused_variables))]
+fn decompress_into(codec: &str, compressed: &[u8], buffer: &mut [u8]) -> Result<usize> {
+    match codec {
+        #[cfg(feature = "lz4")]
+        "lz4" => {
+            let len = i32::try_from(buffer.len()).wrap_err("Field too large for lz4")?;
+
+            lz4::block::decompress_to_buffer(compressed, Some(len), buffer)
+                .wrap_err("lz4 decompression failed")
+        }
+        #[cfg(feature = "zstd")]
+        "zstd" => zstd::bulk::decompress_to_buffer(compressed, buffer)
+            .wrap_err("zstd decompression failed"),
+        #[allow(unreachable_patterns)]
+        "lz4" | "zstd" => Err(missing_feature(codec)),
+        codec => Err(Report::msg(format!("Unknown compression codec {}", codec))),
+    }
+}
+
+/// Returns whether `field` holds compressed values.
+pub fn is_compressed(field: &arrow::datatypes::Field) -> bool {
+    field.metadata().contains_key(CODEC_KEY)
+}
+
+/// Compresses `child` if it is a primitive array without nulls of at least `min_bytes` bytes,
+/// returning the field and child to use instead.
+fn compress_child(
+    field: &arrow::datatypes::FieldRef,
+    child: &arrow::array::ArrayRef,
+    codec: Codec,
+    min_bytes: usize,
+) -> Result<Option<(arrow::datatypes::FieldRef, arrow::array::ArrayRef)>> {
+    let data_type = child.data_type();
+
+    let Some(width) = data_type.primitive_width() else {
+        return Ok(None);
+    };
+    if !data_type.is_primitive() || child.null_count() > 0 || is_compressed(field) {
+        return Ok(None);
+    }
+
+    let data = child.to_data();
+    let Some(buffer) = data.buffers().first() else {
+        return Ok(None);
+    };
+
+    let (start, len) = (data.offset() * width, data.len() * width);
+    if len < min_bytes || start + len > buffer.len() {
+        return Ok(None);
+    }
+
+    let compressed = codec.compress(&buffer.as_slice()[start..start + len])?;
+
+    let mut metadata = field.metadata().clone();
+    metadata.insert(CODEC_KEY.to_string(), codec.as_str().to_string());
+    metadata.insert(DATA_TYPE_KEY.to_string(), data_type.to_string());
+    metadata.insert(LEN_KEY.to_string(), data.len().to_string());
+
+    let field = arrow::datatypes::Field::new(
+        field.name(),
+        arrow::datatypes::DataType::UInt8,
+        field.is_nullable(),
+    )
+    .with_metadata(metadata);
+    let child = arrow::array::UInt8Array::from(compressed);
+
+    Ok(Some((Arc::new(field), Arc::new(child))))
+}
+
+/// Decompresses the values of a field compressed by [`compress`], returning the original field
+/// and values.
+pub(crate) fn decompress_child(
+    field: &arrow::datatypes::FieldRef,
+    child: arrow::array::ArrayData,
+) -> Result<(arrow::datatypes::FieldRef, arrow::array::ArrayData)> {
+    let metadata = field.metadata();
+    let value = |key: &str| {
+        metadata.get(key).ok_or_eyre(format!(
+            "Compressed field {} has no {} metadata",
+            field.name(),
+            key
+        ))
+    };
+
+    let codec = value(CODEC_KEY)?;
+    let data_type = value(DATA_TYPE_KEY)?
+        .parse::<arrow::datatypes::DataType>()
+        .wrap_err_with(|| format!("Invalid data type of compressed field {}", field.name()))?;
+    let len = value(LEN_KEY)?
+        .parse::<usize>()
+        .wrap_err_with(|| format!("Invalid length of compressed field {}", field.name()))?;
+
+    let bytes = data_type
+        .primitive_width()
+        .and_then(|width| width.checked_mul(len))
+        .ok_or_else(|| {
+            Report::msg(format!(
+                "Compressed field {} doesn't hold {} primitive values",
+                field.name(),
+                data_type
+            ))
+        })?;
+
+    if child.data_type() != &arrow::datatypes::DataType::UInt8 {
+        return Err(Report::msg(format!(
+            "Compressed field {} holds {} values, expected UInt8",
+            field.name(),
+            child.data_type()
+        )));
+    }
+    let compressed = child
+        .buffers()
+        .first()
+        .and_then(|buffer| {
+            buffer
+                .as_slice()
+                .get(child.offset()..child.offset() + child.len())
+        })
+        .ok_or_eyre(format!(
+            "Invalid buffer of compressed field {}",
+            field.name()
+        ))?;
+
+    let max_bytes = max_decompressed_len(codec, compressed)
+        .wrap_err_with(|| format!("Failed to decompress field {}", field.name()))?;
+    if bytes > max_bytes {
+        return Err(Report::msg(format!(
+            "Compressed field {} claims {} bytes, but its {} compressed bytes hold at most {}",
+            field.name(),
+            bytes,
+            compressed.len(),
+            max_bytes
+        )));
+    } else if bytes > max_decompressed_bytes() {
+        return Err(Report::msg(format!(
+            "Compressed field {} claims {} bytes, more than the {} bytes allowed, see set_max_decompressed_bytes",
+            field.name(),
+            bytes,
+            max_decompressed_bytes()
+        )));
+    }
+
+    crate::policy::check("compression::decompress", bytes)?;
+    crate::accounting::record("compression::decompress", bytes, true);
+
+    let mut buffer = Vec::new();
+    buffer.try_reserve_exact(bytes).wrap_err_with(|| {
+        format!(
+            "Failed to allocate {} bytes to decompress field {}",
+            bytes,
+            field.name()
+        )
+    })?;
+    buffer.resize(bytes, 0);
+
+    let written = decompress_into(codec, compressed, &mut buffer)
+        .wrap_err_with(|| format!("Failed to decompress field {}", field.name()))?;
+
+    if written != bytes {
+        return Err(Report::msg(format!(
+            "Compressed field {} holds {} bytes, expected {}",
+            field.name(),
+            written,
+            bytes
+        )));
+    }
+
+    let child = arrow::array::ArrayData::builder(data_type.clone())
+        .len(len)
+        .add_buffer(arrow::buffer::Buffer::from_vec(buffer))
+        .build_aligned()
+        .wrap_err_with(|| format!("Invalid values of compressed field {}", field.name()))?;
+
+    let metadata = metadata
+        .iter()
+        .filter(|(key, _)| ![CODEC_KEY, DATA_TYPE_KEY, LEN_KEY].contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<HashMap<_, _>>();
+    let field = arrow::datatypes::Field::new(field.name(), data_type, field.is_nullable())
+        .with_metadata(metadata);
+
+    Ok((Arc::new(field), child))
+}
+
+/// Maps every child of the fastformat message `array_data` with `map`, which returns the field
+/// and child to replace it with, if any.
+fn map_children(
+    array_data: arrow::array::ArrayData,
+    mut map: impl FnMut(
+        &arrow::datatypes::FieldRef,
+        &arrow::array::ArrayRef,
+    ) -> Result<Option<(arrow::datatypes::FieldRef, arrow::array::ArrayRef)>>,
+) -> Result<arrow::array::ArrayData> {
+    use arrow::array::Array;
+
+    let array = arrow::array::UnionArray::from(array_data);
+    let (union_fields, type_ids, offsets, mut children) = array.into_parts();
+
+    let union_fields = union_fields
+        .iter()
+        .map(|(type_id, field)| {
+            let child = children
+                .get_mut(type_id as usize)
+                .ok_or_eyre(format!("No child for union field {}", field.name()))?;
+
+            match map(field, child)? {
+                Some((field, new_child)) => {
+                    *child = new_child;
+
+                    Ok((type_id, field))
+                }
+                None => Ok((type_id, field.clone())),
+            }
+        })
+        .collect::<Result<arrow::datatypes::UnionFields>>()?;
+
+    Ok(
+        arrow::array::UnionArray::try_new(union_fields, type_ids, offsets, children)
+            .wrap_err("Failed to rebuild the UnionArray")?
+            .into_data(),
+    )
+}
+
+/// Compresses every primitive field of the fastformat message `array_data` holding at least
+/// `min_bytes` bytes with `codec`, e.g. the pixel data of an image. Smaller fields, like the
+/// width or the encoding, are left as is.
+///
+/// # Errors
+///
+/// Returns an error if `codec` isn't enabled, or if the compression fails.
+pub fn compress(
+    array_data: arrow::array::ArrayData,
+    codec: Codec,
+    min_bytes: usize,
+) -> Result<arrow::array::ArrayData> {
+    map_children(array_data, |field, child| {
+        compress_child(field, child, codec, min_bytes)
+    })
+}
+
+/// Decompresses every compressed field of the fastformat message `array_data`.
+///
+/// Reading a message with [`FastFormatArrowRawData`](crate::arrow::FastFormatArrowRawData)
+/// already decompresses it; this is for forwarding a message to consumers that don't.
+pub fn decompress(array_data: arrow::array::ArrayData) -> Result<arrow::array::ArrayData> {
+    map_children(array_data, |field, child| {
+        if !is_compressed(field) {
+            return Ok(None);
+        }
+
+        let (field, child) = decompress_child(field, child.to_data())?;
+
+        Ok(Some((field, arrow::array::make_array(child))))
+    })
+}
+
+mod tests {
+    #[test]
+    #[cfg(all(feature = "lz4", feature = "zstd"))]
+    fn test_compression_round_trip() {
+        use crate::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+        use crate::compression::{self, Codec};
+        use arrow::datatypes::{
+            DataType::{Float32, UInt32},
+            Float32Type, UInt32Type,
+        };
+
+        let values = (0..1024)
+            .map(|value| value as f32 / 4.0)
+            .collect::<Vec<f32>>();
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_singleton::<UInt32Type>("width", 1024, UInt32, false)
+            .push_primitive_array::<Float32Type>("data", values.clone(), Float32, false)
+            .into_arrow()
+            .unwrap();
+
+        for codec in [Codec::Lz4, Codec::Zstd { level: 3 }] {
+            let compressed = compression::compress(array_data.clone(), codec, 64).unwrap();
+
+            let fields = match compressed.data_type() {
+                arrow::datatypes::DataType::Union(fields, _) => fields.clone(),
+                _ => unreachable!(),
+            };
+            let compressed_fields = fields
+                .iter()
+                .filter(|(_, field)| compression::is_compressed(field))
+                .map(|(_, field)| field.name().clone())
+                .collect::<Vec<_>>();
+            assert_eq!(compressed_fields, ["data"]);
+
+            let raw_data = FastFormatArrowRawData::new(compressed.clone())
+                .unwrap()
+                .load_primitive::<UInt32Type>("width")
+                .unwrap()
+                .load_primitive::<Float32Type>("data")
+                .unwrap();
+
+            assert_eq!(
+                raw_data.primitive_singleton::<UInt32Type>("width").unwrap(),
+                1024
+            );
+            assert_eq!(
+                raw_data
+                    .primitive_array_view::<Float32Type>("data")
+                    .unwrap(),
+                values
+            );
+
+            assert_eq!(compression::decompress(compressed).unwrap(), array_data);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "lz4", feature = "zstd"))]
+    fn test_decompression_rejects_forged_len() {
+        use crate::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+        use crate::compression::{self, map_children, Codec, LEN_KEY};
+        use arrow::datatypes::{DataType::UInt8, UInt8Type};
+
+        use std::sync::Arc;
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_array::<UInt8Type>("data", vec![0; 4096], UInt8, false)
+            .into_arrow()
+            .unwrap();
+
+        for codec in [Codec::Lz4, Codec::Zstd { level: 3 }] {
+            let compressed = compression::compress(array_data.clone(), codec, 64).unwrap();
+
+            // A message claiming petabytes of values must fail before allocating them.
+            let forged = map_children(compressed, |field, child| {
+                let mut metadata = field.metadata().clone();
+                metadata.insert(LEN_KEY.to_string(), 1_000_000_000_000_000_usize.to_string());
+
+                Ok(Some((
+                    Arc::new(field.as_ref().clone().with_metadata(metadata)),
+                    child.clone(),
+                )))
+            })
+            .unwrap();
+
+            let error = format!("{:?}", FastFormatArrowRawData::new(forged).err().unwrap());
+            assert!(error.contains("claims 1000000000000000 bytes"), "{}", error);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_decompression_rejects_huge_zstd_frame() {
+        use crate::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+        use crate::compression::{
+            map_children, max_decompressed_bytes, CODEC_KEY, DATA_TYPE_KEY, LEN_KEY,
+        };
+        use arrow::datatypes::{DataType::UInt8, UInt8Type};
+
+        use std::sync::Arc;
+
+        // A zstd frame header declaring `len` bytes of content: the magic number, a descriptor
+        // for a single segment with an 8 bytes content size, and the content size.
+        let frame = |len: u64, padding: usize| {
+            let mut frame = vec![0x28, 0xB5, 0x2F, 0xFD, 0xE0];
+            frame.extend_from_slice(&len.to_le_bytes());
+            frame.resize(frame.len() + padding, 0);
+            frame
+        };
+
+        let message = |frame: Vec<u8>, len: u64| {
+            let array_data = FastFormatArrowBuilder::new()
+                .push_primitive_array::<UInt8Type>("data", frame, UInt8, false)
+                .into_arrow()
+                .unwrap();
+
+            map_children(array_data, |field, child| {
+                let mut metadata = field.metadata().clone();
+                metadata.insert(CODEC_KEY.to_string(), "zstd".to_string());
+                metadata.insert(DATA_TYPE_KEY.to_string(), UInt8.to_string());
+                metadata.insert(LEN_KEY.to_string(), len.to_string());
+
+                Ok(Some((
+                    Arc::new(field.as_ref().clone().with_metadata(metadata)),
+                    child.clone(),
+                )))
+            })
+            .unwrap()
+        };
+
+        // 64 GiB claimed by a few bytes: more than the frame can hold.
+        let len = 64 << 30;
+        let error = format!(
+            "{:?}",
+            FastFormatArrowRawData::new(message(frame(len, 4), len))
+                .err()
+                .unwrap()
+        );
+        assert!(error.contains("compressed bytes hold at most"), "{}", error);
+
+        // A frame large enough to hold its claimed size is still capped.
+        let len = max_decompressed_bytes() as u64 + 1;
+        let error = format!(
+            "{:?}",
+            FastFormatArrowRawData::new(message(frame(len, len as usize / 32768 + 1), len))
+                .err()
+                .unwrap()
+        );
+        assert!(
+            error.contains("see set_max_decompressed_bytes"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! ChaCha20-Poly1305 encryption of fastformat messages, for sensor data crossing untrusted
+//! networks.
+//!
+//! [`encrypt`] wraps a message in an envelope holding the message, serialized as an Arrow IPC
+//! stream and encrypted, and the random nonce used. The id of the key is recorded in the
+//! envelope, so that receivers can pick the right key with [`key_id`] before calling
+//! [`decrypt`]:
+//!
+//! ```
+//! use arrow::datatypes::{DataType::UInt8, UInt8Type};
+//! use chacha20poly1305::Key;
+//! use fastformat_converter::{arrow::FastFormatArrowBuilder, encryption};
+//!
+//! let array_data = FastFormatArrowBuilder::new()
+//!     .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+//!     .into_arrow()
+//!     .unwrap();
+//!
+//! let key = Key::from([7; 32]);
+//! let encrypted = encryption::encrypt(array_data.clone(), &key, "fleet-2024").unwrap();
+//!
+//! assert_eq!(encryption::key_id(&encrypted).as_deref(), Some("fleet-2024"));
+//! assert_eq!(encryption::decrypt(encrypted, &key).unwrap(), array_data);
+//! ```
+//!
+//! To both sign and encrypt a message, sign it with [`crate::signing::sign`] first (if the
+//! `ed25519-dalek` feature is enabled), then encrypt the signed message.
+
+use crate::{
+    envelope,
+    ipc::{decode_ipc, encode_ipc},
+};
+use eyre::{Report, Result};
+
+use chacha20poly1305::{
+    aead::{Aead, Generate, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+const ALGORITHM: &str = "chacha20-poly1305";
+
+/// Envelope field holding the nonce.
+const NONCE: &str = "nonce";
+
+/// Encrypts the fastformat message `array_data` with `key`, named `key_id` for receivers. A
+/// new random nonce is drawn for every message.
+pub fn encrypt(
+    array_data: arrow::array::ArrayData,
+    key: &Key,
+    key_id: &str,
+) -> Result<arrow::array::ArrayData> {
+    let payload = encode_ipc(array_data)?;
+
+    let nonce = Nonce::generate();
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, payload.as_slice())
+        .map_err(|_| Report::msg("ChaCha20-Poly1305 encryption failed"))?;
+
+    envelope::seal(ciphertext, NONCE, nonce.to_vec(), ALGORITHM, key_id)
+}
+
+/// Decrypts a message encrypted by [`encrypt`] with `key`.
+///
+/// # Errors
+///
+/// Returns an error if `array_data` isn't an encrypted message, or if it can't be decrypted
+/// with `key`, e.g. because the message was tampered with.
+pub fn decrypt(array_data: arrow::array::ArrayData, key: &Key) -> Result<arrow::array::ArrayData> {
+    use arrow::datatypes::UInt8Type;
+
+    let raw_data = envelope::open(array_data, NONCE, ALGORITHM)?;
+
+    let ciphertext = raw_data.primitive_array_view::<UInt8Type>("payload")?;
+    let nonce = Nonce::try_from(raw_data.primitive_array_view::<UInt8Type>(NONCE)?)
+        .map_err(|_| Report::msg("Invalid ChaCha20-Poly1305 nonce"))?;
+
+    let payload = ChaCha20Poly1305::new(key)
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| Report::msg("The message can't be decrypted with this key"))?;
+
+    decode_ipc(&payload)
+}
+
+/// Returns the id of the key that encrypted `array_data`, or `None` if it isn't an encrypted
+/// message.
+pub fn key_id(array_data: &arrow::array::ArrayData) -> Option<String> {
+    envelope::key_id(array_data, NONCE)
+}
+
+mod tests {
+    #[test]
+    fn test_encryption_tampering() {
+        use crate::arrow::FastFormatArrowBuilder;
+        use crate::encryption;
+        use arrow::datatypes::{DataType::UInt8, UInt8Type};
+        use chacha20poly1305::Key;
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+            .into_arrow()
+            .unwrap();
+
+        let key = Key::from([7; 32]);
+        let encrypted = encryption::encrypt(array_data.clone(), &key, "fleet-2024").unwrap();
+
+        // Nonces are never reused.
+        let other = encryption::encrypt(array_data.clone(), &key, "fleet-2024").unwrap();
+        assert_ne!(
+            encrypted.child_data()[1].buffers()[0],
+            other.child_data()[1].buffers()[0]
+        );
+
+        assert!(encryption::decrypt(encrypted.clone(), &Key::from([8; 32])).is_err());
+        assert!(encryption::decrypt(array_data, &key).is_err());
+
+        // Flip a byte of the ciphertext.
+        let mut bytes = encrypted.child_data()[0].buffers()[0].as_slice().to_vec();
+        bytes[100] ^= 1;
+        let tampered = crate::envelope::seal(
+            bytes,
+            "nonce",
+            encrypted.child_data()[1].buffers()[0].as_slice().to_vec(),
+            "chacha20-poly1305",
+            "fleet-2024",
+        )
+        .unwrap();
+
+        assert!(encryption::decrypt(tampered, &key).is_err());
+    }
+}
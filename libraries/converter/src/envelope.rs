@@ -0,0 +1,84 @@
+//! Messages wrapping another serialized fastformat message, e.g. to sign or encrypt it.
+//!
+//! An envelope has two `UInt8` fields: `"payload"`, and a field holding the signature or nonce,
+//! whose metadata names the algorithm and the key used.
+
+use crate::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+use eyre::{Report, Result};
+
+use std::{collections::HashMap, sync::Arc};
+
+/// Field metadata key holding the algorithm of an envelope.
+pub(crate) const ALGORITHM_KEY: &str = "fastformat.security.algorithm";
+
+/// Field metadata key holding the id of the key used for an envelope, e.g. to pick the key of
+/// the right sender.
+pub(crate) const KEY_ID_KEY: &str = "fastformat.security.key_id";
+
+/// Wraps `payload` with the `field` bytes, recording `algorithm` and `key_id` in the metadata
+/// of `field`.
+pub(crate) fn seal(
+    payload: Vec<u8>,
+    field: &str,
+    bytes: Vec<u8>,
+    algorithm: &str,
+    key_id: &str,
+) -> Result<arrow::array::ArrayData> {
+    use arrow::datatypes::{DataType::UInt8, Field, UInt8Type};
+
+    let metadata = HashMap::from([
+        (ALGORITHM_KEY.to_string(), algorithm.to_string()),
+        (KEY_ID_KEY.to_string(), key_id.to_string()),
+    ]);
+
+    let schema = [
+        (0, Arc::new(Field::new("payload", UInt8, false))),
+        (
+            1,
+            Arc::new(Field::new(field, UInt8, false).with_metadata(metadata)),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    FastFormatArrowBuilder::with_schema(schema)
+        .push_primitive_array::<UInt8Type>("payload", payload, UInt8, false)
+        .push_primitive_array::<UInt8Type>(field, bytes, UInt8, false)
+        .into_arrow()
+}
+
+/// Loads an envelope sealed by [`seal`] with `algorithm`.
+pub(crate) fn open(
+    array_data: arrow::array::ArrayData,
+    field: &str,
+    algorithm: &str,
+) -> Result<FastFormatArrowRawData> {
+    use arrow::datatypes::UInt8Type;
+
+    let raw_data = FastFormatArrowRawData::new(array_data)?;
+
+    if !raw_data.has_field(field)
+        || raw_data
+            .field_metadata(field)?
+            .get(ALGORITHM_KEY)
+            .map(String::as_str)
+            != Some(algorithm)
+    {
+        return Err(Report::msg(format!("Not a {} envelope", algorithm)));
+    }
+
+    raw_data
+        .load_primitive::<UInt8Type>("payload")?
+        .load_primitive::<UInt8Type>(field)
+}
+
+/// Returns the key id recorded in the metadata of `field`, if `array_data` is an envelope.
+pub(crate) fn key_id(array_data: &arrow::array::ArrayData, field: &str) -> Option<String> {
+    match array_data.data_type() {
+        arrow::datatypes::DataType::Union(fields, _) => fields
+            .iter()
+            .find(|(_, candidate)| candidate.name() == field)
+            .and_then(|(_, field)| field.metadata().get(KEY_ID_KEY).cloned()),
+        _ => None,
+    }
+}
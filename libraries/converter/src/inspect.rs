@@ -0,0 +1,229 @@
+//! Human-readable summaries of fastformat messages.
+//!
+//! Printing an `ArrayData` with `Debug` dumps every value, which for an image means megabytes
+//! of numbers. [`inspect`] instead lists the fields of the message with their type, their length
+//! and their first few values:
+//!
+//! ```
+//! use arrow::datatypes::{DataType::{UInt8, Utf8}, UInt8Type};
+//! use fastformat_converter::{arrow::FastFormatArrowBuilder, inspect};
+//!
+//! let array_data = FastFormatArrowBuilder::new()
+//!     .push_primitive_array::<UInt8Type>("data", vec![0; 921600], UInt8, false)
+//!     .push_utf_singleton("encoding", "RGB8", Utf8, false)
+//!     .into_arrow()
+//!     .unwrap();
+//!
+//! let summary = inspect::inspect(&array_data).unwrap();
+//!
+//! assert_eq!(
+//!     summary.to_string(),
+//!     "data: UInt8 [921600] = [0, 0, 0, 0, 0, 0, 0, 0, ...]\nencoding: Utf8 [1] = [\"RGB8\"]\n"
+//! );
+//! ```
+
+use eyre::{Context, Report, Result};
+
+use std::fmt::Display;
+
+/// Number of values shown per field by [`inspect`].
+pub const DEFAULT_MAX_VALUES: usize = 8;
+
+/// Summary of one field of a message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSummary {
+    pub name: String,
+    pub data_type: arrow::datatypes::DataType,
+    /// Number of values in the field.
+    pub len: usize,
+    /// The first values of the field, formatted.
+    pub values: Vec<String>,
+}
+
+/// Summary of a message, one entry per field in schema order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub fields: Vec<FieldSummary>,
+}
+
+/// Summarizes a fastformat message, showing at most [`DEFAULT_MAX_VALUES`] values per field.
+pub fn inspect(array_data: &arrow::array::ArrayData) -> Result<Summary> {
+    inspect_with(array_data, DEFAULT_MAX_VALUES)
+}
+
+/// Summarizes a fastformat message, showing at most `max_values` values per field.
+///
+/// # Errors
+///
+/// Returns an error if `array_data` isn't a `UnionArray`, or if a field can't be formatted.
+pub fn inspect_with(array_data: &arrow::array::ArrayData, max_values: usize) -> Result<Summary> {
+    use arrow::array::Array;
+
+    if !matches!(
+        array_data.data_type(),
+        arrow::datatypes::DataType::Union(_, _)
+    ) {
+        return Err(Report::msg(format!(
+            "Expected a fastformat UnionArray, got {}",
+            array_data.data_type()
+        )));
+    }
+
+    let array = arrow::array::UnionArray::from(array_data.clone());
+    let arrow::datatypes::DataType::Union(union_fields, _) = array.data_type() else {
+        unreachable!("checked above");
+    };
+
+    let options = arrow::util::display::FormatOptions::default();
+
+    let fields = union_fields
+        .iter()
+        .map(|(type_id, field)| {
+            let child = array.child(type_id);
+
+            let formatter = arrow::util::display::ArrayFormatter::try_new(child, &options)
+                .wrap_err(format!("Failed to format field {}", field.name()))?;
+
+            let quoted = matches!(
+                child.data_type(),
+                arrow::datatypes::DataType::Utf8 | arrow::datatypes::DataType::LargeUtf8
+            );
+
+            let values = (0..child.len().min(max_values))
+                .map(|index| match quoted {
+                    true => format!("{:?}", formatter.value(index).to_string()),
+                    false => formatter.value(index).to_string(),
+                })
+                .collect();
+
+            Ok(FieldSummary {
+                name: field.name().clone(),
+                data_type: child.data_type().clone(),
+                len: child.len(),
+                values,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Summary { fields })
+}
+
+impl Display for FieldSummary {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "{}: {} [{}] = [{}",
+            self.name,
+            self.data_type,
+            self.len,
+            self.values.join(", ")
+        )?;
+
+        if self.len > self.values.len() {
+            fmt.write_str(if self.values.is_empty() {
+                "..."
+            } else {
+                ", ..."
+            })?;
+        }
+
+        fmt.write_str("]")
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for field in &self.fields {
+            writeln!(fmt, "{}", field)?;
+        }
+
+        Ok(())
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_inspect_image() {
+        use crate::{arrow::FastFormatArrowBuilder, inspect::inspect};
+        use arrow::datatypes::{
+            DataType::{UInt32, UInt8, Utf8},
+            UInt32Type, UInt8Type,
+        };
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_array::<UInt8Type>("data", (0..27).collect(), UInt8, false)
+            .push_primitive_singleton::<UInt32Type>("width", 3, UInt32, false)
+            .push_primitive_singleton::<UInt32Type>("height", 3, UInt32, false)
+            .push_utf_singleton("encoding", "RGB8", Utf8, false)
+            .push_utf_array("name", vec![], Utf8, true)
+            .into_arrow()
+            .unwrap();
+
+        let summary = inspect(&array_data).unwrap();
+
+        assert_eq!(summary.fields.len(), 5);
+        assert_eq!(summary.fields[0].len, 27);
+        assert_eq!(summary.fields[0].values.len(), 8);
+        assert_eq!(
+            summary.to_string(),
+            "data: UInt8 [27] = [0, 1, 2, 3, 4, 5, 6, 7, ...]\n\
+             width: UInt32 [1] = [3]\n\
+             height: UInt32 [1] = [3]\n\
+             encoding: Utf8 [1] = [\"RGB8\"]\n\
+             name: Utf8 [0] = []\n"
+        );
+    }
+
+    #[test]
+    fn test_inspect_bbox() {
+        use crate::{arrow::FastFormatArrowBuilder, inspect::inspect_with};
+        use arrow::datatypes::{
+            DataType::{Float32, Utf8},
+            Float32Type,
+        };
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_array::<Float32Type>(
+                "data",
+                vec![1.0, 1.0, 2.0, 2.0, 1.5, 1.5, 3.0, 3.0],
+                Float32,
+                false,
+            )
+            .push_primitive_array::<Float32Type>("confidence", vec![0.98, 0.5], Float32, false)
+            .push_utf_array(
+                "label",
+                vec!["cat".to_string(), "dog".to_string()],
+                Utf8,
+                false,
+            )
+            .push_utf_singleton("encoding", "XYXY", Utf8, false)
+            .into_arrow()
+            .unwrap();
+
+        assert_eq!(
+            inspect_with(&array_data, 2).unwrap().to_string(),
+            "data: Float32 [8] = [1.0, 1.0, ...]\n\
+             confidence: Float32 [2] = [0.98, 0.5]\n\
+             label: Utf8 [2] = [\"cat\", \"dog\"]\n\
+             encoding: Utf8 [1] = [\"XYXY\"]\n"
+        );
+
+        let summary = inspect_with(&array_data, 0).unwrap();
+
+        assert!(summary.fields.iter().all(|field| field.values.is_empty()));
+        assert_eq!(
+            summary.fields[1].to_string(),
+            "confidence: Float32 [2] = [...]"
+        );
+    }
+
+    #[test]
+    fn test_inspect_rejects_non_union() {
+        use crate::inspect::inspect;
+        use arrow::array::{Array, UInt8Array};
+
+        let array_data = UInt8Array::from(vec![0, 1, 2]).into_data();
+
+        assert!(inspect(&array_data).is_err());
+    }
+}
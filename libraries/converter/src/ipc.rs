@@ -0,0 +1,56 @@
+//! Serialization of single fastformat messages as Arrow IPC streams, the format of recorded,
+//! signed and encrypted messages, for tools that store messages as opaque bytes.
+
+use eyre::{Context, Report, Result};
+
+use std::sync::Arc;
+
+/// Name of the single column of the Arrow IPC streams.
+const COLUMN: &str = "fastformat";
+
+/// Serializes a fastformat message as an Arrow IPC stream with a single column.
+pub fn encode_ipc(array_data: arrow::array::ArrayData) -> Result<Vec<u8>> {
+    let array = arrow::array::make_array(array_data);
+
+    let schema = Arc::new(arrow::datatypes::Schema::new(vec![
+        arrow::datatypes::Field::new(COLUMN, array.data_type().clone(), false),
+    ]));
+    let batch = arrow::record_batch::RecordBatch::try_new(schema.clone(), vec![array])
+        .wrap_err("Failed to create the Arrow record batch")?;
+
+    let mut bytes = Vec::new();
+    {
+        let mut writer = arrow::ipc::writer::StreamWriter::try_new(&mut bytes, &schema)
+            .wrap_err("Failed to create the Arrow IPC stream")?;
+
+        writer
+            .write(&batch)
+            .wrap_err("Failed to write the Arrow IPC record batch")?;
+        writer
+            .finish()
+            .wrap_err("Failed to finish the Arrow IPC stream")?;
+    }
+
+    Ok(bytes)
+}
+
+/// Reads back a fastformat message serialized by [`encode_ipc`].
+pub fn decode_ipc(bytes: &[u8]) -> Result<arrow::array::ArrayData> {
+    use arrow::array::Array;
+
+    let mut reader = arrow::ipc::reader::StreamReader::try_new(bytes, None)
+        .wrap_err("Failed to read the Arrow IPC stream")?;
+
+    let batch = reader
+        .next()
+        .ok_or_else(|| Report::msg("The Arrow IPC stream has no record batch"))?
+        .wrap_err("Failed to read the Arrow IPC record batch")?;
+
+    match batch.columns() {
+        [column] => Ok(column.to_data()),
+        columns => Err(Report::msg(format!(
+            "Expected a single fastformat column, got {}",
+            columns.len()
+        ))),
+    }
+}
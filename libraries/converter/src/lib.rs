@@ -1,5 +1,29 @@
+pub mod accounting;
+pub mod policy;
+
 #[cfg(feature = "arrow")]
 pub mod arrow;
 
+#[cfg(feature = "arrow")]
+pub mod compression;
+
+#[cfg(feature = "arrow")]
+pub mod inspect;
+
+#[cfg(feature = "arrow")]
+pub mod ipc;
+
 #[cfg(feature = "ndarray")]
 pub mod ndarray;
+
+#[cfg(feature = "mcap")]
+pub mod recording;
+
+#[cfg(any(feature = "ed25519-dalek", feature = "chacha20poly1305"))]
+mod envelope;
+
+#[cfg(feature = "ed25519-dalek")]
+pub mod signing;
+
+#[cfg(feature = "chacha20poly1305")]
+pub mod encryption;
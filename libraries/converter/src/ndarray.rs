@@ -1,11 +1,54 @@
-use eyre::Result;
+use crate::{accounting, policy};
+use eyre::{Context, Result};
+
+/// Returns the elements of `array` in row-major order, to create a datatype from it.
+///
+/// The buffer of the array is moved when it is in standard layout, and its elements are copied
+/// under the copy policy otherwise, e.g. for transposed or strided views.
+pub fn into_row_major_vec<T: Clone, D: ndarray::Dimension>(
+    conversion: &'static str,
+    array: ndarray::Array<T, D>,
+) -> Result<Vec<T>> {
+    let len = array.len();
+
+    if !array.is_standard_layout() {
+        let bytes = len * std::mem::size_of::<T>();
+
+        policy::check(conversion, bytes)?;
+        accounting::record(conversion, bytes, true);
+
+        return Ok(array.iter().cloned().collect());
+    }
+
+    // Sliced arrays can keep elements before and after the slice in their buffer.
+    let (mut data, offset) = array.into_raw_vec_and_offset();
+    data.drain(..offset.unwrap_or(0));
+    data.truncate(len);
+
+    Ok(data)
+}
 
 #[derive(Debug)]
 pub enum Ndarray {
     F32IX1(ndarray::Array<f32, ndarray::Ix1>),
     U8IX2(ndarray::Array<u8, ndarray::Ix2>),
     U8IX3(ndarray::Array<u8, ndarray::Ix3>),
+    I16IX2(ndarray::Array<i16, ndarray::Ix2>),
+    I32IX2(ndarray::Array<i32, ndarray::Ix2>),
+    F64IX2(ndarray::Array<f64, ndarray::Ix2>),
     STRIX1(ndarray::Array<String, ndarray::Ix1>),
+    U16IX2(ndarray::Array<u16, ndarray::Ix2>),
+    F32IX3(ndarray::Array<f32, ndarray::Ix3>),
+    F64IX1(ndarray::Array<f64, ndarray::Ix1>),
+    I64IX1(ndarray::Array<i64, ndarray::Ix1>),
+    U8IXD(ndarray::Array<u8, ndarray::IxDyn>),
+    I16IXD(ndarray::Array<i16, ndarray::IxDyn>),
+    I32IXD(ndarray::Array<i32, ndarray::IxDyn>),
+    F32IXD(ndarray::Array<f32, ndarray::IxDyn>),
+    F64IXD(ndarray::Array<f64, ndarray::IxDyn>),
+    STRIXD(ndarray::Array<String, ndarray::IxDyn>),
+    U16IXD(ndarray::Array<u16, ndarray::IxDyn>),
+    I64IXD(ndarray::Array<i64, ndarray::IxDyn>),
 }
 
 impl Ndarray {
@@ -14,13 +57,73 @@ impl Ndarray {
             Ndarray::F32IX1(array) => array.as_ptr() as *const u64,
             Ndarray::U8IX2(array) => array.as_ptr() as *const u64,
             Ndarray::U8IX3(array) => array.as_ptr() as *const u64,
+            Ndarray::I16IX2(array) => array.as_ptr() as *const u64,
+            Ndarray::I32IX2(array) => array.as_ptr() as *const u64,
+            Ndarray::F64IX2(array) => array.as_ptr() as *const u64,
             Ndarray::STRIX1(array) => array.as_ptr() as *const u64,
+            Ndarray::U16IX2(array) => array.as_ptr() as *const u64,
+            Ndarray::F32IX3(array) => array.as_ptr() as *const u64,
+            Ndarray::F64IX1(array) => array.as_ptr() as *const u64,
+            Ndarray::I64IX1(array) => array.as_ptr() as *const u64,
+            Ndarray::U8IXD(array) => array.as_ptr() as *const u64,
+            Ndarray::I16IXD(array) => array.as_ptr() as *const u64,
+            Ndarray::I32IXD(array) => array.as_ptr() as *const u64,
+            Ndarray::F32IXD(array) => array.as_ptr() as *const u64,
+            Ndarray::F64IXD(array) => array.as_ptr() as *const u64,
+            Ndarray::STRIXD(array) => array.as_ptr() as *const u64,
+            Ndarray::U16IXD(array) => array.as_ptr() as *const u64,
+            Ndarray::I64IXD(array) => array.as_ptr() as *const u64,
+        }
+    }
+
+    /// Returns the shape of the array, e.g. to check the rank of a dynamic-dimension array.
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            Ndarray::F32IX1(array) => array.shape(),
+            Ndarray::U8IX2(array) => array.shape(),
+            Ndarray::U8IX3(array) => array.shape(),
+            Ndarray::I16IX2(array) => array.shape(),
+            Ndarray::I32IX2(array) => array.shape(),
+            Ndarray::F64IX2(array) => array.shape(),
+            Ndarray::STRIX1(array) => array.shape(),
+            Ndarray::U16IX2(array) => array.shape(),
+            Ndarray::F32IX3(array) => array.shape(),
+            Ndarray::F64IX1(array) => array.shape(),
+            Ndarray::I64IX1(array) => array.shape(),
+            Ndarray::U8IXD(array) => array.shape(),
+            Ndarray::I16IXD(array) => array.shape(),
+            Ndarray::I32IXD(array) => array.shape(),
+            Ndarray::F32IXD(array) => array.shape(),
+            Ndarray::F64IXD(array) => array.shape(),
+            Ndarray::STRIXD(array) => array.shape(),
+            Ndarray::U16IXD(array) => array.shape(),
+            Ndarray::I64IXD(array) => array.shape(),
+        }
+    }
+
+    /// Converts a fixed-dimension array into the dynamic-dimension variant of the same type,
+    /// without copying.
+    pub fn into_dyn(self) -> Self {
+        match self {
+            Ndarray::F32IX1(array) => Ndarray::F32IXD(array.into_dyn()),
+            Ndarray::U8IX2(array) => Ndarray::U8IXD(array.into_dyn()),
+            Ndarray::U8IX3(array) => Ndarray::U8IXD(array.into_dyn()),
+            Ndarray::I16IX2(array) => Ndarray::I16IXD(array.into_dyn()),
+            Ndarray::I32IX2(array) => Ndarray::I32IXD(array.into_dyn()),
+            Ndarray::F64IX2(array) => Ndarray::F64IXD(array.into_dyn()),
+            Ndarray::STRIX1(array) => Ndarray::STRIXD(array.into_dyn()),
+            Ndarray::U16IX2(array) => Ndarray::U16IXD(array.into_dyn()),
+            Ndarray::F32IX3(array) => Ndarray::F32IXD(array.into_dyn()),
+            Ndarray::F64IX1(array) => Ndarray::F64IXD(array.into_dyn()),
+            Ndarray::I64IX1(array) => Ndarray::I64IXD(array.into_dyn()),
+            array => array,
         }
     }
 
     pub fn into_u8_ix3(self) -> Result<ndarray::Array<u8, ndarray::Ix3>> {
         match self {
             Ndarray::U8IX3(array) => Ok(array),
+            Ndarray::U8IXD(array) => array.into_dimensionality().wrap_err("Expected U8IX3"),
             _ => Err(eyre::Report::msg("Expected U8IX3")),
         }
     }
@@ -28,6 +131,7 @@ impl Ndarray {
     pub fn into_u8_ix2(self) -> Result<ndarray::Array<u8, ndarray::Ix2>> {
         match self {
             Ndarray::U8IX2(array) => Ok(array),
+            Ndarray::U8IXD(array) => array.into_dimensionality().wrap_err("Expected U8IX2"),
             _ => Err(eyre::Report::msg("Expected U8IX2")),
         }
     }
@@ -35,9 +139,130 @@ impl Ndarray {
     pub fn into_f32_ix1(self) -> Result<ndarray::Array<f32, ndarray::Ix1>> {
         match self {
             Ndarray::F32IX1(array) => Ok(array),
+            Ndarray::F32IXD(array) => array.into_dimensionality().wrap_err("Expected F32IX1"),
             _ => Err(eyre::Report::msg("Expected F32IX1")),
         }
     }
+
+    pub fn into_i16_ix2(self) -> Result<ndarray::Array<i16, ndarray::Ix2>> {
+        match self {
+            Ndarray::I16IX2(array) => Ok(array),
+            Ndarray::I16IXD(array) => array.into_dimensionality().wrap_err("Expected I16IX2"),
+            _ => Err(eyre::Report::msg("Expected I16IX2")),
+        }
+    }
+
+    pub fn into_i32_ix2(self) -> Result<ndarray::Array<i32, ndarray::Ix2>> {
+        match self {
+            Ndarray::I32IX2(array) => Ok(array),
+            Ndarray::I32IXD(array) => array.into_dimensionality().wrap_err("Expected I32IX2"),
+            _ => Err(eyre::Report::msg("Expected I32IX2")),
+        }
+    }
+
+    pub fn into_f64_ix2(self) -> Result<ndarray::Array<f64, ndarray::Ix2>> {
+        match self {
+            Ndarray::F64IX2(array) => Ok(array),
+            Ndarray::F64IXD(array) => array.into_dimensionality().wrap_err("Expected F64IX2"),
+            _ => Err(eyre::Report::msg("Expected F64IX2")),
+        }
+    }
+
+    pub fn into_u16_ix2(self) -> Result<ndarray::Array<u16, ndarray::Ix2>> {
+        match self {
+            Ndarray::U16IX2(array) => Ok(array),
+            Ndarray::U16IXD(array) => array.into_dimensionality().wrap_err("Expected U16IX2"),
+            _ => Err(eyre::Report::msg("Expected U16IX2")),
+        }
+    }
+
+    pub fn into_f32_ix3(self) -> Result<ndarray::Array<f32, ndarray::Ix3>> {
+        match self {
+            Ndarray::F32IX3(array) => Ok(array),
+            Ndarray::F32IXD(array) => array.into_dimensionality().wrap_err("Expected F32IX3"),
+            _ => Err(eyre::Report::msg("Expected F32IX3")),
+        }
+    }
+
+    pub fn into_f64_ix1(self) -> Result<ndarray::Array<f64, ndarray::Ix1>> {
+        match self {
+            Ndarray::F64IX1(array) => Ok(array),
+            Ndarray::F64IXD(array) => array.into_dimensionality().wrap_err("Expected F64IX1"),
+            _ => Err(eyre::Report::msg("Expected F64IX1")),
+        }
+    }
+
+    pub fn into_i64_ix1(self) -> Result<ndarray::Array<i64, ndarray::Ix1>> {
+        match self {
+            Ndarray::I64IX1(array) => Ok(array),
+            Ndarray::I64IXD(array) => array.into_dimensionality().wrap_err("Expected I64IX1"),
+            _ => Err(eyre::Report::msg("Expected I64IX1")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_u8_ixd(self) -> Result<ndarray::ArrayD<u8>> {
+        match self.into_dyn() {
+            Ndarray::U8IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected U8IXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_i16_ixd(self) -> Result<ndarray::ArrayD<i16>> {
+        match self.into_dyn() {
+            Ndarray::I16IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected I16IXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_i32_ixd(self) -> Result<ndarray::ArrayD<i32>> {
+        match self.into_dyn() {
+            Ndarray::I32IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected I32IXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_f32_ixd(self) -> Result<ndarray::ArrayD<f32>> {
+        match self.into_dyn() {
+            Ndarray::F32IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected F32IXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_f64_ixd(self) -> Result<ndarray::ArrayD<f64>> {
+        match self.into_dyn() {
+            Ndarray::F64IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected F64IXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_str_ixd(self) -> Result<ndarray::ArrayD<String>> {
+        match self.into_dyn() {
+            Ndarray::STRIXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected STRIXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_u16_ixd(self) -> Result<ndarray::ArrayD<u16>> {
+        match self.into_dyn() {
+            Ndarray::U16IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected U16IXD")),
+        }
+    }
+
+    /// Accepts the fixed-dimension variants of the same type as well.
+    pub fn into_i64_ixd(self) -> Result<ndarray::ArrayD<i64>> {
+        match self.into_dyn() {
+            Ndarray::I64IXD(array) => Ok(array),
+            _ => Err(eyre::Report::msg("Expected I64IXD")),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -45,7 +270,22 @@ pub enum NdarrayView<'a> {
     F32IX1(ndarray::ArrayView<'a, f32, ndarray::Ix1>),
     U8IX2(ndarray::ArrayView<'a, u8, ndarray::Ix2>),
     U8IX3(ndarray::ArrayView<'a, u8, ndarray::Ix3>),
+    I16IX2(ndarray::ArrayView<'a, i16, ndarray::Ix2>),
+    I32IX2(ndarray::ArrayView<'a, i32, ndarray::Ix2>),
+    F64IX2(ndarray::ArrayView<'a, f64, ndarray::Ix2>),
     STRIX1(ndarray::ArrayView<'a, String, ndarray::Ix1>),
+    U16IX2(ndarray::ArrayView<'a, u16, ndarray::Ix2>),
+    F32IX3(ndarray::ArrayView<'a, f32, ndarray::Ix3>),
+    F64IX1(ndarray::ArrayView<'a, f64, ndarray::Ix1>),
+    I64IX1(ndarray::ArrayView<'a, i64, ndarray::Ix1>),
+    U8IXD(ndarray::ArrayView<'a, u8, ndarray::IxDyn>),
+    I16IXD(ndarray::ArrayView<'a, i16, ndarray::IxDyn>),
+    I32IXD(ndarray::ArrayView<'a, i32, ndarray::IxDyn>),
+    F32IXD(ndarray::ArrayView<'a, f32, ndarray::IxDyn>),
+    F64IXD(ndarray::ArrayView<'a, f64, ndarray::IxDyn>),
+    STRIXD(ndarray::ArrayView<'a, String, ndarray::IxDyn>),
+    U16IXD(ndarray::ArrayView<'a, u16, ndarray::IxDyn>),
+    I64IXD(ndarray::ArrayView<'a, i64, ndarray::IxDyn>),
 }
 
 impl<'a> NdarrayView<'a> {
@@ -54,7 +294,47 @@ impl<'a> NdarrayView<'a> {
             NdarrayView::F32IX1(array) => array.as_ptr() as *const u64,
             NdarrayView::U8IX2(array) => array.as_ptr() as *const u64,
             NdarrayView::U8IX3(array) => array.as_ptr() as *const u64,
+            NdarrayView::I16IX2(array) => array.as_ptr() as *const u64,
+            NdarrayView::I32IX2(array) => array.as_ptr() as *const u64,
+            NdarrayView::F64IX2(array) => array.as_ptr() as *const u64,
             NdarrayView::STRIX1(array) => array.as_ptr() as *const u64,
+            NdarrayView::U16IX2(array) => array.as_ptr() as *const u64,
+            NdarrayView::F32IX3(array) => array.as_ptr() as *const u64,
+            NdarrayView::F64IX1(array) => array.as_ptr() as *const u64,
+            NdarrayView::I64IX1(array) => array.as_ptr() as *const u64,
+            NdarrayView::U8IXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::I16IXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::I32IXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::F32IXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::F64IXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::STRIXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::U16IXD(array) => array.as_ptr() as *const u64,
+            NdarrayView::I64IXD(array) => array.as_ptr() as *const u64,
+        }
+    }
+
+    /// Returns the shape of the array, e.g. to check the rank of a dynamic-dimension array.
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            NdarrayView::F32IX1(array) => array.shape(),
+            NdarrayView::U8IX2(array) => array.shape(),
+            NdarrayView::U8IX3(array) => array.shape(),
+            NdarrayView::I16IX2(array) => array.shape(),
+            NdarrayView::I32IX2(array) => array.shape(),
+            NdarrayView::F64IX2(array) => array.shape(),
+            NdarrayView::STRIX1(array) => array.shape(),
+            NdarrayView::U16IX2(array) => array.shape(),
+            NdarrayView::F32IX3(array) => array.shape(),
+            NdarrayView::F64IX1(array) => array.shape(),
+            NdarrayView::I64IX1(array) => array.shape(),
+            NdarrayView::U8IXD(array) => array.shape(),
+            NdarrayView::I16IXD(array) => array.shape(),
+            NdarrayView::I32IXD(array) => array.shape(),
+            NdarrayView::F32IXD(array) => array.shape(),
+            NdarrayView::F64IXD(array) => array.shape(),
+            NdarrayView::STRIXD(array) => array.shape(),
+            NdarrayView::U16IXD(array) => array.shape(),
+            NdarrayView::I64IXD(array) => array.shape(),
         }
     }
 }
@@ -64,7 +344,22 @@ pub enum NdarrayViewMut<'a> {
     F32IX1(ndarray::ArrayViewMut<'a, f32, ndarray::Ix1>),
     U8IX2(ndarray::ArrayViewMut<'a, u8, ndarray::Ix2>),
     U8IX3(ndarray::ArrayViewMut<'a, u8, ndarray::Ix3>),
+    I16IX2(ndarray::ArrayViewMut<'a, i16, ndarray::Ix2>),
+    I32IX2(ndarray::ArrayViewMut<'a, i32, ndarray::Ix2>),
+    F64IX2(ndarray::ArrayViewMut<'a, f64, ndarray::Ix2>),
     STRIX1(ndarray::ArrayViewMut<'a, String, ndarray::Ix1>),
+    U16IX2(ndarray::ArrayViewMut<'a, u16, ndarray::Ix2>),
+    F32IX3(ndarray::ArrayViewMut<'a, f32, ndarray::Ix3>),
+    F64IX1(ndarray::ArrayViewMut<'a, f64, ndarray::Ix1>),
+    I64IX1(ndarray::ArrayViewMut<'a, i64, ndarray::Ix1>),
+    U8IXD(ndarray::ArrayViewMut<'a, u8, ndarray::IxDyn>),
+    I16IXD(ndarray::ArrayViewMut<'a, i16, ndarray::IxDyn>),
+    I32IXD(ndarray::ArrayViewMut<'a, i32, ndarray::IxDyn>),
+    F32IXD(ndarray::ArrayViewMut<'a, f32, ndarray::IxDyn>),
+    F64IXD(ndarray::ArrayViewMut<'a, f64, ndarray::IxDyn>),
+    STRIXD(ndarray::ArrayViewMut<'a, String, ndarray::IxDyn>),
+    U16IXD(ndarray::ArrayViewMut<'a, u16, ndarray::IxDyn>),
+    I64IXD(ndarray::ArrayViewMut<'a, i64, ndarray::IxDyn>),
 }
 
 impl NdarrayViewMut<'_> {
@@ -73,7 +368,89 @@ impl NdarrayViewMut<'_> {
             NdarrayViewMut::F32IX1(array) => array.as_ptr() as *const u64,
             NdarrayViewMut::U8IX2(array) => array.as_ptr() as *const u64,
             NdarrayViewMut::U8IX3(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::I16IX2(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::I32IX2(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::F64IX2(array) => array.as_ptr() as *const u64,
             NdarrayViewMut::STRIX1(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::U16IX2(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::F32IX3(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::F64IX1(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::I64IX1(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::U8IXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::I16IXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::I32IXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::F32IXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::F64IXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::STRIXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::U16IXD(array) => array.as_ptr() as *const u64,
+            NdarrayViewMut::I64IXD(array) => array.as_ptr() as *const u64,
         }
     }
+
+    /// Returns the shape of the array, e.g. to check the rank of a dynamic-dimension array.
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            NdarrayViewMut::F32IX1(array) => array.shape(),
+            NdarrayViewMut::U8IX2(array) => array.shape(),
+            NdarrayViewMut::U8IX3(array) => array.shape(),
+            NdarrayViewMut::I16IX2(array) => array.shape(),
+            NdarrayViewMut::I32IX2(array) => array.shape(),
+            NdarrayViewMut::F64IX2(array) => array.shape(),
+            NdarrayViewMut::STRIX1(array) => array.shape(),
+            NdarrayViewMut::U16IX2(array) => array.shape(),
+            NdarrayViewMut::F32IX3(array) => array.shape(),
+            NdarrayViewMut::F64IX1(array) => array.shape(),
+            NdarrayViewMut::I64IX1(array) => array.shape(),
+            NdarrayViewMut::U8IXD(array) => array.shape(),
+            NdarrayViewMut::I16IXD(array) => array.shape(),
+            NdarrayViewMut::I32IXD(array) => array.shape(),
+            NdarrayViewMut::F32IXD(array) => array.shape(),
+            NdarrayViewMut::F64IXD(array) => array.shape(),
+            NdarrayViewMut::STRIXD(array) => array.shape(),
+            NdarrayViewMut::U16IXD(array) => array.shape(),
+            NdarrayViewMut::I64IXD(array) => array.shape(),
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_dynamic_dimension() {
+        use crate::ndarray::Ndarray;
+
+        let array = Ndarray::U8IX3(ndarray::Array3::<u8>::zeros((2, 3, 4)));
+        let ptr = array.as_ptr();
+
+        let array = array.into_dyn();
+        assert!(matches!(array, Ndarray::U8IXD(_)));
+        assert_eq!(array.shape(), [2, 3, 4]);
+        assert_eq!(array.as_ptr(), ptr);
+
+        let array = Ndarray::U8IXD(array.into_u8_ixd().unwrap());
+        assert_eq!(array.into_u8_ix3().unwrap().dim(), (2, 3, 4));
+
+        let array = Ndarray::F32IXD(ndarray::ArrayD::<f32>::zeros(vec![5]));
+        assert!(Ndarray::F32IXD(array.into_f32_ixd().unwrap())
+            .into_f64_ixd()
+            .is_err());
+
+        let array = Ndarray::U8IXD(ndarray::ArrayD::<u8>::zeros(vec![2, 3]));
+        assert!(array.into_u8_ix3().is_err());
+    }
+    #[test]
+    fn test_additional_dtypes() {
+        use crate::ndarray::Ndarray;
+
+        let depth = Ndarray::U16IX2(ndarray::Array2::<u16>::zeros((2, 3)));
+        assert_eq!(depth.into_u16_ix2().unwrap().dim(), (2, 3));
+
+        let flow = Ndarray::F32IX3(ndarray::Array3::<f32>::zeros((2, 3, 2)));
+        assert_eq!(flow.into_dyn().into_f32_ix3().unwrap().dim(), (2, 3, 2));
+
+        let position = Ndarray::F64IX1(ndarray::arr1(&[48.85, 2.35, 35.0]));
+        assert!(position.into_f64_ix2().is_err());
+
+        let timestamps = Ndarray::I64IX1(ndarray::arr1(&[0, 1]));
+        assert_eq!(timestamps.into_i64_ixd().unwrap().shape(), [2]);
+    }
 }
@@ -0,0 +1,163 @@
+//! Copy policy for conversions.
+//!
+//! Conversions borrow their data whenever they can, and copy it when they can't (e.g. mutating
+//! a borrowed image). The copy policy decides whether such implicit copies are allowed, so that
+//! memory-constrained users get predictable behavior: a conversion that would have to copy more
+//! than allowed fails instead.
+//!
+//! ```
+//! use fastformat_converter::policy::{self, CopyPolicy};
+//!
+//! // Never copy more than 8 MiB behind the user's back.
+//! policy::set(CopyPolicy::UpTo(8 * 1024 * 1024));
+//!
+//! assert!(policy::check("ImageData::into_u8", 1024).is_ok());
+//! assert!(policy::check("ImageData::into_u8", 16 * 1024 * 1024).is_err());
+//! ```
+//!
+//! Copies that are explicitly requested, like `ensure_owned`, are not subject to the policy.
+//!
+//! [`scoped`] overrides the policy on the current thread only, e.g. to run a single conversion
+//! under a stricter policy without affecting the conversions of other threads.
+
+use eyre::{Report, Result};
+
+use std::{cell::Cell, marker::PhantomData, sync::RwLock};
+
+/// When conversions are allowed to copy data implicitly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CopyPolicy {
+    /// Every implicit copy is allowed. This is the default.
+    #[default]
+    Always,
+    /// Implicit copies of more than the given number of bytes are rejected.
+    UpTo(usize),
+    /// Every implicit copy is rejected: conversions either borrow their data or fail.
+    Never,
+}
+
+impl CopyPolicy {
+    /// Returns whether an implicit copy of `bytes` bytes is allowed.
+    pub fn allows(&self, bytes: usize) -> bool {
+        match self {
+            Self::Always => true,
+            Self::UpTo(max_bytes) => bytes <= *max_bytes,
+            Self::Never => false,
+        }
+    }
+}
+
+static POLICY: RwLock<CopyPolicy> = RwLock::new(CopyPolicy::Always);
+
+thread_local! {
+    /// Override of the process-wide policy on this thread, set by [`scoped`].
+    static SCOPED_POLICY: Cell<Option<CopyPolicy>> = const { Cell::new(None) };
+}
+
+/// Sets the copy policy of every conversion in the process. Threads with a [`scoped`] override
+/// keep using it until it is dropped.
+pub fn set(policy: CopyPolicy) {
+    *POLICY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
+}
+
+/// Returns the current copy policy: the [`scoped`] override of this thread if there is one, the
+/// process-wide policy otherwise.
+pub fn get() -> CopyPolicy {
+    SCOPED_POLICY.with(Cell::get).unwrap_or_else(|| {
+        *POLICY
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    })
+}
+
+/// Overrides the copy policy of the conversions run on the current thread, until the returned
+/// guard is dropped. Overrides can be nested, dropping a guard restores the previous one.
+///
+/// ```
+/// use fastformat_converter::policy::{self, CopyPolicy};
+///
+/// {
+///     let _policy = policy::scoped(CopyPolicy::Never);
+///
+///     assert!(policy::check("ImageData::into_u8", 1024).is_err());
+/// }
+///
+/// assert!(policy::check("ImageData::into_u8", 1024).is_ok());
+/// ```
+pub fn scoped(policy: CopyPolicy) -> ScopedPolicy {
+    ScopedPolicy {
+        previous: SCOPED_POLICY.with(|scoped| scoped.replace(Some(policy))),
+        _not_send: PhantomData,
+    }
+}
+
+/// Guard returned by [`scoped`], restoring the previous policy of the thread when dropped.
+#[must_use = "the override ends when the guard is dropped"]
+#[derive(Debug)]
+pub struct ScopedPolicy {
+    previous: Option<CopyPolicy>,
+    // The override belongs to the thread that created it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ScopedPolicy {
+    fn drop(&mut self) {
+        SCOPED_POLICY.with(|scoped| scoped.set(self.previous));
+    }
+}
+
+/// Returns an error if the current policy rejects `conversion` copying `bytes` bytes.
+pub fn check(conversion: &'static str, bytes: usize) -> Result<()> {
+    let policy = get();
+
+    if policy.allows(bytes) {
+        Ok(())
+    } else {
+        Err(Report::msg(format!(
+            "{} would copy {} bytes, which the copy policy {:?} rejects. Call ensure_owned to copy explicitly.",
+            conversion, bytes, policy
+        )))
+    }
+}
+
+/// Checks a conversion that turns `data` into an owned buffer: only borrowed data gets copied.
+#[inline]
+#[allow(clippy::ptr_arg)] // Whether the data is borrowed is exactly what gets checked.
+pub fn check_cow<T: Clone>(
+    conversion: &'static str,
+    data: &std::borrow::Cow<'_, [T]>,
+) -> Result<()> {
+    match data {
+        std::borrow::Cow::Borrowed(data) => check(conversion, std::mem::size_of_val::<[T]>(*data)),
+        std::borrow::Cow::Owned(_) => Ok(()),
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_scoped_policy() {
+        use crate::policy::{self, CopyPolicy};
+
+        let process_policy = policy::get();
+
+        {
+            let _outer = policy::scoped(CopyPolicy::UpTo(1024));
+            assert_eq!(policy::get(), CopyPolicy::UpTo(1024));
+
+            {
+                let _inner = policy::scoped(CopyPolicy::Never);
+                assert!(policy::check("test", 1).is_err());
+
+                // Other threads keep the process-wide policy.
+                let other_thread = std::thread::spawn(policy::get).join().unwrap();
+                assert_eq!(other_thread, process_policy);
+            }
+
+            assert_eq!(policy::get(), CopyPolicy::UpTo(1024));
+        }
+
+        assert_eq!(policy::get(), process_policy);
+    }
+}
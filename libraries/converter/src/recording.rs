@@ -0,0 +1,456 @@
+//! Recording of fastformat messages to [MCAP](https://mcap.dev) files, and playback.
+//!
+//! A [`Recorder`] writes every message with its topic and timestamp, one MCAP channel per topic.
+//! Messages are stored as Arrow IPC streams holding a single column, so that any datatype can be
+//! recorded and other tools can read them back. A [`Player`] replays a recording with its
+//! original timing, e.g. to debug a dataflow offline:
+//!
+//! ```
+//! use arrow::datatypes::{DataType::{UInt8, Utf8}, UInt8Type};
+//! use fastformat_converter::{
+//!     arrow::FastFormatArrowBuilder,
+//!     recording::{Player, Recorder},
+//! };
+//!
+//! let image = || {
+//!     FastFormatArrowBuilder::new()
+//!         .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+//!         .push_utf_singleton("encoding", "RGB8", Utf8, false)
+//!         .into_arrow()
+//!         .unwrap()
+//! };
+//!
+//! let mut recorder = Recorder::new(std::io::Cursor::new(Vec::new())).unwrap();
+//! recorder.record("camera/image", 1_000_000, image()).unwrap();
+//! recorder.record("camera/image", 34_000_000, image()).unwrap();
+//! let bytes = recorder.finish().unwrap().into_inner();
+//!
+//! let mut topics = Vec::new();
+//! Player::from_bytes(&bytes)
+//!     .unwrap()
+//!     .play(|message| {
+//!         topics.push(message.topic);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//!
+//! assert_eq!(topics, ["camera/image", "camera/image"]);
+//! ```
+
+use crate::ipc::{decode_ipc, encode_ipc};
+use eyre::{Context, Report, Result};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// MCAP message encoding of recorded messages: an Arrow IPC stream with a single column.
+pub const MESSAGE_ENCODING: &str = "arrow.ipc";
+
+/// A recorded message.
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    pub topic: String,
+    /// Time at which the message was recorded, in nanoseconds.
+    pub timestamp_ns: u64,
+    pub array_data: arrow::array::ArrayData,
+}
+
+/// Writes fastformat messages to an MCAP file.
+///
+/// The file is only valid once [`Recorder::finish`] has been called.
+pub struct Recorder<W: Write + Seek> {
+    writer: mcap::Writer<W>,
+    // Channel id and sequence number of the next message, per topic.
+    channels: HashMap<String, (u16, u32)>,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Creates the MCAP file at `path`, truncating it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .wrap_err_with(|| format!("Failed to create recording {}", path.display()))?;
+
+        Self::new(BufWriter::new(file))
+    }
+}
+
+impl<W: Write + Seek> Recorder<W> {
+    pub fn new(writer: W) -> Result<Self> {
+        let writer = mcap::WriteOptions::new()
+            .profile("fastformat")
+            .create(writer)
+            .wrap_err("Failed to start the MCAP recording")?;
+
+        Ok(Self {
+            writer,
+            channels: HashMap::new(),
+        })
+    }
+
+    /// Records a message on `topic`, creating the topic's channel on its first message.
+    ///
+    /// `timestamp_ns` is used to replay the message, so it should increase monotonically, e.g.
+    /// the timestamp of the message's header.
+    pub fn record(
+        &mut self,
+        topic: &str,
+        timestamp_ns: u64,
+        array_data: arrow::array::ArrayData,
+    ) -> Result<()> {
+        let (channel_id, sequence) = match self.channels.get_mut(topic) {
+            Some(channel) => channel,
+            None => {
+                let channel_id = self
+                    .writer
+                    .add_channel(0, topic, MESSAGE_ENCODING, &BTreeMap::new())
+                    .wrap_err_with(|| format!("Failed to add the MCAP channel of {}", topic))?;
+
+                self.channels
+                    .entry(topic.to_string())
+                    .or_insert((channel_id, 0))
+            }
+        };
+
+        let header = mcap::records::MessageHeader {
+            channel_id: *channel_id,
+            sequence: *sequence,
+            log_time: timestamp_ns,
+            publish_time: timestamp_ns,
+        };
+        *sequence = sequence.wrapping_add(1);
+
+        self.writer
+            .write_to_known_channel(&header, &encode_ipc(array_data)?)
+            .wrap_err_with(|| format!("Failed to record a message of {}", topic))
+    }
+
+    /// Writes the MCAP summary and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer
+            .finish()
+            .wrap_err("Failed to finish the MCAP recording")?;
+
+        Ok(self.writer.into_inner())
+    }
+}
+
+/// Replays the messages of an MCAP recording.
+///
+/// Messages are read one chunk at a time, in timestamp order thanks to the chunk index of the
+/// recording, so that recordings larger than memory can be replayed.
+pub struct Player<R: Read + Seek> {
+    reader: R,
+    summary: mcap::Summary,
+    speed: f64,
+}
+
+impl Player<BufReader<File>> {
+    /// Opens the recording at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .wrap_err_with(|| format!("Failed to open recording {}", path.display()))?;
+
+        Self::new(BufReader::new(file))
+            .wrap_err_with(|| format!("Failed to read recording {}", path.display()))
+    }
+}
+
+impl<'a> Player<Cursor<&'a [u8]>> {
+    /// Reads a recording held in memory.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        Self::new(Cursor::new(bytes))
+    }
+}
+
+impl<R: Read + Seek> Player<R> {
+    /// Reads the summary of a recording. Its messages are only read when playing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MCAP file is invalid or wasn't finished, or if one of its channels
+    /// wasn't written by a [`Recorder`].
+    pub fn new(mut reader: R) -> Result<Self> {
+        use mcap::sans_io::{SummaryReadEvent, SummaryReader};
+
+        let mut summary_reader = SummaryReader::new();
+        while let Some(event) = summary_reader.next_event() {
+            match event.wrap_err("Failed to read the MCAP recording")? {
+                SummaryReadEvent::ReadRequest(len) => {
+                    let read = reader
+                        .read(summary_reader.insert(len))
+                        .wrap_err("Failed to read the MCAP recording")?;
+                    summary_reader.notify_read(read);
+                }
+                SummaryReadEvent::SeekRequest(position) => {
+                    let position = reader
+                        .seek(position)
+                        .wrap_err("Failed to read the MCAP recording")?;
+                    summary_reader.notify_seeked(position);
+                }
+            }
+        }
+
+        let summary = summary_reader
+            .finish()
+            .ok_or_else(|| Report::msg("The MCAP recording has no summary, it wasn't finished"))?;
+
+        if let Some(channel) = summary
+            .channels
+            .values()
+            .find(|channel| channel.message_encoding != MESSAGE_ENCODING)
+        {
+            return Err(Report::msg(format!(
+                "Topic {} has message encoding {}, expected {}",
+                channel.topic, channel.message_encoding, MESSAGE_ENCODING
+            )));
+        }
+
+        Ok(Self {
+            reader,
+            summary,
+            speed: 1.0,
+        })
+    }
+
+    /// Sets the playback speed, e.g. `2.0` to replay twice as fast. Defaults to `1.0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `speed` isn't a finite, positive number.
+    pub fn with_speed(mut self, speed: f64) -> Result<Self> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(Report::msg(format!(
+                "Invalid playback speed {}, expected a finite, positive number",
+                speed
+            )));
+        }
+
+        self.speed = speed;
+
+        Ok(self)
+    }
+
+    /// Returns the messages ordered by timestamp, without timing, e.g. to process a recording.
+    pub fn messages(self) -> Result<Messages<R>> {
+        Ok(Messages {
+            indexed_reader: mcap::sans_io::IndexedReader::new(&self.summary)
+                .wrap_err("Failed to read the MCAP chunk index")?,
+            reader: self.reader,
+            channels: self.summary.channels,
+            chunk: Vec::new(),
+            failed: false,
+        })
+    }
+
+    /// Calls `callback` with every message, waiting between messages as long as they were apart
+    /// when recorded, divided by the speed. Stops at the first error returned by `callback`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a message can't be read, if it is too far from the first message to
+    /// be replayed at this speed, or the first error returned by `callback`.
+    pub fn play(self, mut callback: impl FnMut(RecordedMessage) -> Result<()>) -> Result<()> {
+        let speed = self.speed;
+        let mut first_timestamp_ns = None;
+        let start = Instant::now();
+
+        for message in self.messages()? {
+            let message = message?;
+
+            let first_timestamp_ns = *first_timestamp_ns.get_or_insert(message.timestamp_ns);
+            let offset =
+                Duration::from_nanos(message.timestamp_ns.saturating_sub(first_timestamp_ns));
+            let deadline = Duration::try_from_secs_f64(offset.as_secs_f64() / speed)
+                .ok()
+                .and_then(|delay| start.checked_add(delay))
+                .ok_or_else(|| {
+                    Report::msg(format!(
+                        "Message of {} is {:?} after the first one, too far to be replayed at speed {}",
+                        message.topic, offset, speed
+                    ))
+                })?;
+
+            if let Some(delay) = deadline.checked_duration_since(Instant::now()) {
+                std::thread::sleep(delay);
+            }
+
+            callback(message)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator over the messages of a recording ordered by timestamp, see [`Player::messages`].
+/// It ends after the first message that can't be read.
+pub struct Messages<R: Read + Seek> {
+    reader: R,
+    channels: HashMap<u16, Arc<mcap::Channel<'static>>>,
+    indexed_reader: mcap::sans_io::IndexedReader,
+    // Compressed data of the chunk being read.
+    chunk: Vec<u8>,
+    failed: bool,
+}
+
+impl<R: Read + Seek> Messages<R> {
+    fn next_message(&mut self) -> Option<Result<RecordedMessage>> {
+        use mcap::sans_io::IndexedReadEvent;
+
+        loop {
+            let event = match self.indexed_reader.next_event()? {
+                Ok(event) => event,
+                Err(error) => return Some(Err(Report::new(error).wrap_err("Invalid MCAP chunk"))),
+            };
+
+            match event {
+                IndexedReadEvent::ReadChunkRequest { offset, length } => {
+                    if let Err(error) = self.read_chunk(offset, length) {
+                        return Some(Err(error));
+                    }
+                }
+                IndexedReadEvent::Message { header, data } => {
+                    let Some(channel) = self.channels.get(&header.channel_id) else {
+                        return Some(Err(Report::msg(format!(
+                            "Message of unknown MCAP channel {}",
+                            header.channel_id
+                        ))));
+                    };
+
+                    return Some(
+                        decode_ipc(data)
+                            .wrap_err_with(|| format!("Invalid message of {}", channel.topic))
+                            .map(|array_data| RecordedMessage {
+                                topic: channel.topic.clone(),
+                                timestamp_ns: header.log_time,
+                                array_data,
+                            }),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Reads the `length` bytes of chunk data at `offset` into the indexed reader. The length
+    /// comes from the recording, so only the bytes actually there are allocated.
+    fn read_chunk(&mut self, offset: u64, length: usize) -> Result<()> {
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .wrap_err("Failed to read an MCAP chunk")?;
+
+        self.chunk.clear();
+        (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(&mut self.chunk)
+            .wrap_err("Failed to read an MCAP chunk")?;
+
+        if self.chunk.len() != length {
+            return Err(Report::msg(format!(
+                "MCAP chunk at {} holds {} bytes, expected {}",
+                offset,
+                self.chunk.len(),
+                length
+            )));
+        }
+
+        self.indexed_reader
+            .insert_chunk_record_data(offset, &self.chunk)
+            .wrap_err("Invalid MCAP chunk")
+    }
+}
+
+impl<R: Read + Seek> Iterator for Messages<R> {
+    type Item = Result<RecordedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let message = self.next_message();
+        self.failed = matches!(message, Some(Err(_)));
+
+        message
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_play_timing() {
+        use crate::arrow::FastFormatArrowBuilder;
+        use crate::recording::{Player, Recorder};
+        use arrow::datatypes::{DataType::UInt64, UInt64Type};
+
+        let message = |value| {
+            FastFormatArrowBuilder::new()
+                .push_primitive_singleton::<UInt64Type>("value", value, UInt64, false)
+                .into_arrow()
+                .unwrap()
+        };
+
+        let mut recorder = Recorder::new(std::io::Cursor::new(Vec::new())).unwrap();
+        recorder.record("b", 200_000_000, message(2)).unwrap();
+        recorder.record("a", 0, message(1)).unwrap();
+        let bytes = recorder.finish().unwrap().into_inner();
+
+        for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(Player::from_bytes(&bytes)
+                .unwrap()
+                .with_speed(speed)
+                .is_err());
+        }
+
+        let messages = Player::from_bytes(&bytes)
+            .unwrap()
+            .messages()
+            .unwrap()
+            .collect::<eyre::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(messages[0].topic, "a");
+        assert_eq!(messages[1].timestamp_ns, 200_000_000);
+
+        let player = Player::from_bytes(&bytes)
+            .unwrap()
+            .with_speed(10.0)
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let mut values = Vec::new();
+
+        player
+            .play(|message| {
+                let raw_data = crate::arrow::FastFormatArrowRawData::new(message.array_data)?
+                    .load_primitive::<UInt64Type>("value")?;
+                values.push(raw_data.primitive_singleton::<UInt64Type>("value")?);
+
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(values, [1, 2]);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+
+        assert!(Player::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+
+        // Replaying 200 ms at this speed would take longer than an `Instant` can represent.
+        let mut count = 0;
+        let result = Player::from_bytes(&bytes)
+            .unwrap()
+            .with_speed(f64::MIN_POSITIVE)
+            .unwrap()
+            .play(|_| {
+                count += 1;
+                Ok(())
+            });
+
+        assert!(result.is_err());
+        assert_eq!(count, 1);
+    }
+}
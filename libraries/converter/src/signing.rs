@@ -0,0 +1,120 @@
+//! Ed25519 signatures of fastformat messages, for sensor data crossing untrusted networks.
+//!
+//! [`sign`] wraps a message in an envelope holding the message, serialized as an Arrow IPC
+//! stream, and its signature. The id of the signing key is recorded in the envelope, so that
+//! receivers can pick the sender's public key with [`key_id`] before calling [`verify`]:
+//!
+//! ```
+//! use arrow::datatypes::{DataType::UInt8, UInt8Type};
+//! use ed25519_dalek::SigningKey;
+//! use fastformat_converter::{arrow::FastFormatArrowBuilder, signing};
+//!
+//! let array_data = FastFormatArrowBuilder::new()
+//!     .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+//!     .into_arrow()
+//!     .unwrap();
+//!
+//! let key = SigningKey::from_bytes(&[7; 32]);
+//! let signed = signing::sign(array_data.clone(), &key, "camera-front").unwrap();
+//!
+//! assert_eq!(signing::key_id(&signed).as_deref(), Some("camera-front"));
+//! assert_eq!(signing::verify(signed, &key.verifying_key()).unwrap(), array_data);
+//! ```
+//!
+//! Signed messages can be encrypted in turn, see [`crate::encryption`].
+
+use crate::{
+    envelope,
+    ipc::{decode_ipc, encode_ipc},
+};
+use eyre::{Report, Result};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+
+const ALGORITHM: &str = "ed25519";
+
+/// Envelope field holding the signature.
+const SIGNATURE: &str = "signature";
+
+/// Signs the fastformat message `array_data` with `key`, named `key_id` for receivers.
+pub fn sign(
+    array_data: arrow::array::ArrayData,
+    key: &SigningKey,
+    key_id: &str,
+) -> Result<arrow::array::ArrayData> {
+    let payload = encode_ipc(array_data)?;
+    let signature = key.sign(&payload);
+
+    envelope::seal(
+        payload,
+        SIGNATURE,
+        signature.to_bytes().to_vec(),
+        ALGORITHM,
+        key_id,
+    )
+}
+
+/// Checks the signature of a message signed by [`sign`] against `key`, and returns the message.
+///
+/// # Errors
+///
+/// Returns an error if `array_data` isn't a signed message, or if its signature doesn't match
+/// `key`, e.g. because the message was tampered with.
+pub fn verify(
+    array_data: arrow::array::ArrayData,
+    key: &VerifyingKey,
+) -> Result<arrow::array::ArrayData> {
+    use arrow::datatypes::UInt8Type;
+
+    let raw_data = envelope::open(array_data, SIGNATURE, ALGORITHM)?;
+
+    let payload = raw_data.primitive_array_view::<UInt8Type>("payload")?;
+    let signature = Signature::from_slice(raw_data.primitive_array_view::<UInt8Type>(SIGNATURE)?)
+        .map_err(|_| Report::msg("Invalid ed25519 signature"))?;
+
+    key.verify_strict(payload, &signature)
+        .map_err(|_| Report::msg("The message signature doesn't match the key"))?;
+
+    decode_ipc(payload)
+}
+
+/// Returns the id of the key that signed `array_data`, or `None` if it isn't a signed message.
+pub fn key_id(array_data: &arrow::array::ArrayData) -> Option<String> {
+    envelope::key_id(array_data, SIGNATURE)
+}
+
+mod tests {
+    #[test]
+    fn test_signature_tampering() {
+        use crate::arrow::FastFormatArrowBuilder;
+        use crate::signing;
+        use arrow::datatypes::{DataType::UInt8, UInt8Type};
+        use ed25519_dalek::SigningKey;
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+            .into_arrow()
+            .unwrap();
+
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let other_key = SigningKey::from_bytes(&[8; 32]);
+        let signed = signing::sign(array_data.clone(), &key, "camera-front").unwrap();
+
+        assert!(signing::verify(signed.clone(), &other_key.verifying_key()).is_err());
+        assert!(signing::verify(array_data, &key.verifying_key()).is_err());
+
+        // Flip a byte of the payload.
+        let mut bytes = signed.child_data()[0].buffers()[0].as_slice().to_vec();
+        bytes[100] ^= 1;
+        let tampered = crate::envelope::seal(
+            bytes,
+            "signature",
+            signed.child_data()[1].buffers()[0].as_slice().to_vec(),
+            "ed25519",
+            "camera-front",
+        )
+        .unwrap();
+
+        assert!(signing::verify(tampered, &key.verifying_key()).is_err());
+    }
+}
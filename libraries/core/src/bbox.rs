@@ -0,0 +1,226 @@
+use crate::{validation::Issue, ParseEncodingError};
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Display, str::FromStr};
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Encoding {
+    XYXY,
+    XYWH,
+    /// XYXY with coordinates normalized to `[0, 1]` by the image width and height.
+    XYXYN,
+    /// XYWH with coordinates normalized to `[0, 1]` by the image width and height.
+    XYWHN,
+}
+
+impl Encoding {
+    pub fn from_string(encoding: String) -> Result<Encoding, ParseEncodingError> {
+        encoding.parse()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::XYXY => "XYXY",
+            Self::XYWH => "XYWH",
+            Self::XYXYN => "XYXYN",
+            Self::XYWHN => "XYWHN",
+        }
+    }
+
+    /// Returns whether the coordinates are normalized to `[0, 1]` instead of in pixels.
+    pub fn is_normalized(&self) -> bool {
+        matches!(self, Self::XYXYN | Self::XYWHN)
+    }
+
+    /// Returns the corners `[x1, y1, x2, y2]` of a box given in this encoding.
+    pub fn to_xyxy(&self, coordinates: [f32; 4]) -> [f32; 4] {
+        let [x, y, a, b] = coordinates;
+
+        match self {
+            Self::XYXY | Self::XYXYN => [x, y, a, b],
+            Self::XYWH | Self::XYWHN => [x, y, x + a, y + b],
+        }
+    }
+
+    /// Returns the coordinates in this encoding of a box with corners `[x1, y1, x2, y2]`.
+    pub fn from_xyxy(&self, corners: [f32; 4]) -> [f32; 4] {
+        let [x1, y1, x2, y2] = corners;
+
+        match self {
+            Self::XYXY | Self::XYXYN => [x1, y1, x2, y2],
+            Self::XYWH | Self::XYWHN => [x1, y1, x2 - x1, y2 - y1],
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = ParseEncodingError;
+
+    fn from_str(encoding: &str) -> Result<Self, ParseEncodingError> {
+        match encoding {
+            "XYXY" => Ok(Self::XYXY),
+            "XYWH" => Ok(Self::XYWH),
+            "XYXYN" => Ok(Self::XYXYN),
+            "XYWHN" => Ok(Self::XYWHN),
+            _ => Err(ParseEncodingError::new(encoding)),
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// Returns whether `data`, `confidence` and `label` lengths describe the same number of boxes,
+/// with 4 coordinates per box.
+pub fn is_consistent(data_len: usize, confidence_len: usize, label_len: usize) -> bool {
+    data_len == confidence_len * 4 && confidence_len == label_len
+}
+
+/// Returns the intersection over union of two boxes given by their corners `[x1, y1, x2, y2]`,
+/// or 0 if both boxes are empty.
+pub fn iou(a: [f32; 4], b: [f32; 4]) -> f32 {
+    let area = |[x1, y1, x2, y2]: [f32; 4]| (x2 - x1).max(0.0) * (y2 - y1).max(0.0);
+
+    let intersection = area([
+        a[0].max(b[0]),
+        a[1].max(b[1]),
+        a[2].min(b[2]),
+        a[3].min(b[3]),
+    ]);
+    let union = area(a) + area(b) - intersection;
+
+    if union > 0.0 {
+        intersection / union
+    } else {
+        0.0
+    }
+}
+
+/// Checks the boxes for length consistency, non-finite values, negative sizes and confidences
+/// outside of `[0, 1]`. If `image_size` (width, height) is given, also checks that every box
+/// is inside the image, i.e. inside `[0, 1]` for normalized encodings.
+pub fn validate(
+    encoding: Encoding,
+    data: &[f32],
+    confidence: &[f32],
+    label_len: usize,
+    image_size: Option<(u32, u32)>,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if data.len() != confidence.len() * 4 {
+        issues.push(Issue::LengthMismatch {
+            field: "data",
+            expected: confidence.len() * 4,
+            actual: data.len(),
+        });
+    }
+
+    if label_len != confidence.len() {
+        issues.push(Issue::LengthMismatch {
+            field: "label",
+            expected: confidence.len(),
+            actual: label_len,
+        });
+    }
+
+    for (index, coordinates) in data.chunks_exact(4).enumerate() {
+        if coordinates.iter().any(|value| !value.is_finite()) {
+            issues.push(Issue::NotFinite {
+                field: "data",
+                index,
+            });
+
+            continue;
+        }
+
+        let [x1, y1, x2, y2] = encoding.to_xyxy([
+            coordinates[0],
+            coordinates[1],
+            coordinates[2],
+            coordinates[3],
+        ]);
+
+        if x2 < x1 || y2 < y1 {
+            issues.push(Issue::NegativeSize { index });
+        }
+
+        if let Some((width, height)) = image_size {
+            let (width, height) = match encoding.is_normalized() {
+                true => (1.0, 1.0),
+                false => (width as f32, height as f32),
+            };
+
+            if x1 < 0.0 || y1 < 0.0 || x2 > width || y2 > height {
+                issues.push(Issue::OutOfBounds { index });
+            }
+        }
+    }
+
+    for (index, &confidence) in confidence.iter().enumerate() {
+        if !confidence.is_finite() {
+            issues.push(Issue::NotFinite {
+                field: "confidence",
+                index,
+            });
+        } else if !(0.0..=1.0).contains(&confidence) {
+            issues.push(Issue::ConfidenceOutOfRange { index, confidence });
+        }
+    }
+
+    issues
+}
+
+mod tests {
+    #[test]
+    fn test_validate() {
+        use crate::bbox::{validate, Encoding};
+        use crate::validation::Issue;
+
+        let data = [
+            0.0,
+            0.0,
+            10.0,
+            10.0,
+            5.0,
+            5.0,
+            -1.0,
+            2.0,
+            0.0,
+            f32::NAN,
+            1.0,
+            1.0,
+        ];
+        let confidence = [0.5, 1.5, 0.9];
+
+        assert_eq!(
+            validate(Encoding::XYWH, &data, &confidence, 3, Some((8, 8))),
+            [
+                Issue::OutOfBounds { index: 0 },
+                Issue::NegativeSize { index: 1 },
+                Issue::NotFinite {
+                    field: "data",
+                    index: 2
+                },
+                Issue::ConfidenceOutOfRange {
+                    index: 1,
+                    confidence: 1.5
+                },
+            ]
+        );
+
+        assert_eq!(
+            validate(Encoding::XYXY, &data[..4], &confidence[..1], 2, None),
+            [Issue::LengthMismatch {
+                field: "label",
+                expected: 1,
+                actual: 2
+            }]
+        );
+    }
+}
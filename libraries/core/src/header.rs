@@ -0,0 +1,24 @@
+use alloc::string::String;
+
+/// Metadata that can be attached to any datatype, so that consumers can align messages coming
+/// from different sensors.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Header {
+    /// Acquisition time of the data, in nanoseconds (e.g. since the UNIX epoch).
+    pub timestamp_ns: u64,
+    /// Coordinate frame the data is expressed in, e.g. `"camera_front"`.
+    pub frame_id: String,
+    /// Number of messages the sender published before this one.
+    pub sequence: u64,
+}
+
+impl Header {
+    pub fn new(timestamp_ns: u64, frame_id: &str, sequence: u64) -> Self {
+        Self {
+            timestamp_ns,
+            frame_id: frame_id.into(),
+            sequence,
+        }
+    }
+}
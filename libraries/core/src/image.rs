@@ -0,0 +1,140 @@
+use crate::{validation::Issue, ParseEncodingError};
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Encoding {
+    RGB8,
+    BGR8,
+    GRAY8,
+}
+
+impl Encoding {
+    pub fn from_string(encoding: String) -> Result<Encoding, ParseEncodingError> {
+        encoding.parse()
+    }
+
+    /// Maps a V4L2 FourCC pixel format, as reported by camera drivers, to an `Encoding`.
+    ///
+    /// Packed YUV formats like `YUYV` have no matching encoding and are rejected.
+    pub fn from_fourcc(fourcc: u32) -> Result<Encoding, ParseEncodingError> {
+        match &fourcc.to_le_bytes() {
+            b"RGB3" => Ok(Self::RGB8),
+            b"BGR3" => Ok(Self::BGR8),
+            b"GREY" | b"Y800" | b"Y8  " => Ok(Self::GRAY8),
+            bytes => Err(ParseEncodingError::new(&String::from_utf8_lossy(bytes))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RGB8 => "RGB8",
+            Self::BGR8 => "BGR8",
+            Self::GRAY8 => "GRAY8",
+        }
+    }
+
+    /// Number of channels of a pixel.
+    pub fn channels(&self) -> usize {
+        match self {
+            Self::RGB8 | Self::BGR8 => 3,
+            Self::GRAY8 => 1,
+        }
+    }
+
+    /// Number of values the pixel data of a `width` x `height` image holds, or `None` if it
+    /// doesn't fit in a `usize`.
+    pub fn expected_len(&self, width: u32, height: u32) -> Option<usize> {
+        (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(self.channels())
+    }
+}
+
+/// Checks that an image of the given size and encoding holds `len` values.
+pub fn validate(encoding: Encoding, width: u32, height: u32, len: usize) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if width == 0 || height == 0 {
+        issues.push(Issue::Empty);
+    }
+
+    match encoding.expected_len(width, height) {
+        Some(expected) if expected == len => {}
+        expected => issues.push(Issue::LengthMismatch {
+            field: "data",
+            expected: expected.unwrap_or(usize::MAX),
+            actual: len,
+        }),
+    }
+
+    issues
+}
+
+impl FromStr for Encoding {
+    type Err = ParseEncodingError;
+
+    /// Parses an encoding name, ignoring case. Besides the canonical names, common aliases
+    /// from other ecosystems (e.g. ROS `mono8`) and V4L2 FourCCs (e.g. `GREY`) are accepted.
+    fn from_str(encoding: &str) -> Result<Self, ParseEncodingError> {
+        const ALIASES: [(Encoding, &[&str]); 3] = [
+            (Encoding::RGB8, &["RGB8", "RGB", "RGB24", "RGB3"]),
+            (Encoding::BGR8, &["BGR8", "BGR", "BGR24", "BGR3"]),
+            (
+                Encoding::GRAY8,
+                &["GRAY8", "GRAY", "GREY", "MONO8", "MONO", "Y8", "Y800"],
+            ),
+        ];
+
+        ALIASES
+            .iter()
+            .find(|(_, aliases)| {
+                aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(encoding))
+            })
+            .map(|(encoding, _)| *encoding)
+            .ok_or_else(|| ParseEncodingError::new(encoding))
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_expected_len() {
+        use crate::image::Encoding;
+
+        assert_eq!(Encoding::RGB8.expected_len(3, 3), Some(27));
+        assert_eq!(Encoding::GRAY8.expected_len(1280, 720), Some(1280 * 720));
+        assert_eq!("BGR8".parse::<Encoding>(), Ok(Encoding::BGR8));
+        assert!("YUV420".parse::<Encoding>().is_err());
+    }
+
+    #[test]
+    fn test_aliases() {
+        use crate::image::Encoding;
+
+        assert_eq!("bgr8".parse::<Encoding>(), Ok(Encoding::BGR8));
+        assert_eq!("BGR".parse::<Encoding>(), Ok(Encoding::BGR8));
+        assert_eq!("mono8".parse::<Encoding>(), Ok(Encoding::GRAY8));
+        assert_eq!("rgb24".parse::<Encoding>(), Ok(Encoding::RGB8));
+        assert!("yuyv".parse::<Encoding>().is_err());
+
+        assert_eq!(
+            Encoding::from_fourcc(u32::from_le_bytes(*b"RGB3")),
+            Ok(Encoding::RGB8)
+        );
+        assert_eq!(
+            Encoding::from_fourcc(u32::from_le_bytes(*b"GREY")),
+            Ok(Encoding::GRAY8)
+        );
+        assert!(Encoding::from_fourcc(u32::from_le_bytes(*b"YUYV")).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+//! Encodings and layout validation shared by every fastformat datatype.
+//!
+//! This crate only depends on `core` and `alloc`, so that `no_std` senders (e.g.
+//! microcontrollers) can describe and validate messages the same way as the rest of fastformat.
+//! Conversions to Arrow, ndarray and others live in `fastformat-datatypes`.
+//!
+//! The `Image` and `BBox` structs themselves, and their Arrow layout, still live in
+//! `fastformat-datatypes` and require `std`: moving alloc-only versions here is deferred.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::fmt::Display;
+
+pub mod bbox;
+pub mod header;
+pub mod image;
+pub mod units;
+pub mod validation;
+
+/// Error returned when parsing an unknown encoding name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEncodingError {
+    encoding: String,
+}
+
+impl ParseEncodingError {
+    pub(crate) fn new(encoding: &str) -> Self {
+        Self {
+            encoding: encoding.into(),
+        }
+    }
+}
+
+impl Display for ParseEncodingError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "Invalid String Encoding {}", self.encoding)
+    }
+}
+
+impl core::error::Error for ParseEncodingError {}
@@ -0,0 +1,179 @@
+//! Physical units of numeric fields, and checked conversions between them.
+//!
+//! Datatypes record the unit of their fields in the Arrow schema metadata, so that a consumer
+//! mixing sensors from different vendors can check what it receives instead of assuming it.
+
+use alloc::string::String;
+use core::{fmt::Display, str::FromStr};
+
+/// Unit of a numeric field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unit {
+    Meter,
+    Millimeter,
+    Radian,
+    Degree,
+    MeterPerSecond,
+    Second,
+    Nanosecond,
+    Pixel,
+    Decibel,
+}
+
+/// Quantity a unit measures: only units of the same quantity convert into each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quantity {
+    Length,
+    Angle,
+    Speed,
+    Time,
+    Pixels,
+    Gain,
+}
+
+impl Unit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Meter => "m",
+            Self::Millimeter => "mm",
+            Self::Radian => "rad",
+            Self::Degree => "deg",
+            Self::MeterPerSecond => "m/s",
+            Self::Second => "s",
+            Self::Nanosecond => "ns",
+            Self::Pixel => "px",
+            Self::Decibel => "dB",
+        }
+    }
+
+    /// The quantity of the unit, and the factor converting it to the base unit of that
+    /// quantity, as a scale and a power of ten so that metric prefixes convert exactly.
+    fn quantity(&self) -> (Quantity, f64, i32) {
+        match self {
+            Self::Meter => (Quantity::Length, 1.0, 0),
+            Self::Millimeter => (Quantity::Length, 1.0, -3),
+            Self::Radian => (Quantity::Angle, 1.0, 0),
+            Self::Degree => (Quantity::Angle, core::f64::consts::PI / 180.0, 0),
+            Self::MeterPerSecond => (Quantity::Speed, 1.0, 0),
+            Self::Second => (Quantity::Time, 1.0, 0),
+            Self::Nanosecond => (Quantity::Time, 1.0, -9),
+            Self::Pixel => (Quantity::Pixels, 1.0, 0),
+            Self::Decibel => (Quantity::Gain, 1.0, 0),
+        }
+    }
+
+    /// Converts `value` from this unit to `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the units measure different quantities, e.g. meters and radians.
+    pub fn convert(&self, value: f64, to: Unit) -> Result<f64, UnitError> {
+        let ((from_quantity, from_scale, from_exponent), (to_quantity, to_scale, to_exponent)) =
+            (self.quantity(), to.quantity());
+
+        if from_quantity != to_quantity {
+            return Err(UnitError::Incompatible { from: *self, to });
+        }
+
+        // Powers of ten are exact in `f64`, their inverses aren't: divide by them instead of
+        // multiplying, so that e.g. 1500 mm is exactly 1.5 m.
+        let exponent = from_exponent - to_exponent;
+        let power = (0..exponent.unsigned_abs()).fold(1.0, |power, _| power * 10.0);
+        let value = value * from_scale / to_scale;
+
+        if exponent >= 0 {
+            Ok(value * power)
+        } else {
+            Ok(value / power)
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = UnitError;
+
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        match unit {
+            "m" => Ok(Self::Meter),
+            "mm" => Ok(Self::Millimeter),
+            "rad" => Ok(Self::Radian),
+            "deg" => Ok(Self::Degree),
+            "m/s" => Ok(Self::MeterPerSecond),
+            "s" => Ok(Self::Second),
+            "ns" => Ok(Self::Nanosecond),
+            "px" => Ok(Self::Pixel),
+            "dB" => Ok(Self::Decibel),
+            _ => Err(UnitError::Unknown(unit.into())),
+        }
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown unit or converting between incompatible units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitError {
+    Unknown(String),
+    Incompatible { from: Unit, to: Unit },
+}
+
+impl Display for UnitError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unknown(unit) => write!(fmt, "Unknown unit {}", unit),
+            Self::Incompatible { from, to } => {
+                write!(fmt, "Can't convert {} to {}", from, to)
+            }
+        }
+    }
+}
+
+impl core::error::Error for UnitError {}
+
+pub fn deg_to_rad(degrees: f64) -> f64 {
+    degrees.to_radians()
+}
+
+pub fn rad_to_deg(radians: f64) -> f64 {
+    radians.to_degrees()
+}
+
+pub fn mm_to_m(millimeters: f64) -> f64 {
+    millimeters / 1e3
+}
+
+pub fn m_to_mm(meters: f64) -> f64 {
+    meters * 1e3
+}
+
+mod tests {
+    #[test]
+    fn test_unit_conversions() {
+        use crate::units::{Unit, UnitError};
+
+        assert_eq!(Unit::Millimeter.convert(1500.0, Unit::Meter), Ok(1.5));
+        assert_eq!(Unit::Second.convert(2.0, Unit::Nanosecond), Ok(2e9));
+        assert!(
+            (Unit::Degree.convert(180.0, Unit::Radian).unwrap() - core::f64::consts::PI).abs()
+                < 1e-12
+        );
+
+        assert_eq!(
+            Unit::Meter.convert(1.0, Unit::Radian),
+            Err(UnitError::Incompatible {
+                from: Unit::Meter,
+                to: Unit::Radian
+            })
+        );
+
+        for unit in [Unit::MeterPerSecond, Unit::Pixel, Unit::Decibel] {
+            assert_eq!(unit.as_str().parse(), Ok(unit));
+        }
+        assert!("furlong".parse::<Unit>().is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use core::fmt::Display;
+
+/// Something wrong with the content of a datatype, found by its `validate` method.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    /// The datatype has no content, e.g. an image of width 0.
+    Empty,
+    /// A field doesn't have the number of values the rest of the datatype implies.
+    LengthMismatch {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// A field doesn't hold the type of values its encoding implies.
+    WrongType { field: &'static str },
+    /// The value of a field for the element `index` is NaN or infinite.
+    NotFinite { field: &'static str, index: usize },
+    /// The box `index` has a negative width or height.
+    NegativeSize { index: usize },
+    /// The confidence of the box `index` is outside of `[0, 1]`.
+    ConfidenceOutOfRange { index: usize, confidence: f32 },
+    /// The box `index` is not fully inside the image.
+    OutOfBounds { index: usize },
+}
+
+impl Display for Issue {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Empty => write!(fmt, "Datatype is empty"),
+            Self::LengthMismatch {
+                field,
+                expected,
+                actual,
+            } => write!(
+                fmt,
+                "Field {} has {} values, expected {}",
+                field, actual, expected
+            ),
+            Self::WrongType { field } => {
+                write!(fmt, "Field {} doesn't match its encoding type", field)
+            }
+            Self::NotFinite { field, index } => {
+                write!(fmt, "Field {} is not finite at index {}", field, index)
+            }
+            Self::NegativeSize { index } => write!(fmt, "Box {} has a negative size", index),
+            Self::ConfidenceOutOfRange { index, confidence } => write!(
+                fmt,
+                "Box {} has a confidence of {}, outside of [0, 1]",
+                index, confidence
+            ),
+            Self::OutOfBounds { index } => write!(fmt, "Box {} is outside of the image", index),
+        }
+    }
+}
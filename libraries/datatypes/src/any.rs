@@ -0,0 +1,172 @@
+use crate::{bbox::BBox, image::Image, key_value_map::KeyValueMap, tiled_image::TiledImage};
+
+/// Any fastformat datatype, for nodes that handle messages generically, e.g. bridges and
+/// recorders.
+///
+/// With the `arrow` feature, [`AnyDatatype::from_arrow`] detects the datatype of a message from
+/// the field names of its schema.
+#[derive(Debug)]
+pub enum AnyDatatype<'a> {
+    Image(Image<'a>),
+    /// A batch of images, see `Image::into_arrow_batch`.
+    ImageBatch(Vec<Image<'a>>),
+    BBox(BBox<'a>),
+    TiledImage(TiledImage<'a>),
+    KeyValueMap(KeyValueMap),
+}
+
+impl AnyDatatype<'_> {
+    /// Name of the datatype, e.g. `"Image"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Image(_) => "Image",
+            Self::ImageBatch(_) => "ImageBatch",
+            Self::BBox(_) => "BBox",
+            Self::TiledImage(_) => "TiledImage",
+            Self::KeyValueMap(_) => "KeyValueMap",
+        }
+    }
+}
+
+impl<'a> From<Image<'a>> for AnyDatatype<'a> {
+    fn from(image: Image<'a>) -> Self {
+        Self::Image(image)
+    }
+}
+
+impl<'a> From<Vec<Image<'a>>> for AnyDatatype<'a> {
+    fn from(images: Vec<Image<'a>>) -> Self {
+        Self::ImageBatch(images)
+    }
+}
+
+impl<'a> From<BBox<'a>> for AnyDatatype<'a> {
+    fn from(bbox: BBox<'a>) -> Self {
+        Self::BBox(bbox)
+    }
+}
+
+impl<'a> From<TiledImage<'a>> for AnyDatatype<'a> {
+    fn from(image: TiledImage<'a>) -> Self {
+        Self::TiledImage(image)
+    }
+}
+
+impl From<KeyValueMap> for AnyDatatype<'_> {
+    fn from(map: KeyValueMap) -> Self {
+        Self::KeyValueMap(map)
+    }
+}
+
+#[cfg(feature = "arrow")]
+mod arrow {
+    use super::AnyDatatype;
+    use crate::{bbox::BBox, image::Image, key_value_map::KeyValueMap, tiled_image::TiledImage};
+
+    use eyre::{Report, Result};
+
+    /// Returns whether the union schema of `array_data` has every field of `fields`.
+    fn has_fields(array_data: &arrow::array::ArrayData, fields: &[&str]) -> bool {
+        match array_data.data_type() {
+            arrow::datatypes::DataType::Union(union_fields, _) => fields.iter().all(|field| {
+                union_fields
+                    .iter()
+                    .any(|(_, union_field)| union_field.name() == field)
+            }),
+            _ => false,
+        }
+    }
+
+    impl AnyDatatype<'_> {
+        /// Converts a fastformat message into the datatype its schema describes.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the schema doesn't match any datatype, or if the conversion of
+        /// the detected datatype fails.
+        pub fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+            // Tiled images also have the fields of a batch: check them first.
+            if has_fields(
+                &array_data,
+                &["tile_width", "tile_height", "offsets", "data"],
+            ) {
+                Ok(Self::TiledImage(TiledImage::from_arrow(array_data)?))
+            } else if has_fields(
+                &array_data,
+                &["width", "height", "encoding", "offsets", "data"],
+            ) {
+                Ok(Self::ImageBatch(Image::from_arrow_batch(array_data)?))
+            } else if has_fields(&array_data, &["width", "height", "encoding", "data"]) {
+                Ok(Self::Image(Image::from_arrow(array_data)?))
+            } else if has_fields(&array_data, &["data", "confidence", "label", "encoding"]) {
+                Ok(Self::BBox(BBox::from_arrow(array_data)?))
+            } else if has_fields(&array_data, &["keys", "types"]) {
+                Ok(Self::KeyValueMap(KeyValueMap::from_arrow(array_data)?))
+            } else {
+                Err(Report::msg(format!(
+                    "Unknown fastformat datatype with schema {}",
+                    array_data.data_type()
+                )))
+            }
+        }
+
+        pub fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+            match self {
+                Self::Image(image) => image.into_arrow(),
+                Self::ImageBatch(images) => Image::into_arrow_batch(images),
+                Self::BBox(bbox) => bbox.into_arrow(),
+                Self::TiledImage(image) => image.into_arrow(),
+                Self::KeyValueMap(map) => map.into_arrow(),
+            }
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn test_any_from_arrow() {
+        use crate::{
+            any::AnyDatatype, bbox::BBox, image::Image, key_value_map::KeyValueMap,
+            tiled_image::TiledImage,
+        };
+
+        let image = Image::new_gray8(vec![0; 4], 2, 2, Some("camera.test")).unwrap();
+        let any = AnyDatatype::from_arrow(image.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(any.kind(), "Image");
+
+        let images = vec![
+            Image::new_gray8(vec![0; 4], 2, 2, None).unwrap(),
+            Image::new_gray8(vec![0; 9], 3, 3, None).unwrap(),
+        ];
+        let any = AnyDatatype::from_arrow(Image::into_arrow_batch(images).unwrap()).unwrap();
+
+        assert!(matches!(any, AnyDatatype::ImageBatch(images) if images.len() == 2));
+
+        let image = Image::new_gray8(vec![0; 9], 3, 3, None).unwrap();
+        let tiled = TiledImage::from_image(&image, 2, 2).unwrap();
+        let any = AnyDatatype::from_arrow(tiled.into_arrow().unwrap()).unwrap();
+
+        assert!(matches!(any, AnyDatatype::TiledImage(image) if image.len() == 4));
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+        let any = AnyDatatype::from_arrow(AnyDatatype::from(bbox).into_arrow().unwrap()).unwrap();
+
+        assert_eq!(any.kind(), "BBox");
+
+        let map = KeyValueMap::new().with("status", "ok");
+        let any = AnyDatatype::from_arrow(map.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(any.kind(), "KeyValueMap");
+
+        let not_fastformat = arrow::array::ArrayData::new_empty(&arrow::datatypes::DataType::UInt8);
+
+        assert!(AnyDatatype::from_arrow(not_fastformat).is_err());
+    }
+}
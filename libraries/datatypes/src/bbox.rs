@@ -1,11 +1,19 @@
-use eyre::{ContextCompat, Result};
+use eyre::{ContextCompat, Report, Result};
+use fastformat_converter::{accounting, policy};
 
-use encoding::Encoding;
+use crate::{header::Header, validation::Issue};
+pub use encoding::Encoding;
 
 use std::borrow::Cow;
 
+mod clip;
+mod iou;
+mod nms;
+mod transform;
 mod xywh;
+mod xywhn;
 mod xyxy;
+mod xyxyn;
 
 #[cfg(feature = "arrow")]
 mod arrow;
@@ -15,17 +23,113 @@ mod ndarray;
 
 mod encoding;
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BBox<'a> {
     pub data: Cow<'a, [f32]>,
     pub confidence: Cow<'a, [f32]>,
     pub label: Vec<String>,
+    /// Optional numeric class of every box, alongside `label`.
+    pub class_id: Option<Cow<'a, [u32]>>,
     pub encoding: Encoding,
+
+    pub header: Option<Header>,
 }
 
-impl BBox<'_> {
+impl<'a> BBox<'a> {
+    /// Attaches a numeric class to every box. Consumers that key on integer ids can use it
+    /// instead of comparing string labels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there isn't exactly one class id per box.
+    pub fn with_class_id(mut self, class_id: impl Into<Cow<'a, [u32]>>) -> Result<Self> {
+        let class_id = class_id.into();
+
+        if class_id.len() != self.confidence.len() {
+            return Err(Report::msg("Class id and Confidence doesn't match length"));
+        }
+
+        self.class_id = Some(class_id);
+
+        Ok(self)
+    }
+
+    /// Attaches a `Header` to the boxes, e.g. the one of the image they were detected in.
+    pub fn with_header(mut self, header: Header) -> Self {
+        self.header = Some(header);
+
+        self
+    }
+
+    /// Checks the boxes for length consistency, NaNs, negative sizes and confidences outside of
+    /// `[0, 1]`. If the `(width, height)` of the image the boxes belong to is given, also checks
+    /// that every box is inside the image.
+    ///
+    /// Returns every issue found, or an empty `Vec` if the boxes are valid.
+    pub fn validate(&self, image_size: Option<(u32, u32)>) -> Vec<Issue> {
+        let mut issues = fastformat_core::bbox::validate(
+            self.encoding,
+            &self.data,
+            &self.confidence,
+            self.label.len(),
+            image_size,
+        );
+
+        if let Some(class_id) = &self.class_id {
+            if class_id.len() != self.confidence.len() {
+                issues.push(Issue::LengthMismatch {
+                    field: "class_id",
+                    expected: self.confidence.len(),
+                    actual: class_id.len(),
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// Returns whether the box coordinates or confidences are borrowed, in which case the first
+    /// mutation or encoding conversion will copy them.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.data, Cow::Borrowed(_))
+            || matches!(self.confidence, Cow::Borrowed(_))
+            || matches!(self.class_id, Some(Cow::Borrowed(_)))
+    }
+
+    /// Copies borrowed coordinates, confidences and class ids into owned buffers now, so that later
+    /// mutations and conversions happen in place.
+    pub fn ensure_owned(&mut self) {
+        accounting::record_cow("BBox::ensure_owned", &self.data);
+        accounting::record_cow("BBox::ensure_owned", &self.confidence);
+
+        self.data.to_mut();
+        self.confidence.to_mut();
+
+        if let Some(class_id) = &mut self.class_id {
+            accounting::record_cow("BBox::ensure_owned", class_id);
+
+            class_id.to_mut();
+        }
+    }
+
+    /// Returns the box coordinates and confidences for mutation if they are owned, or an error
+    /// if mutating them would copy.
+    pub fn try_mut(&mut self) -> Result<(&mut [f32], &mut [f32])> {
+        match (&mut self.data, &mut self.confidence) {
+            (Cow::Owned(data), Cow::Owned(confidence)) => Ok((data, confidence)),
+            _ => Err(Report::msg(
+                "BBox data is borrowed, call ensure_owned first",
+            )),
+        }
+    }
+
     pub fn into_xyxy(self) -> Result<Self> {
         match self.encoding {
             Encoding::XYWH => {
+                policy::check_cow("BBox::into_xyxy", &self.data)?;
+                accounting::record_cow("BBox::into_xyxy", &self.data);
+
                 let mut data = self.data;
                 {
                     let data = data.to_mut();
@@ -57,16 +161,24 @@ impl BBox<'_> {
                     data,
                     confidence: self.confidence,
                     label: self.label,
-                    encoding: self.encoding,
+                    class_id: self.class_id,
+                    encoding: Encoding::XYXY,
+                    header: self.header,
                 })
             }
             Encoding::XYXY => Ok(self),
+            Encoding::XYXYN | Encoding::XYWHN => Err(Report::msg(
+                "Can't convert normalized BBox to XYXY, call into_pixels first",
+            )),
         }
     }
 
     pub fn into_xywh(self) -> Result<Self> {
         match self.encoding {
             Encoding::XYXY => {
+                policy::check_cow("BBox::into_xywh", &self.data)?;
+                accounting::record_cow("BBox::into_xywh", &self.data);
+
                 let mut data = self.data;
                 {
                     let data = data.to_mut();
@@ -98,11 +210,149 @@ impl BBox<'_> {
                     data,
                     confidence: self.confidence,
                     label: self.label,
-                    encoding: self.encoding,
+                    class_id: self.class_id,
+                    encoding: Encoding::XYWH,
+                    header: self.header,
                 })
             }
             Encoding::XYWH => Ok(self),
+            Encoding::XYXYN | Encoding::XYWHN => Err(Report::msg(
+                "Can't convert normalized BBox to XYWH, call into_pixels first",
+            )),
+        }
+    }
+
+    /// Converts pixel coordinates to coordinates normalized to `[0, 1]` by the size of the image
+    /// the boxes belong to: XYXY becomes XYXYN and XYWH becomes XYWHN.
+    pub fn into_normalized(self, width: u32, height: u32) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(Report::msg("Can't normalize BBox by an empty image size"));
+        }
+
+        match self.encoding {
+            Encoding::XYXY => {
+                self.into_scaled(1.0 / width as f32, 1.0 / height as f32, Encoding::XYXYN)
+            }
+            Encoding::XYWH => {
+                self.into_scaled(1.0 / width as f32, 1.0 / height as f32, Encoding::XYWHN)
+            }
+            Encoding::XYXYN | Encoding::XYWHN => Ok(self),
+        }
+    }
+
+    /// Converts normalized coordinates back to pixel coordinates for an image of the given size:
+    /// XYXYN becomes XYXY and XYWHN becomes XYWH.
+    pub fn into_pixels(self, width: u32, height: u32) -> Result<Self> {
+        match self.encoding {
+            Encoding::XYXYN => self.into_scaled(width as f32, height as f32, Encoding::XYXY),
+            Encoding::XYWHN => self.into_scaled(width as f32, height as f32, Encoding::XYWH),
+            Encoding::XYXY | Encoding::XYWH => Ok(self),
+        }
+    }
+
+    /// Keeps only the boxes for which `keep` is true, compacting the coordinates, confidences,
+    /// labels and class ids in place.
+    fn retain(self, conversion: &'static str, keep: &[bool]) -> Result<Self> {
+        if !fastformat_core::bbox::is_consistent(
+            self.data.len(),
+            self.confidence.len(),
+            self.label.len(),
+        ) || keep.len() != self.confidence.len()
+            || self
+                .class_id
+                .as_ref()
+                .is_some_and(|class_id| class_id.len() != self.confidence.len())
+        {
+            return Err(Report::msg(
+                "Confidence, Label and Data doesn't match length",
+            ));
+        }
+
+        if keep.iter().all(|&keep| keep) {
+            return Ok(self);
+        }
+
+        policy::check_cow(conversion, &self.data)?;
+        policy::check_cow(conversion, &self.confidence)?;
+
+        accounting::record_cow(conversion, &self.data);
+        accounting::record_cow(conversion, &self.confidence);
+
+        if let Some(class_id) = &self.class_id {
+            policy::check_cow(conversion, class_id)?;
+            accounting::record_cow(conversion, class_id);
         }
+
+        let (mut data, mut confidence, mut label) = (self.data, self.confidence, self.label);
+        {
+            let (data, confidence) = (data.to_mut(), confidence.to_mut());
+
+            let mut kept = 0;
+            for (index, _) in keep.iter().enumerate().filter(|(_, &keep)| keep) {
+                data.copy_within(index * 4..index * 4 + 4, kept * 4);
+                confidence[kept] = confidence[index];
+                kept += 1;
+            }
+
+            data.truncate(kept * 4);
+            confidence.truncate(kept);
+        }
+
+        let mut keep_label = keep.iter();
+        label.retain(|_| keep_label.next().copied().unwrap_or_default());
+
+        let class_id = self.class_id.map(|class_id| {
+            let mut keep = keep.iter();
+
+            let mut class_id = class_id.into_owned();
+            class_id.retain(|_| keep.next().copied().unwrap_or_default());
+
+            Cow::Owned(class_id)
+        });
+
+        Ok(Self {
+            data,
+            confidence,
+            label,
+            class_id,
+            encoding: self.encoding,
+            header: self.header,
+        })
+    }
+
+    /// Returns the corners `[x1, y1, x2, y2]` of every box.
+    fn corners(&self) -> impl Iterator<Item = [f32; 4]> + '_ {
+        self.data.chunks_exact(4).map(|coordinates| {
+            self.encoding.to_xyxy([
+                coordinates[0],
+                coordinates[1],
+                coordinates[2],
+                coordinates[3],
+            ])
+        })
+    }
+
+    /// Multiplies x coordinates and widths by `sx`, y coordinates and heights by `sy`.
+    fn into_scaled(self, sx: f32, sy: f32, encoding: Encoding) -> Result<Self> {
+        policy::check_cow("BBox::into_scaled", &self.data)?;
+        accounting::record_cow("BBox::into_scaled", &self.data);
+
+        let mut data = self.data;
+        for coordinates in data.to_mut().chunks_exact_mut(4) {
+            coordinates[0] *= sx;
+            coordinates[1] *= sy;
+            coordinates[2] *= sx;
+            coordinates[3] *= sy;
+        }
+
+        Ok(Self {
+            data,
+            confidence: self.confidence,
+            label: self.label,
+            class_id: self.class_id,
+            encoding,
+            header: self.header,
+        })
     }
 }
 
@@ -140,4 +390,121 @@ mod tests {
 
         assert_eq!(expected_bbox, final_bbox_data.into_owned());
     }
+
+    #[test]
+    fn test_ensure_owned() {
+        use crate::bbox::{encoding::Encoding, BBox};
+        use std::borrow::Cow;
+
+        let flat_bbox = vec![1.0, 1.0, 2.0, 2.0];
+        let confidence = vec![0.98];
+
+        let mut bbox = BBox {
+            data: Cow::Borrowed(&flat_bbox),
+            confidence: Cow::Borrowed(&confidence),
+            label: vec!["cat".to_string()],
+            class_id: None,
+            encoding: Encoding::XYXY,
+            header: None,
+        };
+
+        assert!(bbox.is_borrowed());
+        assert!(bbox.try_mut().is_err());
+
+        bbox.ensure_owned();
+
+        assert!(!bbox.is_borrowed());
+
+        let (data, confidence) = bbox.try_mut().unwrap();
+        data[0] = 0.0;
+        confidence[0] = 0.5;
+
+        assert_eq!(bbox.data.as_ref(), &[0.0, 1.0, 2.0, 2.0]);
+        assert_eq!(flat_bbox, vec![1.0, 1.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_json_round_trip() {
+        use crate::bbox::BBox;
+
+        let bbox = BBox::new_xywh(
+            vec![1.0, 1.0, 1.0, 1.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+
+        let json = serde_json::to_string(&bbox).unwrap();
+        let final_bbox = serde_json::from_str::<BBox>(&json).unwrap();
+
+        assert_eq!(final_bbox.data, bbox.data);
+        assert_eq!(final_bbox.confidence, bbox.confidence);
+        assert_eq!(final_bbox.label, bbox.label);
+        assert_eq!(final_bbox.encoding, bbox.encoding);
+    }
+
+    #[test]
+    fn test_normalized_round_trip() {
+        use crate::bbox::{encoding::Encoding, BBox};
+
+        let bbox = BBox::new_xywh(
+            vec![64.0, 48.0, 320.0, 240.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+
+        let bbox = bbox.into_normalized(640, 480).unwrap();
+
+        assert_eq!(bbox.encoding, Encoding::XYWHN);
+        assert!(bbox
+            .data
+            .iter()
+            .zip([0.1, 0.1, 0.5, 0.5])
+            .all(|(value, expected)| (value - expected).abs() < 1e-6));
+        assert!(bbox.into_xyxy().is_err());
+
+        let bbox = BBox::new_xywhn(
+            vec![0.1, 0.1, 0.5, 0.5],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap()
+        .into_pixels(640, 480)
+        .unwrap()
+        .into_xyxy()
+        .unwrap();
+
+        assert_eq!(bbox.encoding, Encoding::XYXY);
+        assert_eq!(bbox.data.as_ref(), &[64.0, 48.0, 384.0, 288.0]);
+    }
+
+    #[test]
+    fn test_class_id() {
+        use crate::bbox::BBox;
+
+        let bbox = BBox::new_xyxy(
+            vec![0.0, 0.0, 10.0, 10.0, 1.0, 1.0, 10.0, 10.0],
+            vec![0.9, 0.8],
+            vec!["cat".to_string(), "cat".to_string()],
+        )
+        .unwrap();
+
+        assert!(bbox.with_class_id(vec![15]).is_err());
+
+        let bbox = BBox::new_xyxy(
+            vec![0.0, 0.0, 10.0, 10.0, 1.0, 1.0, 10.0, 10.0],
+            vec![0.9, 0.8],
+            vec!["cat".to_string(), "cat".to_string()],
+        )
+        .unwrap()
+        .with_class_id(vec![15, 16])
+        .unwrap()
+        .nms(0.5)
+        .unwrap();
+
+        assert_eq!(bbox.class_id.as_deref(), Some(&[15][..]));
+        assert!(bbox.validate(None).is_empty());
+    }
 }
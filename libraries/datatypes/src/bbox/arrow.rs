@@ -1,9 +1,97 @@
 use std::borrow::Cow;
 
 use super::{encoding::Encoding, BBox};
-use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+use crate::{header, units};
+use fastformat_converter::{
+    accounting,
+    arrow::{FastFormatArrowBuilder, FastFormatArrowRawData},
+    policy,
+};
+
+use eyre::{Report, Result};
+
+use std::sync::OnceLock;
+
+fn union_fields() -> arrow::datatypes::UnionFields {
+    use arrow::datatypes::DataType::{Float32, UInt32, Utf8};
+
+    static UNION_FIELDS: OnceLock<arrow::datatypes::UnionFields> = OnceLock::new();
+
+    UNION_FIELDS
+        .get_or_init(|| {
+            let schema = FastFormatArrowBuilder::schema(
+                &[
+                    &[
+                        ("data", Float32, false),
+                        ("confidence", Float32, false),
+                        ("label", Utf8, false),
+                        ("class_id", UInt32, false),
+                        ("encoding", Utf8, false),
+                    ][..],
+                    &header::fields(),
+                ]
+                .concat(),
+            );
+
+            units::annotate(schema, &header::units())
+        })
+        .clone()
+}
+
+/// Loads the class ids, if the message has them: messages from older versions don't.
+fn load_class_id(raw_data: FastFormatArrowRawData) -> Result<FastFormatArrowRawData> {
+    if !raw_data.has_field("class_id") {
+        return Ok(raw_data);
+    }
+
+    raw_data.load_primitive::<arrow::datatypes::UInt32Type>("class_id")
+}
+
+/// Checks that received boxes have matching lengths, like the `BBox::new_*` constructors and
+/// `BBox::with_class_id` do, so that malformed messages are rejected here instead of panicking
+/// later.
+fn check_lens(
+    data: &[f32],
+    confidence: &[f32],
+    label: &[String],
+    class_id: Option<&[u32]>,
+) -> Result<()> {
+    if !fastformat_core::bbox::is_consistent(data.len(), confidence.len(), label.len()) {
+        return Err(Report::msg(
+            "Confidence, Label and Data doesn't match length",
+        ));
+    }
 
-use eyre::Result;
+    if class_id.is_some_and(|class_id| class_id.len() != confidence.len()) {
+        return Err(Report::msg("Class id and Confidence doesn't match length"));
+    }
+
+    Ok(())
+}
+
+/// Takes a primitive array out of the raw data, copying it only if its buffer is shared, e.g.
+/// when it was imported through the Arrow C Data Interface.
+fn take_or_copy<T: arrow::datatypes::ArrowPrimitiveType>(
+    raw_data: &mut FastFormatArrowRawData,
+    field: &str,
+) -> Result<Vec<T::Native>> {
+    if let Ok(data) = raw_data.primitive_array::<T>(field) {
+        accounting::record(
+            "BBox::from_raw_data",
+            std::mem::size_of_val(&data[..]),
+            false,
+        );
+
+        return Ok(data);
+    }
+
+    let data = raw_data.primitive_array_view::<T>(field)?;
+
+    policy::check("BBox::from_raw_data", std::mem::size_of_val(data))?;
+    accounting::record("BBox::from_raw_data", std::mem::size_of_val(data), true);
+
+    Ok(data.to_vec())
+}
 
 impl<'a> BBox<'a> {
     pub fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
@@ -15,38 +103,67 @@ impl<'a> BBox<'a> {
             .load_utf("label")?
             .load_utf("encoding")?;
 
-        Ok(raw_data)
+        header::load(load_class_id(raw_data)?)
     }
 
     pub fn from_raw_data(mut raw_data: FastFormatArrowRawData) -> Result<Self> {
-        use arrow::datatypes::Float32Type;
+        use arrow::datatypes::{Float32Type, UInt32Type};
 
-        let data = raw_data.primitive_array::<Float32Type>("data")?;
-        let confidence = raw_data.primitive_array::<Float32Type>("confidence")?;
+        let data = take_or_copy::<Float32Type>(&mut raw_data, "data")?;
+        let confidence = take_or_copy::<Float32Type>(&mut raw_data, "confidence")?;
         let label = raw_data.utf8_array("label")?;
-        let encoding = Encoding::from_string(raw_data.utf8_singleton("encoding")?)?;
+        let class_id = match raw_data.has_field("class_id") {
+            true => Some(take_or_copy::<UInt32Type>(&mut raw_data, "class_id")?),
+            false => None,
+        }
+        .filter(|class_id| !class_id.is_empty() || confidence.is_empty());
+        let encoding = raw_data
+            .utf8_singleton_view("encoding")?
+            .parse::<Encoding>()?;
+        let header = header::read(&raw_data)?;
+
+        check_lens(&data, &confidence, &label, class_id.as_deref())?;
 
         Ok(Self {
             data: Cow::Owned(data),
             confidence: Cow::Owned(confidence),
             label,
+            class_id: class_id.map(Cow::Owned),
             encoding,
+            header,
         })
     }
 
     pub fn view_from_raw_data(raw_data: &'a FastFormatArrowRawData) -> Result<Self> {
-        use arrow::datatypes::Float32Type;
+        use arrow::datatypes::{Float32Type, UInt32Type};
 
         let data = raw_data.primitive_array_view::<Float32Type>("data")?;
         let confidence = raw_data.primitive_array_view::<Float32Type>("confidence")?;
         let label = raw_data.utf8_array("label")?;
-        let encoding = Encoding::from_string(raw_data.utf8_singleton("encoding")?)?;
+        let class_id = match raw_data.has_field("class_id") {
+            true => Some(raw_data.primitive_array_view::<UInt32Type>("class_id")?),
+            false => None,
+        }
+        .filter(|class_id| !class_id.is_empty() || confidence.is_empty());
+        let encoding = raw_data
+            .utf8_singleton_view("encoding")?
+            .parse::<Encoding>()?;
+        let header = header::read(raw_data)?;
+
+        check_lens(data, confidence, &label, class_id)?;
+        accounting::record(
+            "BBox::view_from_raw_data",
+            (data.len() + confidence.len()) * 4,
+            false,
+        );
 
         Ok(Self {
             data: Cow::Borrowed(data),
             confidence: Cow::Borrowed(confidence),
             label,
+            class_id: class_id.map(Cow::Borrowed),
             encoding,
+            header,
         })
     }
 
@@ -56,11 +173,22 @@ impl<'a> BBox<'a> {
 
     pub fn into_arrow(self) -> Result<arrow::array::ArrayData> {
         use arrow::datatypes::{
-            DataType::{Float32, Utf8},
-            Float32Type,
+            DataType::{Float32, UInt32, Utf8},
+            Float32Type, UInt32Type,
         };
 
-        let raw_data = FastFormatArrowBuilder::new()
+        policy::check_cow("BBox::into_arrow", &self.data)?;
+        policy::check_cow("BBox::into_arrow", &self.confidence)?;
+
+        accounting::record_cow("BBox::into_arrow", &self.data);
+        accounting::record_cow("BBox::into_arrow", &self.confidence);
+
+        if let Some(class_id) = &self.class_id {
+            policy::check_cow("BBox::into_arrow", class_id)?;
+            accounting::record_cow("BBox::into_arrow", class_id);
+        }
+
+        let raw_data = FastFormatArrowBuilder::with_schema(union_fields())
             .push_primitive_array::<Float32Type>("data", self.data.into_owned(), Float32, false)
             .push_primitive_array::<Float32Type>(
                 "confidence",
@@ -69,9 +197,16 @@ impl<'a> BBox<'a> {
                 false,
             )
             .push_utf_array("label", self.label, Utf8, false)
-            .push_utf_singleton("encoding", self.encoding.to_string(), Utf8, false);
+            // Boxes without class ids are serialized with an empty field.
+            .push_primitive_array::<UInt32Type>(
+                "class_id",
+                self.class_id.map(Cow::into_owned).unwrap_or_default(),
+                UInt32,
+                false,
+            )
+            .push_utf_singleton("encoding", self.encoding.as_str(), Utf8, false);
 
-        raw_data.into_arrow()
+        header::push(raw_data, self.header.as_ref()).into_arrow()
     }
 }
 
@@ -149,4 +284,91 @@ mod tests {
         assert_eq!(original_buffer_address, bbox_buffer_address);
         assert_ne!(bbox_buffer_address, final_bbox_buffer);
     }
+
+    #[test]
+    #[cfg(feature = "accounting")]
+    fn test_arrow_copy_accounting() {
+        use crate::bbox::BBox;
+        use fastformat_converter::accounting;
+
+        let flat_bbox = vec![1.0, 1.0, 2.0, 2.0];
+        let confidence = vec![0.98];
+        let label = vec!["cat".to_string()];
+
+        let xyxy_bbox = BBox::new_xyxy(flat_bbox, confidence, label).unwrap();
+        let arrow_bbox = xyxy_bbox.into_arrow().unwrap();
+
+        let raw_data = BBox::raw_data(arrow_bbox).unwrap();
+        let xyxy_bbox = BBox::view_from_raw_data(&raw_data).unwrap();
+
+        let view_stats = accounting::stats("BBox::view_from_raw_data");
+        assert!(view_stats.borrowed_bytes >= 20);
+
+        xyxy_bbox.into_xywh().unwrap();
+
+        let copy_stats = accounting::stats("BBox::into_xywh");
+        assert!(copy_stats.copies >= 1);
+        assert!(copy_stats.copied_bytes >= 16);
+    }
+
+    #[test]
+    fn test_arrow_header() {
+        use crate::bbox::BBox;
+        use crate::header::Header;
+
+        let header = Header::new(1_700_000_000_000_000_000, "camera_front", 42);
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap()
+        .with_header(header.clone());
+
+        let bbox = BBox::from_arrow(bbox.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(bbox.header, Some(header));
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+
+        let bbox = BBox::from_arrow(bbox.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(bbox.header, None);
+    }
+
+    #[test]
+    fn test_arrow_class_id() {
+        use crate::bbox::BBox;
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0],
+            vec![0.98, 0.5],
+            vec!["cat".to_string(), "dog".to_string()],
+        )
+        .unwrap()
+        .with_class_id(vec![15, 16])
+        .unwrap();
+
+        let raw_data = BBox::raw_data(bbox.into_arrow().unwrap()).unwrap();
+        let bbox = BBox::view_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(bbox.class_id.as_deref(), Some(&[15, 16][..]));
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+
+        let bbox = BBox::from_arrow(bbox.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(bbox.class_id, None);
+    }
 }
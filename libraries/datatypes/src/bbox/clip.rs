@@ -0,0 +1,87 @@
+use super::BBox;
+use eyre::Result;
+use fastformat_converter::{accounting, policy};
+
+impl BBox<'_> {
+    /// Clamps every box to an image of the given size, and drops the boxes that end up with no
+    /// area (e.g. boxes fully outside of the image).
+    ///
+    /// Normalized boxes are clamped to `[0, 1]`, whatever the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the image the boxes belong to.
+    /// * `height` - The height of the image the boxes belong to.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the clipped boxes if successful, or an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data, confidence and label lengths don't match.
+    pub fn clip(self, width: u32, height: u32) -> Result<Self> {
+        let (width, height) = match self.encoding.is_normalized() {
+            true => (1.0, 1.0),
+            false => (width as f32, height as f32),
+        };
+
+        let clipped = self
+            .corners()
+            .map(|[x1, y1, x2, y2]| {
+                [
+                    x1.clamp(0.0, width),
+                    y1.clamp(0.0, height),
+                    x2.clamp(0.0, width),
+                    y2.clamp(0.0, height),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let keep = clipped
+            .iter()
+            .map(|[x1, y1, x2, y2]| x2 > x1 && y2 > y1)
+            .collect::<Vec<_>>();
+
+        let mut bbox = self.retain("BBox::retain", &keep)?;
+
+        policy::check_cow("BBox::clip", &bbox.data)?;
+        accounting::record_cow("BBox::clip", &bbox.data);
+
+        let encoding = bbox.encoding;
+        for (coordinates, corners) in bbox.data.to_mut().chunks_exact_mut(4).zip(
+            clipped
+                .into_iter()
+                .filter(|[x1, y1, x2, y2]| x2 > x1 && y2 > y1),
+        ) {
+            coordinates.copy_from_slice(&encoding.from_xyxy(corners));
+        }
+
+        Ok(bbox)
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_clip() {
+        use crate::bbox::BBox;
+
+        let bbox = BBox::new_xywh(
+            vec![
+                -10.0, 10.0, 30.0, 30.0, 700.0, 10.0, 20.0, 20.0, 600.0, 460.0, 100.0, 100.0,
+            ],
+            vec![0.9, 0.8, 0.7],
+            vec!["cat".to_string(), "dog".to_string(), "car".to_string()],
+        )
+        .unwrap();
+
+        let bbox = bbox.clip(640, 480).unwrap();
+
+        assert_eq!(
+            bbox.data.as_ref(),
+            &[0.0, 10.0, 20.0, 30.0, 600.0, 460.0, 40.0, 20.0]
+        );
+        assert_eq!(bbox.confidence.as_ref(), &[0.9, 0.7]);
+        assert_eq!(bbox.label, vec!["cat".to_string(), "car".to_string()]);
+    }
+}
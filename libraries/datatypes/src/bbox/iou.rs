@@ -0,0 +1,66 @@
+use super::BBox;
+use eyre::{Report, Result};
+
+impl BBox<'_> {
+    /// Computes the intersection over union of every box of `self` with every box of `other`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the IoUs in row-major order: the IoU of box `i` of `self` with box
+    /// `j` of `other` is at index `i * other.len() + j`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one set of boxes is normalized and the other isn't.
+    pub fn iou(&self, other: &BBox) -> Result<Vec<f32>> {
+        if self.encoding.is_normalized() != other.encoding.is_normalized() {
+            return Err(Report::msg(
+                "Can't compare normalized and pixel BBox, call into_pixels first",
+            ));
+        }
+
+        let other = other.corners().collect::<Vec<_>>();
+
+        Ok(self
+            .corners()
+            .flat_map(|corners| {
+                other
+                    .iter()
+                    .map(move |&other| fastformat_core::bbox::iou(corners, other))
+            })
+            .collect())
+    }
+
+    /// Number of boxes.
+    pub fn len(&self) -> usize {
+        self.data.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_iou() {
+        use crate::bbox::BBox;
+
+        let bbox = BBox::new_xyxy(
+            vec![0.0, 0.0, 10.0, 10.0, 20.0, 20.0, 30.0, 30.0],
+            vec![0.9, 0.8],
+            vec!["cat".to_string(), "dog".to_string()],
+        )
+        .unwrap();
+
+        let other = BBox::new_xywh(
+            vec![5.0, 0.0, 10.0, 10.0],
+            vec![0.7],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(bbox.iou(&other).unwrap(), vec![1.0 / 3.0, 0.0]);
+        assert!(bbox.iou(&other.into_normalized(640, 480).unwrap()).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 use super::{encoding::Encoding, BBox};
 use eyre::{Context, Report, Result};
 
-use fastformat_converter::ndarray::{Ndarray, NdarrayView, NdarrayViewMut};
+use fastformat_converter::ndarray::{into_row_major_vec, Ndarray, NdarrayView, NdarrayViewMut};
 
 pub type NdarrayBBox = (Ndarray, Ndarray, Ndarray, Encoding);
 pub type NdarrayBBoxView<'a> = (NdarrayView<'a>, NdarrayView<'a>, NdarrayView<'a>, Encoding);
@@ -21,9 +21,9 @@ impl BBox<'_> {
                 Ndarray::STRIX1(label),
                 Encoding::XYXY,
             ) => Self::new_xyxy(
-                data.into_raw_vec_and_offset().0,
-                confidence.into_raw_vec_and_offset().0,
-                label.into_raw_vec_and_offset().0,
+                into_row_major_vec("BBox::from_ndarray", data)?,
+                into_row_major_vec("BBox::from_ndarray", confidence)?,
+                into_row_major_vec("BBox::from_ndarray", label)?,
             ),
             (
                 Ndarray::F32IX1(data),
@@ -31,9 +31,29 @@ impl BBox<'_> {
                 Ndarray::STRIX1(label),
                 Encoding::XYWH,
             ) => Self::new_xywh(
-                data.into_raw_vec_and_offset().0,
-                confidence.into_raw_vec_and_offset().0,
-                label.into_raw_vec_and_offset().0,
+                into_row_major_vec("BBox::from_ndarray", data)?,
+                into_row_major_vec("BBox::from_ndarray", confidence)?,
+                into_row_major_vec("BBox::from_ndarray", label)?,
+            ),
+            (
+                Ndarray::F32IX1(data),
+                Ndarray::F32IX1(confidence),
+                Ndarray::STRIX1(label),
+                Encoding::XYXYN,
+            ) => Self::new_xyxyn(
+                into_row_major_vec("BBox::from_ndarray", data)?,
+                into_row_major_vec("BBox::from_ndarray", confidence)?,
+                into_row_major_vec("BBox::from_ndarray", label)?,
+            ),
+            (
+                Ndarray::F32IX1(data),
+                Ndarray::F32IX1(confidence),
+                Ndarray::STRIX1(label),
+                Encoding::XYWHN,
+            ) => Self::new_xywhn(
+                into_row_major_vec("BBox::from_ndarray", data)?,
+                into_row_major_vec("BBox::from_ndarray", confidence)?,
+                into_row_major_vec("BBox::from_ndarray", label)?,
             ),
             _ => Err(Report::msg("Invalid Ndarray type")).context("from_ndarray"),
         }
@@ -95,3 +115,30 @@ impl<'a> BBox<'a> {
         ))
     }
 }
+
+impl BBox<'_> {
+    /// Computes the pairwise intersection over union of the boxes of `self` (rows) and `other`
+    /// (columns), see [`BBox::iou`].
+    pub fn iou_matrix(&self, other: &BBox) -> Result<ndarray::Array2<f32>> {
+        ndarray::Array2::from_shape_vec((self.len(), other.len()), self.iou(other)?)
+            .wrap_err("Failed to reshape IoUs into ndarray")
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_iou_matrix() {
+        use crate::bbox::BBox;
+
+        let bbox = BBox::new_xyxy(
+            vec![0.0, 0.0, 10.0, 10.0, 20.0, 20.0, 30.0, 30.0],
+            vec![0.9, 0.8],
+            vec!["cat".to_string(), "dog".to_string()],
+        )
+        .unwrap();
+
+        let matrix = bbox.iou_matrix(&bbox).unwrap();
+
+        assert_eq!(matrix, ndarray::array![[1.0, 0.0], [0.0, 1.0]]);
+    }
+}
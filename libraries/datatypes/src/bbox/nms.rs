@@ -0,0 +1,94 @@
+use super::BBox;
+use eyre::Result;
+use fastformat_core::bbox::iou;
+
+impl BBox<'_> {
+    /// Non-maximum suppression, ignoring labels: among boxes overlapping each other with an
+    /// intersection over union above `iou_threshold`, only the most confident one is kept.
+    ///
+    /// The remaining boxes keep their order, and are compacted in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data, confidence and label lengths don't match.
+    pub fn nms(self, iou_threshold: f32) -> Result<Self> {
+        let keep = self.nms_keep(iou_threshold, false);
+
+        self.retain("BBox::nms", &keep)
+    }
+
+    /// Non-maximum suppression within each label: boxes with different labels never suppress
+    /// each other. See [`BBox::nms`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data, confidence and label lengths don't match.
+    pub fn nms_per_label(self, iou_threshold: f32) -> Result<Self> {
+        let keep = self.nms_keep(iou_threshold, true);
+
+        self.retain("BBox::nms_per_label", &keep)
+    }
+
+    /// Greedy suppression, from the most to the least confident box. Returns whether each box
+    /// is kept.
+    fn nms_keep(&self, iou_threshold: f32, per_label: bool) -> Vec<bool> {
+        let corners = self.corners().collect::<Vec<_>>();
+        let len = corners.len().min(self.confidence.len());
+
+        let mut order = (0..len).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| self.confidence[b].total_cmp(&self.confidence[a]));
+
+        let mut keep = vec![true; self.confidence.len()];
+        for (position, &index) in order.iter().enumerate() {
+            if !keep[index] {
+                continue;
+            }
+
+            for &other in &order[position + 1..] {
+                if per_label && self.label.get(index) != self.label.get(other) {
+                    continue;
+                }
+
+                if keep[other] && iou(corners[index], corners[other]) > iou_threshold {
+                    keep[other] = false;
+                }
+            }
+        }
+
+        keep
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_nms() {
+        use crate::bbox::BBox;
+
+        let bbox = || {
+            BBox::new_xyxy(
+                vec![
+                    0.0, 0.0, 10.0, 10.0, 1.0, 1.0, 11.0, 11.0, 0.0, 0.0, 10.0, 9.0, 20.0, 20.0,
+                    30.0, 30.0,
+                ],
+                vec![0.6, 0.9, 0.8, 0.5],
+                vec![
+                    "cat".to_string(),
+                    "cat".to_string(),
+                    "dog".to_string(),
+                    "cat".to_string(),
+                ],
+            )
+            .unwrap()
+        };
+
+        let agnostic = bbox().nms(0.5).unwrap();
+
+        assert_eq!(agnostic.confidence.as_ref(), &[0.9, 0.5]);
+        assert_eq!(agnostic.label, vec!["cat".to_string(), "cat".to_string()]);
+
+        let per_label = bbox().nms_per_label(0.5).unwrap();
+
+        assert_eq!(per_label.confidence.as_ref(), &[0.9, 0.8, 0.5]);
+        assert_eq!(per_label.data[4..8], [0.0, 0.0, 10.0, 9.0]);
+    }
+}
@@ -0,0 +1,92 @@
+use super::{encoding::Encoding, BBox};
+use eyre::{Report, Result};
+use fastformat_converter::{accounting, policy};
+
+impl BBox<'_> {
+    /// Maps boxes computed on an image of size `from` (width, height) to an image of size `to`,
+    /// e.g. from the resized frame a model ran on back to the camera resolution.
+    ///
+    /// Normalized boxes don't depend on the image size and are returned as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` is an empty size.
+    pub fn scale(self, from: (u32, u32), to: (u32, u32)) -> Result<Self> {
+        if from.0 == 0 || from.1 == 0 {
+            return Err(Report::msg("Can't scale BBox from an empty image size"));
+        }
+
+        if self.encoding.is_normalized() {
+            return Ok(self);
+        }
+
+        let encoding = self.encoding;
+
+        self.into_scaled(
+            to.0 as f32 / from.0 as f32,
+            to.1 as f32 / from.1 as f32,
+            encoding,
+        )
+    }
+
+    /// Moves every box by `dx` horizontally and `dy` vertically, in the units of the encoding
+    /// (pixels, or image sizes for normalized encodings). Combined with [`BBox::scale`], this
+    /// maps boxes computed on a letterboxed frame back to the original frame.
+    pub fn translate(self, dx: f32, dy: f32) -> Result<Self> {
+        policy::check_cow("BBox::translate", &self.data)?;
+        accounting::record_cow("BBox::translate", &self.data);
+
+        let mut data = self.data;
+        for coordinates in data.to_mut().chunks_exact_mut(4) {
+            coordinates[0] += dx;
+            coordinates[1] += dy;
+
+            match self.encoding {
+                Encoding::XYXY | Encoding::XYXYN => {
+                    coordinates[2] += dx;
+                    coordinates[3] += dy;
+                }
+                Encoding::XYWH | Encoding::XYWHN => {}
+            }
+        }
+
+        Ok(Self {
+            data,
+            confidence: self.confidence,
+            label: self.label,
+            class_id: self.class_id,
+            encoding: self.encoding,
+            header: self.header,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_letterbox_to_original() {
+        use crate::bbox::BBox;
+
+        // A 1280x720 frame letterboxed into 640x640: scaled by 0.5 then padded by 140 rows.
+        let bbox = BBox::new_xyxy(
+            vec![100.0, 190.0, 200.0, 240.0],
+            vec![0.9],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+
+        let bbox = bbox
+            .translate(0.0, -140.0)
+            .unwrap()
+            .scale((640, 360), (1280, 720))
+            .unwrap();
+
+        assert_eq!(bbox.data.as_ref(), &[200.0, 100.0, 400.0, 200.0]);
+
+        let bbox = BBox::new_xywh(vec![1.0, 1.0, 2.0, 2.0], vec![0.9], vec!["cat".to_string()])
+            .unwrap()
+            .translate(1.0, 2.0)
+            .unwrap();
+
+        assert_eq!(bbox.data.as_ref(), &[2.0, 3.0, 2.0, 2.0]);
+    }
+}
@@ -5,10 +5,7 @@ use eyre::{Report, Result};
 
 impl BBox<'_> {
     pub fn new_xywh(data: Vec<f32>, confidence: Vec<f32>, label: Vec<String>) -> Result<Self> {
-        if confidence.len() != label.len()
-            || confidence.len() * 4 != data.len()
-            || label.len() * 4 != data.len()
-        {
+        if !fastformat_core::bbox::is_consistent(data.len(), confidence.len(), label.len()) {
             return Err(Report::msg(
                 "Confidence, Label and Data doesn't match length",
             ));
@@ -18,7 +15,9 @@ impl BBox<'_> {
             data: Cow::from(data),
             confidence: Cow::from(confidence),
             label,
+            class_id: None,
             encoding: Encoding::XYWH,
+            header: None,
         })
     }
 }
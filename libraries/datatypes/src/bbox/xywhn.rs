@@ -0,0 +1,37 @@
+use std::borrow::Cow;
+
+use super::{encoding::Encoding, BBox};
+use eyre::{Report, Result};
+
+impl BBox<'_> {
+    /// Creates boxes whose coordinates are normalized to `[0, 1]` by the image width and height.
+    pub fn new_xywhn(data: Vec<f32>, confidence: Vec<f32>, label: Vec<String>) -> Result<Self> {
+        if !fastformat_core::bbox::is_consistent(data.len(), confidence.len(), label.len()) {
+            return Err(Report::msg(
+                "Confidence, Label and Data doesn't match length",
+            ));
+        }
+
+        Ok(BBox {
+            data: Cow::from(data),
+            confidence: Cow::from(confidence),
+            label,
+            class_id: None,
+            encoding: Encoding::XYWHN,
+            header: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_xywhn_creation() {
+        use crate::bbox::BBox;
+
+        let flat_bbox = vec![0.1, 0.1, 0.1, 0.1];
+        let confidence = vec![0.98];
+        let label = vec!["cat".to_string()];
+
+        BBox::new_xywhn(flat_bbox, confidence, label).unwrap();
+    }
+}
@@ -0,0 +1,296 @@
+use crate::header::Header;
+
+use std::collections::{BTreeMap, HashMap};
+
+/// What a sequence number tells about the messages of a topic, see [`DropDetector::observe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// First message of the topic.
+    First,
+    /// The message directly follows the previous one.
+    InOrder,
+    /// `missed` messages were skipped before this one: dropped, or late.
+    Gap { missed: u64 },
+    /// The message arrived after a message sent later, it was counted as dropped before.
+    Reordered,
+    /// The message was already received.
+    Duplicate,
+    /// The sequence jumped back, e.g. to 0 after the sender restarted: the topic starts over
+    /// from this message.
+    Restart,
+}
+
+/// Message counts of a topic, see [`DropDetector::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DropStats {
+    pub received: u64,
+    /// Messages skipped by the sequence numbers and not received since.
+    pub dropped: u64,
+    pub reordered: u64,
+    pub duplicates: u64,
+    pub restarts: u64,
+}
+
+impl DropStats {
+    /// Fraction of the sent messages that were dropped, between 0 and 1. Duplicates aren't
+    /// counted as sent.
+    pub fn drop_rate(&self) -> f64 {
+        match self.received - self.duplicates + self.dropped {
+            0 => 0.0,
+            sent => self.dropped as f64 / sent as f64,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Topic {
+    next_sequence: u64,
+    /// Ranges of skipped sequence numbers that may still show up, start to end excluded.
+    missing: BTreeMap<u64, u64>,
+    stats: DropStats,
+}
+
+impl Topic {
+    /// Removes `sequence` from the missing ranges, returning whether it was missing.
+    fn take_missing(&mut self, sequence: u64) -> bool {
+        let Some((&start, &end)) = self.missing.range(..=sequence).next_back() else {
+            return false;
+        };
+        if sequence >= end {
+            return false;
+        }
+
+        self.missing.remove(&start);
+        if start < sequence {
+            self.missing.insert(start, sequence);
+        }
+        if sequence + 1 < end {
+            self.missing.insert(sequence + 1, end);
+        }
+
+        true
+    }
+
+    /// Forgets the missing messages too old to be reordered, so that memory stays bounded.
+    fn forget_missing(&mut self, reorder_window: u64) {
+        let oldest = self.next_sequence.saturating_sub(reorder_window);
+
+        while let Some((&start, &end)) = self.missing.first_key_value() {
+            if end > oldest {
+                if start < oldest {
+                    self.missing.remove(&start);
+                    self.missing.insert(oldest, end);
+                }
+
+                break;
+            }
+
+            self.missing.remove(&start);
+        }
+    }
+}
+
+/// Default of [`DropDetector::with_reorder_window`].
+pub const DEFAULT_REORDER_WINDOW: u64 = 1024;
+
+/// Detects dropped and reordered messages per topic, from the `sequence` of their header.
+///
+/// Senders number their messages `0, 1, 2, ...`; any jump in the sequence numbers received on a
+/// topic is counted as dropped messages, until they show up late. A message that goes back to 0,
+/// or further back than the reorder window, is a restart of the sender rather than a late one.
+///
+/// # Example
+///
+/// ```
+/// use fastformat_datatypes::drops::{DropDetector, SequenceEvent};
+///
+/// let mut detector = DropDetector::new();
+///
+/// assert_eq!(detector.observe("camera", 0), SequenceEvent::First);
+/// assert_eq!(detector.observe("camera", 3), SequenceEvent::Gap { missed: 2 });
+/// assert_eq!(detector.observe("camera", 1), SequenceEvent::Reordered);
+///
+/// assert_eq!(detector.observe("camera", 1), SequenceEvent::Duplicate);
+/// assert_eq!(detector.observe("camera", 0), SequenceEvent::Restart);
+///
+/// assert_eq!(detector.stats("camera").unwrap().dropped, 1);
+/// ```
+#[derive(Debug)]
+pub struct DropDetector {
+    topics: HashMap<String, Topic>,
+    reorder_window: u64,
+}
+
+impl Default for DropDetector {
+    fn default() -> Self {
+        Self {
+            topics: HashMap::new(),
+            reorder_window: DEFAULT_REORDER_WINDOW,
+        }
+    }
+}
+
+impl DropDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many messages back a late message may be, [`DEFAULT_REORDER_WINDOW`] by default.
+    /// Going further back is a restart of the sender.
+    pub fn with_reorder_window(mut self, reorder_window: u64) -> Self {
+        self.reorder_window = reorder_window;
+
+        self
+    }
+
+    /// Records the message of `topic` numbered `sequence`.
+    pub fn observe(&mut self, topic: &str, sequence: u64) -> SequenceEvent {
+        let Some(state) = self.topics.get_mut(topic) else {
+            self.topics.insert(
+                topic.to_string(),
+                Topic {
+                    next_sequence: sequence.wrapping_add(1),
+                    missing: BTreeMap::new(),
+                    stats: DropStats {
+                        received: 1,
+                        ..Default::default()
+                    },
+                },
+            );
+
+            return SequenceEvent::First;
+        };
+
+        state.stats.received += 1;
+
+        if sequence < state.next_sequence {
+            if state.take_missing(sequence) {
+                state.stats.dropped -= 1;
+                state.stats.reordered += 1;
+
+                return SequenceEvent::Reordered;
+            }
+
+            if sequence != 0 && state.next_sequence - sequence <= self.reorder_window {
+                state.stats.duplicates += 1;
+
+                return SequenceEvent::Duplicate;
+            }
+
+            // The messages still missing from before the restart stay dropped.
+            state.next_sequence = sequence.wrapping_add(1);
+            state.missing.clear();
+            state.stats.restarts += 1;
+
+            return SequenceEvent::Restart;
+        }
+
+        let missed = sequence - state.next_sequence;
+        if missed > 0 {
+            state.missing.insert(state.next_sequence, sequence);
+        }
+
+        state.next_sequence = sequence.wrapping_add(1);
+        state.stats.dropped += missed;
+        state.forget_missing(self.reorder_window);
+
+        match missed {
+            0 => SequenceEvent::InOrder,
+            missed => SequenceEvent::Gap { missed },
+        }
+    }
+
+    /// Records a message of `topic` from its header.
+    pub fn observe_header(&mut self, topic: &str, header: &Header) -> SequenceEvent {
+        self.observe(topic, header.sequence)
+    }
+
+    /// Returns the message counts of `topic`, or `None` if no message was observed on it.
+    pub fn stats(&self, topic: &str) -> Option<DropStats> {
+        self.topics.get(topic).map(|topic| topic.stats)
+    }
+
+    /// Returns the message counts of every observed topic.
+    pub fn topics(&self) -> impl Iterator<Item = (&str, DropStats)> {
+        self.topics
+            .iter()
+            .map(|(name, topic)| (name.as_str(), topic.stats))
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_drop_detector() {
+        use crate::drops::{DropDetector, DropStats, SequenceEvent};
+        use crate::header::Header;
+
+        let mut detector = DropDetector::new();
+
+        for sequence in [5, 6, 8, 9, 7, 12] {
+            detector.observe_header("lidar", &Header::new(0, "lidar", sequence));
+        }
+        assert_eq!(detector.observe("imu", 0), SequenceEvent::First);
+        assert_eq!(detector.observe("imu", 1), SequenceEvent::InOrder);
+
+        let stats = detector.stats("lidar").unwrap();
+        assert_eq!(
+            stats,
+            DropStats {
+                received: 6,
+                dropped: 2,
+                reordered: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(stats.drop_rate(), 0.25);
+
+        assert_eq!(detector.stats("imu").unwrap().dropped, 0);
+        assert_eq!(detector.stats("gps"), None);
+        assert_eq!(detector.topics().count(), 2);
+    }
+
+    #[test]
+    fn test_drop_detector_restart_and_duplicate() {
+        use crate::drops::{DropDetector, DropStats, SequenceEvent};
+
+        let mut detector = DropDetector::new().with_reorder_window(100);
+
+        for sequence in 1000..1010 {
+            detector.observe("camera", sequence);
+        }
+        assert_eq!(
+            detector.observe("camera", 1012),
+            SequenceEvent::Gap { missed: 2 }
+        );
+        assert_eq!(detector.observe("camera", 1005), SequenceEvent::Duplicate);
+        assert_eq!(detector.observe("camera", 1010), SequenceEvent::Reordered);
+        assert_eq!(
+            detector.observe("camera", 1010),
+            SequenceEvent::Duplicate,
+            "a late message is only counted once"
+        );
+
+        // The sender restarts: the next messages are in order again, and the message still
+        // missing from before the restart stays dropped.
+        assert_eq!(detector.observe("camera", 0), SequenceEvent::Restart);
+        for sequence in 1..5 {
+            assert_eq!(detector.observe("camera", sequence), SequenceEvent::InOrder);
+        }
+        assert_eq!(
+            detector.observe("camera", 500),
+            SequenceEvent::Gap { missed: 495 }
+        );
+        assert_eq!(detector.observe("camera", 100), SequenceEvent::Restart);
+
+        assert_eq!(
+            detector.stats("camera").unwrap(),
+            DropStats {
+                received: 21,
+                dropped: 496,
+                reordered: 1,
+                duplicates: 2,
+                restarts: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! The [`Header`] shared by every datatype, serialized with the same Arrow fields by all of them.
+
+pub use fastformat_core::header::Header;
+
+use crate::{
+    any::AnyDatatype, bbox::BBox, image::Image, key_value_map::KeyValueMap, tiled_image::TiledImage,
+};
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "arrow")]
+pub use arrow::{fields, from_arrow, load, push, read, units};
+
+/// A datatype that can carry a [`Header`], so that generic code can read and stamp headers
+/// without knowing the payload type.
+pub trait HasHeader {
+    fn header(&self) -> Option<&Header>;
+
+    fn header_mut(&mut self) -> &mut Option<Header>;
+}
+
+impl HasHeader for Image<'_> {
+    fn header(&self) -> Option<&Header> {
+        self.header.as_ref()
+    }
+
+    fn header_mut(&mut self) -> &mut Option<Header> {
+        &mut self.header
+    }
+}
+
+impl HasHeader for BBox<'_> {
+    fn header(&self) -> Option<&Header> {
+        self.header.as_ref()
+    }
+
+    fn header_mut(&mut self) -> &mut Option<Header> {
+        &mut self.header
+    }
+}
+
+impl HasHeader for TiledImage<'_> {
+    fn header(&self) -> Option<&Header> {
+        self.header.as_ref()
+    }
+
+    fn header_mut(&mut self) -> &mut Option<Header> {
+        &mut self.header
+    }
+}
+
+impl HasHeader for KeyValueMap {
+    fn header(&self) -> Option<&Header> {
+        self.header.as_ref()
+    }
+
+    fn header_mut(&mut self) -> &mut Option<Header> {
+        &mut self.header
+    }
+}
+
+impl AnyDatatype<'_> {
+    /// Returns the header of the message. Batches of images have no header of their own.
+    pub fn header(&self) -> Option<&Header> {
+        match self {
+            Self::Image(image) => image.header(),
+            Self::ImageBatch(_) => None,
+            Self::BBox(bbox) => bbox.header(),
+            Self::TiledImage(image) => image.header(),
+            Self::KeyValueMap(map) => map.header(),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use super::Header;
+use crate::units::Unit;
+use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+
+use eyre::Result;
+
+/// Arrow fields of the header, to add to the schema of a datatype at the position it pushes
+/// them with [`push`].
+pub fn fields() -> [(&'static str, arrow::datatypes::DataType, bool); 3] {
+    use arrow::datatypes::DataType::{UInt64, Utf8};
+
+    [
+        ("timestamp_ns", UInt64, false),
+        ("frame_id", Utf8, false),
+        ("sequence", UInt64, false),
+    ]
+}
+
+/// Units of the header fields, to annotate the schema of a datatype with.
+pub fn units() -> [(&'static str, Unit); 1] {
+    [("timestamp_ns", Unit::Nanosecond)]
+}
+
+/// Pushes the header fields of a message. A missing header is serialized as empty fields.
+pub fn push(builder: FastFormatArrowBuilder, header: Option<&Header>) -> FastFormatArrowBuilder {
+    use arrow::datatypes::{
+        DataType::{UInt64, Utf8},
+        UInt64Type,
+    };
+
+    builder
+        .push_primitive_array::<UInt64Type>(
+            "timestamp_ns",
+            header.iter().map(|header| header.timestamp_ns).collect(),
+            UInt64,
+            false,
+        )
+        .push_utf_array(
+            "frame_id",
+            header
+                .iter()
+                .map(|header| header.frame_id.clone())
+                .collect(),
+            Utf8,
+            false,
+        )
+        .push_primitive_array::<UInt64Type>(
+            "sequence",
+            header.iter().map(|header| header.sequence).collect(),
+            UInt64,
+            false,
+        )
+}
+
+/// Loads the header fields, if the message has them: messages from older versions don't.
+pub fn load(raw_data: FastFormatArrowRawData) -> Result<FastFormatArrowRawData> {
+    use arrow::datatypes::UInt64Type;
+
+    if !raw_data.has_field("timestamp_ns") {
+        return Ok(raw_data);
+    }
+
+    crate::units::check(&raw_data, "timestamp_ns", Unit::Nanosecond)?;
+
+    raw_data
+        .load_primitive::<UInt64Type>("timestamp_ns")?
+        .load_utf("frame_id")?
+        .load_primitive::<UInt64Type>("sequence")
+}
+
+/// Reads the header loaded by [`load`].
+pub fn read(raw_data: &FastFormatArrowRawData) -> Result<Option<Header>> {
+    use arrow::datatypes::UInt64Type;
+
+    if !raw_data.has_field("timestamp_ns") {
+        return Ok(None);
+    }
+
+    let timestamp_ns = raw_data.primitive_array_view::<UInt64Type>("timestamp_ns")?;
+    let frame_id = raw_data.utf8_array("frame_id")?;
+    let sequence = raw_data.primitive_array_view::<UInt64Type>("sequence")?;
+
+    match (
+        timestamp_ns.first(),
+        frame_id.into_iter().next(),
+        sequence.first(),
+    ) {
+        (Some(&timestamp_ns), Some(frame_id), Some(&sequence)) => Ok(Some(Header {
+            timestamp_ns,
+            frame_id,
+            sequence,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Reads the header of any fastformat message, without knowing its datatype.
+pub fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Option<Header>> {
+    read(&load(FastFormatArrowRawData::new(array_data)?)?)
+}
+
+mod tests {
+    #[test]
+    fn test_header_from_arrow() {
+        use crate::{
+            bbox::BBox,
+            header::{from_arrow, Header},
+            image::Image,
+        };
+
+        let header = Header::new(1_700_000_000_000_000_000, "camera_front", 42);
+
+        let image = Image::new_gray8(vec![0; 4], 2, 2, None)
+            .unwrap()
+            .with_header(header.clone());
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap()
+        .with_header(header.clone());
+
+        assert_eq!(
+            from_arrow(image.into_arrow().unwrap()).unwrap(),
+            Some(header.clone())
+        );
+        assert_eq!(
+            from_arrow(bbox.into_arrow().unwrap()).unwrap(),
+            Some(header)
+        );
+
+        let image = Image::new_gray8(vec![0; 4], 2, 2, None).unwrap();
+
+        assert_eq!(from_arrow(image.into_arrow().unwrap()).unwrap(), None);
+    }
+}
@@ -1,12 +1,26 @@
 use eyre::{Report, Result};
 
-use data::ImageData;
+use crate::{header::Header, validation::Issue};
+pub use data::ImageData;
 pub use encoding::Encoding;
+pub use half::f16;
 
 mod bgr8;
 mod gray8;
 mod rgb8;
 
+mod builder;
+pub use builder::ImageBuilder;
+
+mod capture;
+pub use capture::{CaptureMetadata, GpsTag};
+
+mod rows;
+pub use rows::RowBand;
+
+mod view;
+pub use view::{ImageView, Primitive};
+
 #[cfg(feature = "arrow")]
 mod arrow;
 
@@ -16,7 +30,40 @@ mod ndarray;
 #[cfg(feature = "ndarray")]
 pub use ndarray::{NdarrayImage, NdarrayImageView, NdarrayImageViewMut};
 
-mod data;
+#[cfg(feature = "wgpu")]
+mod wgpu;
+
+#[cfg(feature = "memmap2")]
+mod mmap;
+
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "ros2")]
+mod ros2;
+
+#[cfg(feature = "opencv")]
+mod opencv;
+
+#[cfg(feature = "kornia-image")]
+mod kornia;
+
+#[cfg(feature = "ort")]
+mod ort;
+
+#[cfg(feature = "candle-core")]
+mod candle;
+
+#[cfg(feature = "tch")]
+mod tch;
+
+#[cfg(feature = "tract-data")]
+mod tract;
+
+#[cfg(feature = "wgpu")]
+pub use wgpu::texture_format;
+
+pub(crate) mod data;
 pub mod encoding;
 
 #[derive(Debug)]
@@ -29,6 +76,68 @@ pub struct Image<'a> {
     pub encoding: Encoding,
 
     pub name: Option<String>,
+
+    pub header: Option<Header>,
+
+    pub capture: Option<CaptureMetadata>,
+}
+
+impl<'a> Image<'a> {
+    /// Attaches a `Header` to the image, e.g. to timestamp it.
+    pub fn with_header(mut self, header: Header) -> Self {
+        self.header = Some(header);
+
+        self
+    }
+
+    /// Attaches the acquisition parameters of the image, e.g. its exposure time and gain.
+    pub fn with_capture(mut self, capture: CaptureMetadata) -> Self {
+        self.capture = Some(capture);
+
+        self
+    }
+
+    /// Checks that the pixel data matches the width, height and encoding of the image.
+    ///
+    /// Returns every issue found, or an empty `Vec` if the image is valid.
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = fastformat_core::image::validate(
+            self.encoding,
+            self.width,
+            self.height,
+            self.data.len(),
+        );
+
+        // Every encoding holds `u8` values.
+        if self.data.as_u8().is_err() {
+            issues.push(Issue::WrongType { field: "data" });
+        }
+
+        issues
+    }
+
+    /// Returns whether the pixel data is borrowed (e.g. from an Arrow buffer), in which case the
+    /// first mutation or encoding conversion will copy it.
+    pub fn is_borrowed(&self) -> bool {
+        self.data.is_borrowed()
+    }
+
+    /// Copies borrowed pixel data into an owned buffer now, e.g. outside of a hot loop, so that
+    /// later mutations and conversions happen in place.
+    pub fn ensure_owned(&mut self) {
+        self.data.ensure_owned();
+    }
+
+    /// Returns the pixel data for mutation if it is owned, or an error if mutating it would copy.
+    pub fn try_mut(&mut self) -> Result<&mut ImageData<'a>> {
+        if self.data.is_borrowed() {
+            return Err(Report::msg(
+                "Image data is borrowed, call ensure_owned first",
+            ));
+        }
+
+        Ok(&mut self.data)
+    }
 }
 
 impl Image<'_> {
@@ -37,15 +146,16 @@ impl Image<'_> {
             Encoding::BGR8 => {
                 let mut data = self.data.into_u8()?;
 
-                for i in (0..data.len()).step_by(3) {
-                    data.swap(i, i + 2);
-                }
+                swap_red_blue(&mut data);
+
                 Ok(Image {
                     data: ImageData::from_vec_u8(data),
                     width: self.width,
                     height: self.height,
                     encoding: Encoding::RGB8,
                     name: self.name.clone(),
+                    header: self.header.clone(),
+                    capture: self.capture.clone(),
                 })
             }
             Encoding::RGB8 => Ok(self),
@@ -58,9 +168,7 @@ impl Image<'_> {
             Encoding::RGB8 => {
                 let mut data = self.data.into_u8()?;
 
-                for i in (0..data.len()).step_by(3) {
-                    data.swap(i, i + 2);
-                }
+                swap_red_blue(&mut data);
 
                 Ok(Image {
                     data: ImageData::from_vec_u8(data),
@@ -68,6 +176,8 @@ impl Image<'_> {
                     height: self.height,
                     encoding: Encoding::BGR8,
                     name: self.name.clone(),
+                    header: self.header.clone(),
+                    capture: self.capture.clone(),
                 })
             }
             Encoding::BGR8 => Ok(self),
@@ -76,6 +186,36 @@ impl Image<'_> {
     }
 }
 
+/// Frames with at least this many bytes are converted in parallel when the `rayon` feature is
+/// enabled. Below it, the thread pool overhead outweighs the gain.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 1 << 20;
+
+/// Number of pixels handed to a single rayon task.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_PIXELS: usize = 16 * 1024;
+
+/// Swaps the first and third channel of every 3-byte pixel, turning RGB8 into BGR8 and back.
+fn swap_red_blue(data: &mut [u8]) {
+    #[cfg(feature = "rayon")]
+    if data.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+
+        data.par_chunks_mut(PARALLEL_CHUNK_PIXELS * 3)
+            .for_each(|chunk| {
+                for pixel in chunk.chunks_exact_mut(3) {
+                    pixel.swap(0, 2);
+                }
+            });
+
+        return;
+    }
+
+    for pixel in data.chunks_exact_mut(3) {
+        pixel.swap(0, 2);
+    }
+}
+
 mod tests {
     #[test]
     fn test_rgb8_into_bgr8() {
@@ -112,4 +252,90 @@ mod tests {
 
         assert_eq!(&expected_image, final_image_data);
     }
+
+    #[test]
+    fn test_large_rgb8_into_bgr8() {
+        use crate::image::Image;
+
+        let (width, height) = (1280, 720);
+        let flat_image = (0..width * height * 3)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<u8>>();
+        let expected_image = flat_image
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[2], pixel[1], pixel[0]])
+            .collect::<Vec<u8>>();
+
+        let image = Image::new_rgb8(flat_image, width, height, None).unwrap();
+
+        let final_image = image.into_bgr8().unwrap();
+        let final_image_data = final_image.data.as_u8().unwrap();
+
+        assert_eq!(&expected_image, final_image_data);
+    }
+
+    #[test]
+    fn test_ensure_owned() {
+        use crate::image::{data::ImageData, Encoding, Image};
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let original_buffer_address = flat_image.as_ptr() as *const u64;
+
+        let mut image = Image {
+            data: ImageData::from_slice_u8(&flat_image),
+            width: 3,
+            height: 3,
+            encoding: Encoding::RGB8,
+            name: None,
+            header: None,
+            capture: None,
+        };
+
+        assert!(image.is_borrowed());
+        assert!(image.try_mut().is_err());
+
+        image.ensure_owned();
+        let owned_buffer_address = image.data.as_ptr();
+
+        assert!(!image.is_borrowed());
+        assert_ne!(original_buffer_address, owned_buffer_address);
+
+        image.try_mut().unwrap().try_mut_u8().unwrap()[0] = 42;
+        let final_image = image.into_bgr8().unwrap();
+
+        assert_eq!(owned_buffer_address, final_image.data.as_ptr());
+        assert_eq!(final_image.data.as_u8().unwrap()[2], 42);
+    }
+
+    #[test]
+    fn test_validate() {
+        use crate::image::{data::ImageData, Encoding, Image};
+        use crate::validation::Issue;
+
+        let image = Image::new_gray8(vec![0; 4], 2, 2, None).unwrap();
+
+        assert!(image.validate().is_empty());
+
+        let image = Image {
+            data: ImageData::from_vec_f32(vec![0.0; 3]),
+            width: 2,
+            height: 2,
+            encoding: Encoding::GRAY8,
+            name: None,
+            header: None,
+            capture: None,
+        };
+
+        assert_eq!(
+            image.validate(),
+            [
+                Issue::LengthMismatch {
+                    field: "data",
+                    expected: 4,
+                    actual: 3
+                },
+                Issue::WrongType { field: "data" },
+            ]
+        );
+    }
 }
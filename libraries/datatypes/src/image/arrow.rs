@@ -1,6 +1,395 @@
-use super::{data::ImageData, encoding::Encoding, Image};
-use eyre::Result;
-use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+use super::{
+    capture::{CaptureMetadata, GpsTag},
+    data::ImageData,
+    encoding::Encoding,
+    Image,
+};
+use crate::{
+    header,
+    units::{self, Unit},
+};
+use eyre::{OptionExt, Report, Result};
+use fastformat_converter::{
+    accounting,
+    arrow::{FastFormatArrowBuilder, FastFormatArrowRawData},
+    policy,
+};
+
+use std::sync::OnceLock;
+
+/// Arrow layout of an `Image`, built once and shared by every `into_arrow` call.
+fn union_fields() -> arrow::datatypes::UnionFields {
+    use arrow::datatypes::DataType::{Float32, Float64, UInt32, UInt64, UInt8, Utf8};
+
+    static UNION_FIELDS: OnceLock<arrow::datatypes::UnionFields> = OnceLock::new();
+
+    UNION_FIELDS
+        .get_or_init(|| {
+            let schema = FastFormatArrowBuilder::schema(
+                &[
+                    &[
+                        ("width", UInt32, false),
+                        ("height", UInt32, false),
+                        ("encoding", Utf8, false),
+                        ("name", Utf8, false),
+                        ("data", UInt8, false),
+                    ][..],
+                    &header::fields(),
+                    &[
+                        ("exposure_time_ns", UInt64, false),
+                        ("gain_db", Float32, false),
+                        ("iso", UInt32, false),
+                        ("lens", Utf8, false),
+                        ("gps", Float64, false),
+                    ],
+                ]
+                .concat(),
+            );
+
+            units::annotate(
+                schema,
+                &[
+                    &[
+                        ("width", Unit::Pixel),
+                        ("height", Unit::Pixel),
+                        ("exposure_time_ns", Unit::Nanosecond),
+                        ("gain_db", Unit::Decibel),
+                    ][..],
+                    &header::units(),
+                ]
+                .concat(),
+            )
+        })
+        .clone()
+}
+
+/// Loads the capture metadata fields, if the message has them: messages from older versions
+/// don't.
+fn load_capture(raw_data: FastFormatArrowRawData) -> Result<FastFormatArrowRawData> {
+    use arrow::datatypes::{Float32Type, Float64Type, UInt32Type, UInt64Type};
+
+    if !raw_data.has_field("exposure_time_ns") {
+        return Ok(raw_data);
+    }
+
+    units::check(&raw_data, "exposure_time_ns", Unit::Nanosecond)?;
+    units::check(&raw_data, "gain_db", Unit::Decibel)?;
+
+    raw_data
+        .load_primitive::<UInt64Type>("exposure_time_ns")?
+        .load_primitive::<Float32Type>("gain_db")?
+        .load_primitive::<UInt32Type>("iso")?
+        .load_utf("lens")?
+        .load_primitive::<Float64Type>("gps")
+}
+
+/// Reads the capture metadata loaded by `load_capture`. Every field is serialized as an array
+/// with zero or one value (three for the GPS tag), and metadata without any field as `None`.
+fn capture(raw_data: &FastFormatArrowRawData) -> Result<Option<CaptureMetadata>> {
+    use arrow::datatypes::{Float32Type, Float64Type, UInt32Type, UInt64Type};
+
+    if !raw_data.has_field("exposure_time_ns") {
+        return Ok(None);
+    }
+
+    let capture = CaptureMetadata {
+        exposure_time_ns: raw_data
+            .primitive_array_view::<UInt64Type>("exposure_time_ns")?
+            .first()
+            .copied(),
+        gain_db: raw_data
+            .primitive_array_view::<Float32Type>("gain_db")?
+            .first()
+            .copied(),
+        iso: raw_data
+            .primitive_array_view::<UInt32Type>("iso")?
+            .first()
+            .copied(),
+        lens: raw_data.utf8_array("lens")?.into_iter().next(),
+        gps: match raw_data.primitive_array_view::<Float64Type>("gps")? {
+            &[latitude_deg, longitude_deg, altitude_m] => Some(GpsTag {
+                latitude_deg,
+                longitude_deg,
+                altitude_m,
+            }),
+            _ => None,
+        },
+    };
+
+    Ok(Some(capture).filter(|capture| !capture.is_empty()))
+}
+
+/// Checks that received pixel data matches the image size, like the `Image::new_*`
+/// constructors do, so that malformed messages are rejected here instead of panicking later.
+fn check_len(encoding: Encoding, width: u32, height: u32, len: usize) -> Result<()> {
+    if encoding.expected_len(width, height) != Some(len) {
+        return Err(Report::msg(format!(
+            "Invalid pixel data length: {} values for a {}x{} {} image.",
+            len, width, height, encoding
+        )));
+    }
+
+    Ok(())
+}
+
+/// Arrow layout of a batch of `Image`s, see [`Image::into_arrow_batch`].
+fn batch_union_fields() -> arrow::datatypes::UnionFields {
+    use arrow::datatypes::DataType::{Float32, Float64, UInt32, UInt64, UInt8, Utf8};
+
+    static UNION_FIELDS: OnceLock<arrow::datatypes::UnionFields> = OnceLock::new();
+
+    UNION_FIELDS
+        .get_or_init(|| {
+            let schema = FastFormatArrowBuilder::schema(
+                &[
+                    &[
+                        ("width", UInt32, false),
+                        ("height", UInt32, false),
+                        ("encoding", Utf8, false),
+                        ("name", Utf8, false),
+                        ("offsets", UInt64, false),
+                        ("data", UInt8, false),
+                        ("has_header", UInt8, false),
+                    ][..],
+                    &header::fields(),
+                    &[
+                        ("capture_fields", UInt8, false),
+                        ("exposure_time_ns", UInt64, false),
+                        ("gain_db", Float32, false),
+                        ("iso", UInt32, false),
+                        ("lens", Utf8, false),
+                        ("gps", Float64, false),
+                    ],
+                ]
+                .concat(),
+            );
+
+            units::annotate(
+                schema,
+                &[
+                    &[
+                        ("width", Unit::Pixel),
+                        ("height", Unit::Pixel),
+                        ("exposure_time_ns", Unit::Nanosecond),
+                        ("gain_db", Unit::Decibel),
+                    ][..],
+                    &header::units(),
+                ]
+                .concat(),
+            )
+        })
+        .clone()
+}
+
+/// Bits of the `"capture_fields"` of a batch, telling which capture fields an image has.
+const EXPOSURE_TIME: u8 = 1;
+const GAIN: u8 = 1 << 1;
+const ISO: u8 = 1 << 2;
+const LENS: u8 = 1 << 3;
+const GPS: u8 = 1 << 4;
+
+/// Headers and capture metadata of the images of a batch. The fields of a header or capture
+/// metadata only have values for the images that have them, like a single image has zero or one
+/// value, and `"has_header"` and `"capture_fields"` tell which images these are.
+#[derive(Default)]
+struct BatchMetadata {
+    has_header: Vec<u8>,
+    timestamps_ns: Vec<u64>,
+    frame_ids: Vec<String>,
+    sequences: Vec<u64>,
+    capture_fields: Vec<u8>,
+    exposure_times_ns: Vec<u64>,
+    gains_db: Vec<f32>,
+    isos: Vec<u32>,
+    lenses: Vec<String>,
+    gps: Vec<f64>,
+}
+
+impl BatchMetadata {
+    fn push(&mut self, header: Option<header::Header>, capture: Option<CaptureMetadata>) {
+        self.has_header.push(header.is_some() as u8);
+        if let Some(header) = header {
+            self.timestamps_ns.push(header.timestamp_ns);
+            self.frame_ids.push(header.frame_id);
+            self.sequences.push(header.sequence);
+        }
+
+        let capture = capture.unwrap_or_default();
+        let mut fields = 0;
+        if let Some(exposure_time_ns) = capture.exposure_time_ns {
+            fields |= EXPOSURE_TIME;
+            self.exposure_times_ns.push(exposure_time_ns);
+        }
+        if let Some(gain_db) = capture.gain_db {
+            fields |= GAIN;
+            self.gains_db.push(gain_db);
+        }
+        if let Some(iso) = capture.iso {
+            fields |= ISO;
+            self.isos.push(iso);
+        }
+        if let Some(lens) = capture.lens {
+            fields |= LENS;
+            self.lenses.push(lens);
+        }
+        if let Some(gps) = capture.gps {
+            fields |= GPS;
+            self.gps
+                .extend([gps.latitude_deg, gps.longitude_deg, gps.altitude_m]);
+        }
+        self.capture_fields.push(fields);
+    }
+
+    fn push_into(self, builder: FastFormatArrowBuilder) -> FastFormatArrowBuilder {
+        use arrow::datatypes::{
+            DataType::{Float32, Float64, UInt32, UInt64, UInt8, Utf8},
+            Float32Type, Float64Type, UInt32Type, UInt64Type, UInt8Type,
+        };
+
+        builder
+            .push_primitive_array::<UInt8Type>("has_header", self.has_header, UInt8, false)
+            .push_primitive_array::<UInt64Type>("timestamp_ns", self.timestamps_ns, UInt64, false)
+            .push_utf_array("frame_id", self.frame_ids, Utf8, false)
+            .push_primitive_array::<UInt64Type>("sequence", self.sequences, UInt64, false)
+            .push_primitive_array::<UInt8Type>("capture_fields", self.capture_fields, UInt8, false)
+            .push_primitive_array::<UInt64Type>(
+                "exposure_time_ns",
+                self.exposure_times_ns,
+                UInt64,
+                false,
+            )
+            .push_primitive_array::<Float32Type>("gain_db", self.gains_db, Float32, false)
+            .push_primitive_array::<UInt32Type>("iso", self.isos, UInt32, false)
+            .push_utf_array("lens", self.lenses, Utf8, false)
+            .push_primitive_array::<Float64Type>("gps", self.gps, Float64, false)
+    }
+}
+
+/// Loads the header and capture fields of a batch, if it has them: batches from older versions
+/// don't.
+fn load_batch_metadata(raw_data: FastFormatArrowRawData) -> Result<FastFormatArrowRawData> {
+    use arrow::datatypes::UInt8Type;
+
+    if !raw_data.has_field("has_header") {
+        return Ok(raw_data);
+    }
+
+    let raw_data = header::load(raw_data.load_primitive::<UInt8Type>("has_header")?)?;
+
+    load_capture(raw_data.load_primitive::<UInt8Type>("capture_fields")?)
+}
+
+/// Reads the headers and capture metadata of the `len` images of a batch.
+#[allow(clippy::type_complexity)]
+fn batch_metadata(
+    raw_data: &FastFormatArrowRawData,
+    len: usize,
+) -> Result<Vec<(Option<header::Header>, Option<CaptureMetadata>)>> {
+    use arrow::datatypes::{Float32Type, Float64Type, UInt32Type, UInt64Type, UInt8Type};
+
+    if !raw_data.has_field("has_header") {
+        return Ok(vec![(None, None); len]);
+    }
+
+    let has_header = raw_data.primitive_array_view::<UInt8Type>("has_header")?;
+    let capture_fields = raw_data.primitive_array_view::<UInt8Type>("capture_fields")?;
+    if has_header.len() != len || capture_fields.len() != len {
+        return Err(Report::msg(
+            "Image batch fields don't have the same number of images.",
+        ));
+    }
+
+    let mut timestamps_ns = raw_data
+        .primitive_array_view::<UInt64Type>("timestamp_ns")?
+        .iter();
+    let mut frame_ids = raw_data.utf8_array("frame_id")?.into_iter();
+    let mut sequences = raw_data
+        .primitive_array_view::<UInt64Type>("sequence")?
+        .iter();
+    let mut exposure_times_ns = raw_data
+        .primitive_array_view::<UInt64Type>("exposure_time_ns")?
+        .iter();
+    let mut gains_db = raw_data
+        .primitive_array_view::<Float32Type>("gain_db")?
+        .iter();
+    let mut isos = raw_data.primitive_array_view::<UInt32Type>("iso")?.iter();
+    let mut lenses = raw_data.utf8_array("lens")?.into_iter();
+    let mut gps = raw_data
+        .primitive_array_view::<Float64Type>("gps")?
+        .chunks(3);
+
+    let missing = |field: &str| Report::msg(format!("Image batch is missing {} values.", field));
+
+    let metadata = has_header
+        .iter()
+        .zip(capture_fields)
+        .map(|(&has_header, &fields)| {
+            let header = match has_header {
+                0 => None,
+                _ => Some(header::Header {
+                    timestamp_ns: *timestamps_ns
+                        .next()
+                        .ok_or_else(|| missing("timestamp_ns"))?,
+                    frame_id: frame_ids.next().ok_or_else(|| missing("frame_id"))?,
+                    sequence: *sequences.next().ok_or_else(|| missing("sequence"))?,
+                }),
+            };
+
+            let has = |bit: u8| fields & bit != 0;
+            let capture = CaptureMetadata {
+                exposure_time_ns: match has(EXPOSURE_TIME) {
+                    true => Some(
+                        *exposure_times_ns
+                            .next()
+                            .ok_or_else(|| missing("exposure_time_ns"))?,
+                    ),
+                    false => None,
+                },
+                gain_db: match has(GAIN) {
+                    true => Some(*gains_db.next().ok_or_else(|| missing("gain_db"))?),
+                    false => None,
+                },
+                iso: match has(ISO) {
+                    true => Some(*isos.next().ok_or_else(|| missing("iso"))?),
+                    false => None,
+                },
+                lens: match has(LENS) {
+                    true => Some(lenses.next().ok_or_else(|| missing("lens"))?),
+                    false => None,
+                },
+                gps: match has(GPS) {
+                    true => match gps.next().ok_or_else(|| missing("gps"))? {
+                        &[latitude_deg, longitude_deg, altitude_m] => Some(GpsTag {
+                            latitude_deg,
+                            longitude_deg,
+                            altitude_m,
+                        }),
+                        _ => return Err(missing("gps")),
+                    },
+                    false => None,
+                },
+            };
+
+            Ok((header, Some(capture).filter(|capture| !capture.is_empty())))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if timestamps_ns.next().is_some()
+        || frame_ids.next().is_some()
+        || sequences.next().is_some()
+        || exposure_times_ns.next().is_some()
+        || gains_db.next().is_some()
+        || isos.next().is_some()
+        || lenses.next().is_some()
+        || gps.next().is_some()
+    {
+        return Err(Report::msg(
+            "Image batch has more header or capture values than images.",
+        ));
+    }
+
+    Ok(metadata)
+}
 
 impl<'a> Image<'a> {
     /// Extracts raw data from an Arrow `ArrayData` and converts it to `FastFormatArrowRawData`.
@@ -29,8 +418,12 @@ impl<'a> Image<'a> {
             .load_primitive::<UInt32Type>("height")?
             .load_utf("encoding")?
             .load_utf("name")?;
+        let raw_data = header::load(raw_data)?;
+        let raw_data = load_capture(raw_data)?;
 
-        let encoding = Encoding::from_string(raw_data.utf8_singleton("encoding")?)?;
+        let encoding = raw_data
+            .utf8_singleton_view("encoding")?
+            .parse::<Encoding>()?;
         let raw_data = match encoding {
             Encoding::RGB8 => raw_data.load_primitive::<UInt8Type>("data")?,
             Encoding::BGR8 => raw_data.load_primitive::<UInt8Type>("data")?,
@@ -40,10 +433,30 @@ impl<'a> Image<'a> {
         Ok(raw_data)
     }
 
+    /// Extracts raw data like [`Image::raw_data`], with the pixel buffer aligned to 64 bytes.
+    ///
+    /// The pixel buffer is copied once into aligned memory if the incoming buffer is misaligned,
+    /// so that the view returned by `view_from_raw_data` can be handed to SIMD or GPU upload code
+    /// without unaligned penalties. Use `view_from_raw_data` on the result: a realigned buffer
+    /// can't be taken as an owned `Vec`.
+    ///
+    /// # Arguments
+    ///
+    /// * `array_data` - The Arrow `ArrayData` containing the image metadata and pixel data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FastFormatArrowRawData` if successful, or an error otherwise.
+    pub fn aligned_raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        Self::raw_data(array_data)?.ensure_aligned("data")
+    }
+
     /// Constructs an `Image` object from `FastFormatArrowRawData`.
     ///
     /// This function parses the width, height, encoding, and name of the image from the
-    /// provided raw data and loads the pixel data based on the encoding type.
+    /// provided raw data and loads the pixel data based on the encoding type. The pixel data
+    /// stays in the Arrow buffer it was received in, so no copy happens here even if the buffer
+    /// is shared; it is only copied on the first mutation if the buffer can't be reused.
     ///
     /// # Arguments
     ///
@@ -57,25 +470,34 @@ impl<'a> Image<'a> {
     ///
     /// Returns an error if the raw data is missing fields or contains invalid values.
     pub fn from_raw_data(mut raw_data: FastFormatArrowRawData) -> Result<Self> {
-        use arrow::datatypes::{UInt32Type, UInt8Type};
+        use arrow::datatypes::UInt32Type;
 
         let width = raw_data.primitive_singleton::<UInt32Type>("width")?;
         let height = raw_data.primitive_singleton::<UInt32Type>("height")?;
-        let encoding = Encoding::from_string(raw_data.utf8_singleton("encoding")?)?;
+        let encoding = raw_data
+            .utf8_singleton_view("encoding")?
+            .parse::<Encoding>()?;
         let name = Some(raw_data.utf8_singleton("name")?).filter(|s| !s.is_empty());
+        let header = header::read(&raw_data)?;
+        let capture = capture(&raw_data)?;
 
         let data = match encoding {
-            Encoding::RGB8 => raw_data.primitive_array::<UInt8Type>("data")?,
-            Encoding::BGR8 => raw_data.primitive_array::<UInt8Type>("data")?,
-            Encoding::GRAY8 => raw_data.primitive_array::<UInt8Type>("data")?,
+            Encoding::RGB8 => raw_data.primitive_buffer("data")?,
+            Encoding::BGR8 => raw_data.primitive_buffer("data")?,
+            Encoding::GRAY8 => raw_data.primitive_buffer("data")?,
         };
 
+        check_len(encoding, width, height, data.len())?;
+        accounting::record("Image::from_raw_data", data.len(), false);
+
         Ok(Self {
-            data: ImageData::from_vec_u8(data),
+            data: ImageData::from_arrow_buffer(data),
             width,
             height,
             encoding,
             name,
+            header,
+            capture,
         })
     }
 
@@ -100,8 +522,12 @@ impl<'a> Image<'a> {
 
         let width = raw_data.primitive_singleton::<UInt32Type>("width")?;
         let height = raw_data.primitive_singleton::<UInt32Type>("height")?;
-        let encoding = Encoding::from_string(raw_data.utf8_singleton("encoding")?)?;
+        let encoding = raw_data
+            .utf8_singleton_view("encoding")?
+            .parse::<Encoding>()?;
         let name = Some(raw_data.utf8_singleton("name")?).filter(|s| !s.is_empty());
+        let header = header::read(raw_data)?;
+        let capture = capture(raw_data)?;
 
         let data = match encoding {
             Encoding::RGB8 => raw_data.primitive_array_view::<UInt8Type>("data")?,
@@ -109,12 +535,17 @@ impl<'a> Image<'a> {
             Encoding::GRAY8 => raw_data.primitive_array_view::<UInt8Type>("data")?,
         };
 
+        check_len(encoding, width, height, data.len())?;
+        accounting::record("Image::view_from_raw_data", data.len(), false);
+
         Ok(Self {
             data: ImageData::from_slice_u8(data),
             width,
             height,
             encoding,
             name,
+            header,
+            capture,
         })
     }
 
@@ -144,17 +575,19 @@ impl<'a> Image<'a> {
     /// A `Result` containing the serialized `ArrayData` if successful, or an error otherwise.
     pub fn into_arrow(self) -> Result<arrow::array::ArrayData> {
         use arrow::datatypes::{
-            DataType::{UInt32, UInt8, Utf8},
-            UInt32Type, UInt8Type,
+            DataType::{Float32, Float64, UInt32, UInt64, UInt8, Utf8},
+            Float32Type, Float64Type, UInt32Type, UInt64Type, UInt8Type,
         };
 
-        let raw_data = FastFormatArrowBuilder::new()
+        let capture = self.capture.unwrap_or_default();
+
+        let raw_data = FastFormatArrowBuilder::with_schema(union_fields())
             .push_primitive_singleton::<UInt32Type>("width", self.width, UInt32, false)
             .push_primitive_singleton::<UInt32Type>("height", self.height, UInt32, false)
-            .push_utf_singleton("encoding", self.encoding.to_string(), Utf8, false)
+            .push_utf_singleton("encoding", self.encoding.as_str(), Utf8, false)
             .push_utf_singleton(
                 "name",
-                self.name.map_or_else(|| "".to_string(), |s| s),
+                self.name.as_deref().unwrap_or_default(),
                 Utf8,
                 false,
             );
@@ -180,7 +613,234 @@ impl<'a> Image<'a> {
             ),
         };
 
-        raw_data.into_arrow()
+        header::push(raw_data, self.header.as_ref())
+            .push_primitive_array::<UInt64Type>(
+                "exposure_time_ns",
+                capture.exposure_time_ns.into_iter().collect(),
+                UInt64,
+                false,
+            )
+            .push_primitive_array::<Float32Type>(
+                "gain_db",
+                capture.gain_db.into_iter().collect(),
+                Float32,
+                false,
+            )
+            .push_primitive_array::<UInt32Type>(
+                "iso",
+                capture.iso.into_iter().collect(),
+                UInt32,
+                false,
+            )
+            .push_utf_array("lens", capture.lens.into_iter().collect(), Utf8, false)
+            .push_primitive_array::<Float64Type>(
+                "gps",
+                capture
+                    .gps
+                    .iter()
+                    .flat_map(|gps| [gps.latitude_deg, gps.longitude_deg, gps.altitude_m])
+                    .collect(),
+                Float64,
+                false,
+            )
+            .into_arrow()
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Serializes a batch of images into a single Arrow `ArrayData`.
+    ///
+    /// Sending many small images (thumbnails, patches, ...) one message at a time is dominated by
+    /// the per-message overhead. A batch carries one schema for all frames: width, height,
+    /// encoding and name become arrays with one entry per image, and the pixel data of all images
+    /// is concatenated into a single buffer, split by an `offsets` array of `N + 1` entries.
+    ///
+    /// Headers and capture metadata are kept per image: their fields only hold values for the
+    /// images that have them, and `has_header` and a `capture_fields` bitmask, with one entry per
+    /// image, tell which images these are.
+    ///
+    /// # Arguments
+    ///
+    /// * `images` - The images to serialize, in order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the serialized `ArrayData` if successful, or an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel data of an image doesn't match its encoding.
+    pub fn into_arrow_batch(images: Vec<Image>) -> Result<arrow::array::ArrayData> {
+        use arrow::datatypes::{
+            DataType::{UInt32, UInt64, UInt8, Utf8},
+            UInt32Type, UInt64Type, UInt8Type,
+        };
+
+        let total_len = images.iter().map(|image| image.data.len()).sum::<usize>();
+        policy::check("Image::into_arrow_batch", total_len)?;
+
+        let mut widths = Vec::with_capacity(images.len());
+        let mut heights = Vec::with_capacity(images.len());
+        let mut encodings = Vec::with_capacity(images.len());
+        let mut names = Vec::with_capacity(images.len());
+        let mut offsets = Vec::with_capacity(images.len() + 1);
+        let mut data = Vec::with_capacity(total_len);
+        let mut metadata = BatchMetadata::default();
+
+        offsets.push(0);
+
+        for image in images {
+            let pixels = match image.encoding {
+                Encoding::RGB8 | Encoding::BGR8 | Encoding::GRAY8 => image.data.as_u8()?,
+            };
+
+            data.extend_from_slice(pixels);
+            offsets.push(data.len() as u64);
+
+            widths.push(image.width);
+            heights.push(image.height);
+            encodings.push(image.encoding.as_str().to_string());
+            names.push(image.name.unwrap_or_default());
+            metadata.push(image.header, image.capture);
+        }
+
+        accounting::record("Image::into_arrow_batch", data.len(), true);
+
+        let builder = FastFormatArrowBuilder::with_schema(batch_union_fields())
+            .push_primitive_array::<UInt32Type>("width", widths, UInt32, false)
+            .push_primitive_array::<UInt32Type>("height", heights, UInt32, false)
+            .push_utf_array("encoding", encodings, Utf8, false)
+            .push_utf_array("name", names, Utf8, false)
+            .push_primitive_array::<UInt64Type>("offsets", offsets, UInt64, false)
+            .push_primitive_array::<UInt8Type>("data", data, UInt8, false);
+
+        metadata.push_into(builder).into_arrow()
+    }
+
+    /// Extracts raw data from an Arrow `ArrayData` produced by [`Image::into_arrow_batch`].
+    ///
+    /// # Arguments
+    ///
+    /// * `array_data` - The Arrow `ArrayData` containing the batch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FastFormatArrowRawData` if successful, or an error otherwise.
+    pub fn batch_raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        use arrow::datatypes::{UInt32Type, UInt64Type, UInt8Type};
+
+        let raw_data = FastFormatArrowRawData::new(array_data)?
+            .load_primitive::<UInt32Type>("width")?
+            .load_primitive::<UInt32Type>("height")?
+            .load_utf("encoding")?
+            .load_utf("name")?
+            .load_primitive::<UInt64Type>("offsets")?
+            .load_primitive::<UInt8Type>("data")?;
+
+        load_batch_metadata(raw_data)
+    }
+
+    /// Creates read-only views of every `Image` of a batch, without copying the pixel data.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_data` - A reference to the raw data returned by [`Image::batch_raw_data`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the images in batch order if successful, or an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fields of the batch don't have matching lengths, if the offsets
+    /// don't fit the pixel data, or if the header and capture fields don't match their flags.
+    pub fn view_batch_from_raw_data(raw_data: &'a FastFormatArrowRawData) -> Result<Vec<Self>> {
+        use arrow::datatypes::{UInt32Type, UInt64Type, UInt8Type};
+
+        let widths = raw_data.primitive_array_view::<UInt32Type>("width")?;
+        let heights = raw_data.primitive_array_view::<UInt32Type>("height")?;
+        let encodings = raw_data.utf8_array("encoding")?;
+        let names = raw_data.utf8_array("name")?;
+        let offsets = raw_data.primitive_array_view::<UInt64Type>("offsets")?;
+        let data = raw_data.primitive_array_view::<UInt8Type>("data")?;
+
+        let len = widths.len();
+        if heights.len() != len
+            || encodings.len() != len
+            || names.len() != len
+            || offsets.len() != len + 1
+        {
+            return Err(Report::msg(
+                "Image batch fields don't have the same number of images.",
+            ));
+        }
+
+        let metadata = batch_metadata(raw_data, len)?;
+
+        let images = (0..len)
+            .zip(metadata)
+            .map(|(index, (header, capture))| {
+                let (start, end) = (offsets[index] as usize, offsets[index + 1] as usize);
+                let pixels = data.get(start..end).ok_or_eyre(Report::msg(format!(
+                    "Offsets of image {} are out of the batch data.",
+                    index
+                )))?;
+
+                let encoding = encodings[index].parse::<Encoding>()?;
+                check_len(encoding, widths[index], heights[index], pixels.len())?;
+
+                let data = match encoding {
+                    Encoding::RGB8 | Encoding::BGR8 | Encoding::GRAY8 => {
+                        ImageData::from_slice_u8(pixels)
+                    }
+                };
+
+                Ok(Self {
+                    data,
+                    width: widths[index],
+                    height: heights[index],
+                    encoding,
+                    name: Some(names[index].clone()).filter(|s| !s.is_empty()),
+                    header,
+                    capture,
+                })
+            })
+            .collect::<Result<Vec<Self>>>()?;
+
+        accounting::record("Image::view_batch_from_raw_data", data.len(), false);
+
+        Ok(images)
+    }
+
+    /// Converts Arrow `ArrayData` produced by [`Image::into_arrow_batch`] back into owned images.
+    ///
+    /// Each image gets its own pixel buffer, so the pixel data is copied once. Use
+    /// [`Image::view_batch_from_raw_data`] to read the batch without copying.
+    ///
+    /// # Arguments
+    ///
+    /// * `array_data` - The Arrow `ArrayData` containing the batch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the images in batch order if successful, or an error otherwise.
+    pub fn from_arrow_batch(array_data: arrow::array::ArrayData) -> Result<Vec<Self>> {
+        let raw_data = Self::batch_raw_data(array_data)?;
+
+        let images = Image::view_batch_from_raw_data(&raw_data)?
+            .into_iter()
+            .map(|image| Self {
+                data: image.data.into_static(),
+                width: image.width,
+                height: image.height,
+                encoding: image.encoding,
+                name: image.name,
+                header: image.header,
+                capture: image.capture,
+            })
+            .collect::<Vec<Self>>();
+
+        Ok(images)
     }
 }
 
@@ -250,4 +910,263 @@ mod tests {
         assert_eq!(original_buffer_address, image_buffer_address);
         assert_ne!(image_buffer_address, final_image_buffer);
     }
+
+    #[test]
+    fn test_arrow_schema_is_shared() {
+        use crate::image::Image;
+
+        let first = Image::new_bgr8(vec![0; 27], 3, 3, None).unwrap();
+        let second = Image::new_rgb8(vec![0; 12], 2, 2, Some("camera.test")).unwrap();
+
+        let first = first.into_arrow().unwrap();
+        let second = second.into_arrow().unwrap();
+
+        match (first.data_type(), second.data_type()) {
+            (
+                arrow::datatypes::DataType::Union(first, _),
+                arrow::datatypes::DataType::Union(second, _),
+            ) => {
+                let (_, first) = first.iter().next().unwrap();
+                let (_, second) = second.iter().next().unwrap();
+
+                assert!(std::sync::Arc::ptr_eq(first, second));
+            }
+            _ => panic!("Image is not serialized as a UnionArray"),
+        }
+    }
+
+    #[test]
+    fn test_arrow_aligned_view() {
+        use crate::image::Image;
+        use fastformat_converter::arrow::ALIGNMENT;
+
+        let flat_image = vec![0; 27];
+
+        let bgr8_image = Image::new_bgr8(flat_image, 3, 3, None).unwrap();
+        let arrow_image = bgr8_image.into_arrow().unwrap();
+
+        let raw_data = Image::aligned_raw_data(arrow_image).unwrap();
+        let image = Image::view_from_raw_data(&raw_data).unwrap();
+
+        assert!(raw_data.is_aligned("data").unwrap());
+        assert!(image.data.is_aligned(ALIGNMENT));
+        assert_eq!(image.data.as_u8().unwrap(), &[0; 27]);
+    }
+
+    #[test]
+    fn test_arrow_header() {
+        use crate::header::Header;
+        use crate::image::Image;
+
+        let header = Header::new(1_700_000_000_000_000_000, "camera_front", 42);
+
+        let image = Image::new_bgr8(vec![0; 27], 3, 3, None)
+            .unwrap()
+            .with_header(header.clone());
+        let arrow_image = image.into_arrow().unwrap();
+
+        let raw_data = Image::raw_data(arrow_image.clone()).unwrap();
+        let view = Image::view_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(view.header.as_ref(), Some(&header));
+
+        let image = Image::from_arrow(arrow_image).unwrap();
+
+        assert_eq!(image.header, Some(header));
+    }
+
+    #[test]
+    fn test_arrow_capture() {
+        use crate::image::{CaptureMetadata, GpsTag, Image};
+
+        let capture = CaptureMetadata {
+            exposure_time_ns: Some(8_333_333),
+            gain_db: Some(12.0),
+            lens: Some("Sony FE 24mm F1.4 GM".to_string()),
+            gps: Some(GpsTag {
+                latitude_deg: 48.8584,
+                longitude_deg: 2.2945,
+                altitude_m: 35.0,
+            }),
+            ..Default::default()
+        };
+
+        let image = Image::new_bgr8(vec![0; 27], 3, 3, None)
+            .unwrap()
+            .with_capture(capture.clone());
+        let image = Image::from_arrow(image.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(image.capture, Some(capture));
+
+        let image = Image::new_bgr8(vec![0; 27], 3, 3, None).unwrap();
+        let image = Image::from_arrow(image.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(image.capture, None);
+    }
+
+    #[test]
+    fn test_arrow_without_header_fields() {
+        use crate::image::Image;
+        use arrow::datatypes::{
+            DataType::{UInt32, UInt8, Utf8},
+            UInt32Type, UInt8Type,
+        };
+        use fastformat_converter::arrow::FastFormatArrowBuilder;
+
+        // Messages serialized before headers existed don't have the header fields at all.
+        let arrow_image = FastFormatArrowBuilder::new()
+            .push_primitive_singleton::<UInt32Type>("width", 3, UInt32, false)
+            .push_primitive_singleton::<UInt32Type>("height", 3, UInt32, false)
+            .push_utf_singleton("encoding", "BGR8", Utf8, false)
+            .push_utf_singleton("name", "", Utf8, false)
+            .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+            .into_arrow()
+            .unwrap();
+
+        let image = Image::from_arrow(arrow_image).unwrap();
+
+        assert_eq!(image.header, None);
+        assert_eq!(image.capture, None);
+        assert_eq!(image.data.as_u8().unwrap(), &[0; 27]);
+    }
+
+    #[test]
+    fn test_arrow_batch() {
+        use crate::{
+            header::{self, Header},
+            image::{CaptureMetadata, Encoding, GpsTag, Image},
+        };
+
+        let header = Header::new(1_700_000_000_000_000_000, "camera_front", 7);
+        let capture = CaptureMetadata {
+            iso: Some(400),
+            gps: Some(GpsTag {
+                latitude_deg: 48.85,
+                longitude_deg: 2.35,
+                altitude_m: 35.0,
+            }),
+            ..Default::default()
+        };
+
+        let images = vec![
+            Image::new_bgr8(vec![1; 27], 3, 3, Some("patch.0")).unwrap(),
+            Image::new_gray8(vec![2; 4], 2, 2, None)
+                .unwrap()
+                .with_header(header.clone())
+                .with_capture(capture.clone()),
+            Image::new_rgb8(vec![3; 6], 2, 1, Some("patch.2")).unwrap(),
+        ];
+
+        let arrow_batch = Image::into_arrow_batch(images).unwrap();
+
+        let raw_data = Image::batch_raw_data(arrow_batch.clone()).unwrap();
+        let views = Image::view_batch_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(views.len(), 3);
+        assert!(views.iter().all(|image| image.is_borrowed()));
+        assert_eq!(views[1].encoding, Encoding::GRAY8);
+        assert_eq!(views[1].name, None);
+        assert_eq!(views[2].name.as_deref(), Some("patch.2"));
+        assert_eq!(views[2].data.as_u8().unwrap(), &[3; 6]);
+        assert_eq!(views[0].header, None);
+        assert_eq!(views[0].capture, None);
+        assert_eq!(views[1].header.as_ref(), Some(&header));
+        assert_eq!(views[1].capture.as_ref(), Some(&capture));
+        assert_eq!(views[2].header, None);
+        assert_eq!(views[2].capture, None);
+        assert_eq!(
+            header::from_arrow(arrow_batch.clone()).unwrap(),
+            Some(header.clone())
+        );
+
+        let images = Image::from_arrow_batch(arrow_batch).unwrap();
+
+        assert_eq!(images[1].header, Some(header));
+        assert_eq!(images[1].capture, Some(capture));
+
+        assert!(images.iter().all(|image| !image.is_borrowed()));
+        assert_eq!((images[0].width, images[0].height), (3, 3));
+        assert_eq!(images[0].data.as_u8().unwrap(), &[1; 27]);
+    }
+
+    #[test]
+    fn test_arrow_zero_copy_shared_buffer() {
+        use crate::image::Image;
+
+        let flat_image = vec![0; 27];
+        let original_buffer_address = flat_image.as_ptr() as *const u64;
+
+        let bgr8_image = Image::new_bgr8(flat_image, 3, 3, None).unwrap();
+        let arrow_image = bgr8_image.into_arrow().unwrap();
+
+        // Keep a second reference to the buffers, like a dataflow forwarding the same message to
+        // several nodes.
+        let shared_arrow_image = arrow_image.clone();
+
+        let bgr8_image = Image::from_arrow(arrow_image).unwrap();
+
+        assert!(bgr8_image.is_borrowed());
+        assert_eq!(original_buffer_address, bgr8_image.data.as_ptr());
+
+        let rgb8_image = bgr8_image.into_rgb8().unwrap();
+
+        assert_ne!(original_buffer_address, rgb8_image.data.as_ptr());
+
+        let raw_data = Image::raw_data(shared_arrow_image).unwrap();
+        let shared_image = Image::view_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(original_buffer_address, shared_image.data.as_ptr());
+    }
+
+    #[test]
+    fn test_arrow_malformed() {
+        use crate::image::Image;
+        use arrow::datatypes::{
+            DataType::{UInt32, UInt8, Utf8},
+            UInt32Type, UInt8Type,
+        };
+        use fastformat_converter::arrow::FastFormatArrowBuilder;
+
+        let message = |width: Vec<u32>, data: Vec<u8>| {
+            FastFormatArrowBuilder::new()
+                .push_primitive_array::<UInt32Type>("width", width, UInt32, false)
+                .push_primitive_singleton::<UInt32Type>("height", 3, UInt32, false)
+                .push_utf_singleton("encoding", "RGB8", Utf8, false)
+                .push_utf_singleton("name", "", Utf8, false)
+                .push_primitive_array::<UInt8Type>("data", data, UInt8, false)
+                .into_arrow()
+                .unwrap()
+        };
+
+        assert!(Image::from_arrow(message(vec![3], vec![0; 27])).is_ok());
+
+        // Pixel data too short for the image size.
+        assert!(Image::from_arrow(message(vec![3], vec![0; 5])).is_err());
+        let raw_data = Image::raw_data(message(vec![3], vec![0; 5])).unwrap();
+        assert!(Image::view_from_raw_data(&raw_data).is_err());
+
+        // No width value.
+        assert!(Image::from_arrow(message(vec![], vec![0; 27])).is_err());
+
+        // Not a union.
+        let array_data = arrow::array::ArrayData::from(arrow::array::UInt8Array::from(vec![0; 27]));
+        assert!(Image::from_arrow(array_data).is_err());
+
+        // A child whose buffer is shorter than its length, e.g. received from a faulty peer.
+        let array_data = message(vec![3], vec![0; 27]);
+        let mut children = array_data.child_data().to_vec();
+        children[0] = unsafe {
+            arrow::array::ArrayData::builder(UInt32)
+                .len(4)
+                .add_buffer(arrow::buffer::Buffer::from_vec(vec![3u32]))
+                .build_unchecked()
+        };
+        let array_data = unsafe {
+            array_data
+                .into_builder()
+                .child_data(children)
+                .build_unchecked()
+        };
+        assert!(Image::from_arrow(array_data).is_err());
+    }
 }
@@ -33,7 +33,7 @@ impl Image<'_> {
     /// let image = Image::new_bgr8(data, 3, 3, Some("example")).unwrap();
     /// ```
     pub fn new_bgr8(data: Vec<u8>, width: u32, height: u32, name: Option<&str>) -> Result<Self> {
-        if width * height * 3 != data.len() as u32 {
+        if Encoding::BGR8.expected_len(width, height) != Some(data.len()) {
             return Err(Report::msg(
                 "Width, height and BGR8 encoding doesn't match data length.",
             ));
@@ -45,6 +45,8 @@ impl Image<'_> {
             height,
             encoding: Encoding::BGR8,
             name: name.map(|s| s.to_string()),
+            header: None,
+            capture: None,
         })
     }
 }
@@ -0,0 +1,267 @@
+use super::{capture::CaptureMetadata, data::ImageData, encoding::Encoding, Image};
+use crate::header::Header;
+use eyre::{Report, Result};
+
+/// Fluent constructor for an `Image`, validated when calling [`ImageBuilder::build`].
+///
+/// # Example
+///
+/// ```
+/// use fastformat_datatypes::image::{Encoding, Image};
+///
+/// let image = Image::builder()
+///     .width(3)
+///     .height(3)
+///     .encoding(Encoding::BGR8)
+///     .name("camera.front")
+///     .zeroed()
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(image.data.len(), 27);
+/// ```
+#[derive(Debug, Default)]
+pub struct ImageBuilder {
+    width: Option<u32>,
+    height: Option<u32>,
+    encoding: Option<Encoding>,
+    stride: Option<usize>,
+    name: Option<String>,
+    header: Option<Header>,
+    capture: Option<CaptureMetadata>,
+    data: Option<Vec<u8>>,
+    zeroed: bool,
+}
+
+impl Image<'_> {
+    /// Starts building an `Image`, see [`ImageBuilder`].
+    pub fn builder() -> ImageBuilder {
+        ImageBuilder::default()
+    }
+}
+
+impl ImageBuilder {
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+
+        self
+    }
+
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = Some(encoding);
+
+        self
+    }
+
+    /// Sets the number of bytes between the start of two rows of `data`, for data with padded
+    /// rows. The padding is removed at build time.
+    pub fn stride(mut self, stride: usize) -> Self {
+        self.stride = Some(stride);
+
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+
+        self
+    }
+
+    pub fn header(mut self, header: Header) -> Self {
+        self.header = Some(header);
+
+        self
+    }
+
+    pub fn capture(mut self, capture: CaptureMetadata) -> Self {
+        self.capture = Some(capture);
+
+        self
+    }
+
+    /// Sets the pixel data. The `Vec` is moved into the image without copying, unless rows are
+    /// padded (see [`ImageBuilder::stride`]).
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = Some(data);
+        self.zeroed = false;
+
+        self
+    }
+
+    /// Fills the image with zeros instead of taking pixel data. Can't be combined with
+    /// [`ImageBuilder::stride`].
+    pub fn zeroed(mut self) -> Self {
+        self.data = None;
+        self.zeroed = true;
+
+        self
+    }
+
+    /// Builds the `Image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the width, height, encoding or data is missing, if the stride is
+    /// shorter than a row or set on a zeroed image, or if the data length doesn't match the
+    /// width, height, encoding and stride.
+    pub fn build<'a>(self) -> Result<Image<'a>> {
+        let width = self
+            .width
+            .ok_or_else(|| Report::msg("Image width is missing"))?;
+        let height = self
+            .height
+            .ok_or_else(|| Report::msg("Image height is missing"))?;
+        let encoding = self
+            .encoding
+            .ok_or_else(|| Report::msg("Image encoding is missing"))?;
+
+        let len = encoding.expected_len(width, height).ok_or_else(|| {
+            Report::msg(format!(
+                "A {}x{} {} image doesn't fit in memory",
+                width, height, encoding
+            ))
+        })?;
+        let row_len = width as usize * encoding.channels();
+
+        let data = match (self.data, self.zeroed) {
+            (Some(data), _) => match self.stride {
+                Some(stride) if stride < row_len => {
+                    return Err(Report::msg(format!(
+                        "Stride {} is shorter than a row of {} bytes",
+                        stride, row_len
+                    )))
+                }
+                Some(stride) if stride != row_len => {
+                    // The last row doesn't need to be padded.
+                    let padded_len = match height {
+                        0 => Some(0),
+                        _ => stride
+                            .checked_mul(height as usize - 1)
+                            .and_then(|len| len.checked_add(row_len)),
+                    }
+                    .ok_or_else(|| {
+                        Report::msg(format!(
+                            "A {}x{} {} image with a stride of {} doesn't fit in memory",
+                            width, height, encoding, stride
+                        ))
+                    })?;
+
+                    if data.len() < padded_len {
+                        return Err(Report::msg(format!(
+                            "Width, height, stride and {} encoding doesn't match data length.",
+                            encoding
+                        )));
+                    }
+
+                    data.chunks(stride)
+                        .take(height as usize)
+                        .flat_map(|row| &row[..row_len])
+                        .copied()
+                        .collect()
+                }
+                _ => data,
+            },
+            (None, true) if self.stride.is_some() => {
+                return Err(Report::msg("Image stride can't be set on a zeroed image"))
+            }
+            (None, true) => vec![0; len],
+            (None, false) => return Err(Report::msg("Image data is missing")),
+        };
+
+        if data.len() != len {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                encoding
+            )));
+        }
+
+        Ok(Image {
+            data: ImageData::from_vec_u8(data),
+            width,
+            height,
+            encoding,
+            name: self.name,
+            header: self.header,
+            capture: self.capture,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_builder_zero_copy() {
+        use crate::image::{Encoding, Image};
+
+        let flat_image = vec![0; 27];
+        let original_buffer_address = flat_image.as_ptr() as *const u64;
+
+        let image = Image::builder()
+            .width(3)
+            .height(3)
+            .encoding(Encoding::RGB8)
+            .data(flat_image)
+            .build()
+            .unwrap();
+
+        assert_eq!(original_buffer_address, image.data.as_ptr());
+        assert!(Image::builder()
+            .width(3)
+            .height(3)
+            .zeroed()
+            .build()
+            .is_err());
+        assert!(Image::builder()
+            .width(3)
+            .height(3)
+            .encoding(Encoding::RGB8)
+            .data(vec![0; 26])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_stride() {
+        use crate::image::{Encoding, Image};
+
+        // 2x2 GRAY8 image with rows padded to 4 bytes.
+        let image = Image::builder()
+            .width(2)
+            .height(2)
+            .encoding(Encoding::GRAY8)
+            .stride(4)
+            .data(vec![1, 2, 0, 0, 3, 4, 0, 0])
+            .build()
+            .unwrap();
+
+        assert_eq!(image.data.as_u8().unwrap(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_builder_stride_errors() {
+        use crate::image::{Encoding, Image};
+
+        // The padded length overflows `usize`.
+        assert!(Image::builder()
+            .width(2)
+            .height(3)
+            .encoding(Encoding::GRAY8)
+            .stride(usize::MAX)
+            .data(vec![0; 8])
+            .build()
+            .is_err());
+        assert!(Image::builder()
+            .width(2)
+            .height(2)
+            .encoding(Encoding::GRAY8)
+            .stride(4)
+            .zeroed()
+            .build()
+            .is_err());
+    }
+}
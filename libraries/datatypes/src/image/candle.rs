@@ -0,0 +1,97 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Context, Report, Result};
+
+use candle_core::{DType, Device, Tensor};
+use fastformat_converter::{accounting, policy};
+
+impl Image<'_> {
+    /// Converts the image into a `u8` candle tensor of shape `[height, width, channels]` on
+    /// `device`.
+    ///
+    /// On the CPU the pixel data is moved into the tensor without copying when it is owned. On
+    /// other devices, e.g. CUDA, it is transferred to the device memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use candle_core::Device;
+    /// use fastformat_datatypes::image::Image;
+    ///
+    /// let image = Image::new_rgb8(vec![0; 27], 3, 3, None).unwrap();
+    /// let tensor = image.into_candle(&Device::Cpu).unwrap();
+    ///
+    /// assert_eq!(tensor.dims(), [3, 3, 3]);
+    /// ```
+    pub fn into_candle(self, device: &Device) -> Result<Tensor> {
+        let shape = (
+            self.height as usize,
+            self.width as usize,
+            self.encoding.channels(),
+        );
+
+        Tensor::from_vec(self.data.into_u8()?, shape, device).wrap_err(format!(
+            "Failed to create candle tensor: width, height and {} encoding doesn't match data length.",
+            self.encoding
+        ))
+    }
+
+    /// Creates an image from a `u8` candle tensor of shape `[height, width, channels]`, e.g. the
+    /// output of a model cast back with `to_dtype(DType::U8)`.
+    ///
+    /// candle tensors can't hand out their storage, so the pixel data is always copied, and
+    /// transferred back from the device first if the tensor isn't on the CPU.
+    pub fn from_candle(tensor: &Tensor, encoding: Encoding) -> Result<Image<'static>> {
+        let (height, width, channels) = tensor
+            .dims3()
+            .wrap_err("Expected a candle tensor of shape [height, width, channels]")?;
+
+        if tensor.dtype() != DType::U8 || channels != encoding.channels() {
+            return Err(Report::msg(format!(
+                "Candle tensor of type {:?} with {} channels doesn't match {} encoding",
+                tensor.dtype(),
+                channels,
+                encoding
+            )));
+        }
+
+        policy::check("Image::from_candle", tensor.elem_count())?;
+        accounting::record("Image::from_candle", tensor.elem_count(), true);
+
+        let data = tensor
+            .to_device(&Device::Cpu)?
+            .flatten_all()?
+            .to_vec1::<u8>()?;
+
+        Ok(Image {
+            data: ImageData::from_vec_u8(data),
+            width: width as u32,
+            height: height as u32,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_candle_round_trip() {
+        use crate::image::{Encoding, Image};
+        use candle_core::{DType, Device};
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+
+        let image = Image::new_bgr8(flat_image.clone(), 3, 3, None).unwrap();
+        let tensor = image.into_candle(&Device::Cpu).unwrap();
+
+        assert_eq!(tensor.dims(), [3, 3, 3]);
+
+        let image = Image::from_candle(&tensor, Encoding::BGR8).unwrap();
+
+        assert_eq!(image.data.as_u8().unwrap(), &flat_image);
+
+        assert!(Image::from_candle(&tensor, Encoding::GRAY8).is_err());
+        assert!(Image::from_candle(&tensor.to_dtype(DType::F32).unwrap(), Encoding::BGR8).is_err());
+    }
+}
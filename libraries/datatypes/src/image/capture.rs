@@ -0,0 +1,30 @@
+/// Acquisition parameters of an image, forwarded by camera drivers to downstream nodes, e.g.
+/// auto-exposure or SLAM. Every field is optional, as cameras report different subsets of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaptureMetadata {
+    pub exposure_time_ns: Option<u64>,
+    /// Analog gain, in decibels.
+    pub gain_db: Option<f32>,
+    pub iso: Option<u32>,
+    /// Lens model, e.g. `"Sony FE 24mm F1.4 GM"`.
+    pub lens: Option<String>,
+    pub gps: Option<GpsTag>,
+}
+
+/// Position of the camera when the image was captured, in WGS 84 (like EXIF GPS tags).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpsTag {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    /// Altitude above the WGS 84 ellipsoid, in meters.
+    pub altitude_m: f64,
+}
+
+impl CaptureMetadata {
+    /// Returns whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -1,12 +1,71 @@
 use eyre::Result;
+use fastformat_converter::{accounting, policy};
+
+use half::{f16, slice::HalfFloatSliceExt};
 
 use std::borrow::Cow;
 
+/// Pixel data of an image.
+///
+/// Non-exhaustive, as some variants such as `ArrowBuffer` only exist with their feature enabled.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ImageData<'a> {
     U8(Cow<'a, [u8]>),
     U16(Cow<'a, [u16]>),
     F32(Cow<'a, [f32]>),
+    I16(Cow<'a, [i16]>),
+    I32(Cow<'a, [i32]>),
+    F64(Cow<'a, [f64]>),
+    /// Half-precision data, e.g. depth maps or feature maps produced in fp16, kept at half the
+    /// size of `F32`. See [`ImageData::f16_to_f32`] and [`ImageData::from_f32_as_f16`].
+    F16(Cow<'a, [f16]>),
+
+    /// `u8` pixel data kept inside the Arrow buffer it was received in.
+    ///
+    /// The buffer may be shared with other arrays, so mutating the data copies it unless the
+    /// buffer turns out to be uniquely owned.
+    #[cfg(feature = "arrow")]
+    ArrowBuffer(arrow::buffer::Buffer),
+}
+
+/// Takes the bytes out of an Arrow buffer, copying them only if the buffer can't be reused.
+#[cfg(feature = "arrow")]
+fn arrow_buffer_into_vec(conversion: &'static str, buffer: arrow::buffer::Buffer) -> Vec<u8> {
+    let len = buffer.len();
+
+    match buffer.into_vec::<u8>() {
+        Ok(data) => {
+            accounting::record(conversion, len, false);
+
+            data
+        }
+        Err(buffer) => {
+            accounting::record(conversion, len, true);
+
+            buffer.to_vec()
+        }
+    }
+}
+
+/// Like [`arrow_buffer_into_vec`] for implicit conversions: copying is subject to the copy policy.
+#[cfg(feature = "arrow")]
+fn arrow_buffer_into_vec_checked(
+    conversion: &'static str,
+    buffer: arrow::buffer::Buffer,
+) -> Result<Vec<u8>> {
+    match buffer.into_vec::<u8>() {
+        Ok(data) => {
+            accounting::record(conversion, data.len(), false);
+
+            Ok(data)
+        }
+        Err(buffer) => {
+            policy::check(conversion, buffer.len())?;
+
+            Ok(arrow_buffer_into_vec(conversion, buffer))
+        }
+    }
 }
 
 impl ImageData<'_> {
@@ -15,41 +74,131 @@ impl ImageData<'_> {
             Self::U8(data) => data.len(),
             Self::U16(data) => data.len(),
             Self::F32(data) => data.len(),
+            Self::I16(data) => data.len(),
+            Self::I32(data) => data.len(),
+            Self::F64(data) => data.len(),
+            Self::F16(data) => data.len(),
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(buffer) => buffer.len(),
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn as_ptr(&self) -> *const u64 {
         match self {
             Self::U8(data) => data.as_ptr() as *const u64,
             Self::U16(data) => data.as_ptr() as *const u64,
             Self::F32(data) => data.as_ptr() as *const u64,
+            Self::I16(data) => data.as_ptr() as *const u64,
+            Self::I32(data) => data.as_ptr() as *const u64,
+            Self::F64(data) => data.as_ptr() as *const u64,
+            Self::F16(data) => data.as_ptr() as *const u64,
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(buffer) => buffer.as_ptr() as *const u64,
         }
     }
 
+    /// Returns whether the pixel buffer starts on an `alignment`-byte boundary.
+    pub fn is_aligned(&self, alignment: usize) -> bool {
+        (self.as_ptr() as *const u8).align_offset(alignment) == 0
+    }
+
     pub fn into_u8(self) -> Result<Vec<u8>> {
         match self {
-            Self::U8(data) => Ok(data.into_owned()),
+            Self::U8(data) => {
+                policy::check_cow("ImageData::into_u8", &data)?;
+                accounting::record_cow("ImageData::into_u8", &data);
+
+                Ok(data.into_owned())
+            }
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(buffer) => {
+                arrow_buffer_into_vec_checked("ImageData::into_u8", buffer)
+            }
             _ => Err(eyre::Report::msg("Can't convert data to u8")),
         }
     }
 
     pub fn into_u16(self) -> Result<Vec<u16>> {
         match self {
-            Self::U16(data) => Ok(data.into_owned()),
+            Self::U16(data) => {
+                policy::check_cow("ImageData::into_u16", &data)?;
+                accounting::record_cow("ImageData::into_u16", &data);
+
+                Ok(data.into_owned())
+            }
             _ => Err(eyre::Report::msg("Can't convert data to u16")),
         }
     }
 
     pub fn into_f32(self) -> Result<Vec<f32>> {
         match self {
-            Self::F32(data) => Ok(data.into_owned()),
+            Self::F32(data) => {
+                policy::check_cow("ImageData::into_f32", &data)?;
+                accounting::record_cow("ImageData::into_f32", &data);
+
+                Ok(data.into_owned())
+            }
             _ => Err(eyre::Report::msg("Can't convert data to f32")),
         }
     }
 
+    pub fn into_i16(self) -> Result<Vec<i16>> {
+        match self {
+            Self::I16(data) => {
+                policy::check_cow("ImageData::into_i16", &data)?;
+                accounting::record_cow("ImageData::into_i16", &data);
+
+                Ok(data.into_owned())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to i16")),
+        }
+    }
+
+    pub fn into_i32(self) -> Result<Vec<i32>> {
+        match self {
+            Self::I32(data) => {
+                policy::check_cow("ImageData::into_i32", &data)?;
+                accounting::record_cow("ImageData::into_i32", &data);
+
+                Ok(data.into_owned())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to i32")),
+        }
+    }
+
+    pub fn into_f64(self) -> Result<Vec<f64>> {
+        match self {
+            Self::F64(data) => {
+                policy::check_cow("ImageData::into_f64", &data)?;
+                accounting::record_cow("ImageData::into_f64", &data);
+
+                Ok(data.into_owned())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to f64")),
+        }
+    }
+
+    pub fn into_f16(self) -> Result<Vec<f16>> {
+        match self {
+            Self::F16(data) => {
+                policy::check_cow("ImageData::into_f16", &data)?;
+                accounting::record_cow("ImageData::into_f16", &data);
+
+                Ok(data.into_owned())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to f16")),
+        }
+    }
+
     pub fn as_u8(&self) -> Result<&[u8]> {
         match self {
             Self::U8(data) => Ok(data),
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(buffer) => Ok(buffer.as_slice()),
             _ => Err(eyre::Report::msg("Can't convert data to u8")),
         }
     }
@@ -68,27 +217,310 @@ impl ImageData<'_> {
         }
     }
 
+    pub fn as_i16(&self) -> Result<&[i16]> {
+        match self {
+            Self::I16(data) => Ok(data),
+            _ => Err(eyre::Report::msg("Can't convert data to i16")),
+        }
+    }
+
+    pub fn as_i32(&self) -> Result<&[i32]> {
+        match self {
+            Self::I32(data) => Ok(data),
+            _ => Err(eyre::Report::msg("Can't convert data to i32")),
+        }
+    }
+
+    pub fn as_f64(&self) -> Result<&[f64]> {
+        match self {
+            Self::F64(data) => Ok(data),
+            _ => Err(eyre::Report::msg("Can't convert data to f64")),
+        }
+    }
+
+    pub fn as_f16(&self) -> Result<&[f16]> {
+        match self {
+            Self::F16(data) => Ok(data),
+            _ => Err(eyre::Report::msg("Can't convert data to f16")),
+        }
+    }
+
     pub fn as_mut_u8(&mut self) -> Result<&mut Vec<u8>> {
+        #[cfg(feature = "arrow")]
+        if let Self::ArrowBuffer(buffer) = self {
+            let buffer = std::mem::replace(buffer, arrow::buffer::Buffer::from_vec::<u8>(vec![]));
+
+            // Put the buffer back if the policy rejects the copy, so the pixels aren't lost.
+            let data = match buffer.into_vec::<u8>() {
+                Ok(data) => {
+                    accounting::record("ImageData::as_mut_u8", data.len(), false);
+
+                    data
+                }
+                Err(buffer) => {
+                    if let Err(error) = policy::check("ImageData::as_mut_u8", buffer.len()) {
+                        *self = Self::ArrowBuffer(buffer);
+
+                        return Err(error);
+                    }
+
+                    arrow_buffer_into_vec("ImageData::as_mut_u8", buffer)
+                }
+            };
+
+            *self = Self::U8(Cow::Owned(data));
+        }
+
         match self {
-            Self::U8(data) => Ok(data.to_mut()),
+            Self::U8(data) => {
+                policy::check_cow("ImageData::as_mut_u8", data)?;
+                accounting::record_cow("ImageData::as_mut_u8", data);
+
+                Ok(data.to_mut())
+            }
             _ => Err(eyre::Report::msg("Can't convert data to u8")),
         }
     }
 
     pub fn as_mut_u16(&mut self) -> Result<&mut Vec<u16>> {
         match self {
-            Self::U16(data) => Ok(data.to_mut()),
+            Self::U16(data) => {
+                policy::check_cow("ImageData::as_mut_u16", data)?;
+                accounting::record_cow("ImageData::as_mut_u16", data);
+
+                Ok(data.to_mut())
+            }
             _ => Err(eyre::Report::msg("Can't convert data to 16")),
         }
     }
 
     pub fn as_mut_f32(&mut self) -> Result<&mut Vec<f32>> {
         match self {
-            Self::F32(data) => Ok(data.to_mut()),
+            Self::F32(data) => {
+                policy::check_cow("ImageData::as_mut_f32", data)?;
+                accounting::record_cow("ImageData::as_mut_f32", data);
+
+                Ok(data.to_mut())
+            }
             _ => Err(eyre::Report::msg("Can't convert data to f32")),
         }
     }
 
+    pub fn as_mut_i16(&mut self) -> Result<&mut Vec<i16>> {
+        match self {
+            Self::I16(data) => {
+                policy::check_cow("ImageData::as_mut_i16", data)?;
+                accounting::record_cow("ImageData::as_mut_i16", data);
+
+                Ok(data.to_mut())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to i16")),
+        }
+    }
+
+    pub fn as_mut_i32(&mut self) -> Result<&mut Vec<i32>> {
+        match self {
+            Self::I32(data) => {
+                policy::check_cow("ImageData::as_mut_i32", data)?;
+                accounting::record_cow("ImageData::as_mut_i32", data);
+
+                Ok(data.to_mut())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to i32")),
+        }
+    }
+
+    pub fn as_mut_f64(&mut self) -> Result<&mut Vec<f64>> {
+        match self {
+            Self::F64(data) => {
+                policy::check_cow("ImageData::as_mut_f64", data)?;
+                accounting::record_cow("ImageData::as_mut_f64", data);
+
+                Ok(data.to_mut())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to f64")),
+        }
+    }
+
+    pub fn as_mut_f16(&mut self) -> Result<&mut Vec<f16>> {
+        match self {
+            Self::F16(data) => {
+                policy::check_cow("ImageData::as_mut_f16", data)?;
+                accounting::record_cow("ImageData::as_mut_f16", data);
+
+                Ok(data.to_mut())
+            }
+            _ => Err(eyre::Report::msg("Can't convert data to f16")),
+        }
+    }
+
+    /// Returns whether the data is borrowed, i.e. whether the next mutation will copy it.
+    pub fn is_borrowed(&self) -> bool {
+        match self {
+            Self::U8(data) => matches!(data, Cow::Borrowed(_)),
+            Self::U16(data) => matches!(data, Cow::Borrowed(_)),
+            Self::F32(data) => matches!(data, Cow::Borrowed(_)),
+            Self::I16(data) => matches!(data, Cow::Borrowed(_)),
+            Self::I32(data) => matches!(data, Cow::Borrowed(_)),
+            Self::F64(data) => matches!(data, Cow::Borrowed(_)),
+            Self::F16(data) => matches!(data, Cow::Borrowed(_)),
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(_) => true,
+        }
+    }
+
+    /// Copies borrowed data into an owned buffer now, so that later mutations don't.
+    pub fn ensure_owned(&mut self) {
+        match self {
+            Self::U8(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            Self::U16(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            Self::F32(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            Self::I16(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            Self::I32(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            Self::F64(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            Self::F16(data) => {
+                accounting::record_cow("ImageData::ensure_owned", data);
+                data.to_mut();
+            }
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(buffer) => {
+                let buffer =
+                    std::mem::replace(buffer, arrow::buffer::Buffer::from_vec::<u8>(vec![]));
+
+                *self = Self::U8(Cow::Owned(arrow_buffer_into_vec(
+                    "ImageData::ensure_owned",
+                    buffer,
+                )));
+            }
+        }
+    }
+
+    pub fn try_mut_u8(&mut self) -> Result<&mut [u8]> {
+        match self {
+            Self::U8(Cow::Owned(data)) => Ok(data),
+            Self::U8(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(_) => Err(eyre::Report::msg(
+                "Data is held by an Arrow buffer, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to u8")),
+        }
+    }
+
+    pub fn try_mut_u16(&mut self) -> Result<&mut [u16]> {
+        match self {
+            Self::U16(Cow::Owned(data)) => Ok(data),
+            Self::U16(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to u16")),
+        }
+    }
+
+    pub fn try_mut_f32(&mut self) -> Result<&mut [f32]> {
+        match self {
+            Self::F32(Cow::Owned(data)) => Ok(data),
+            Self::F32(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to f32")),
+        }
+    }
+
+    pub fn try_mut_i16(&mut self) -> Result<&mut [i16]> {
+        match self {
+            Self::I16(Cow::Owned(data)) => Ok(data),
+            Self::I16(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to i16")),
+        }
+    }
+
+    pub fn try_mut_i32(&mut self) -> Result<&mut [i32]> {
+        match self {
+            Self::I32(Cow::Owned(data)) => Ok(data),
+            Self::I32(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to i32")),
+        }
+    }
+
+    pub fn try_mut_f64(&mut self) -> Result<&mut [f64]> {
+        match self {
+            Self::F64(Cow::Owned(data)) => Ok(data),
+            Self::F64(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to f64")),
+        }
+    }
+
+    pub fn try_mut_f16(&mut self) -> Result<&mut [f16]> {
+        match self {
+            Self::F16(Cow::Owned(data)) => Ok(data),
+            Self::F16(Cow::Borrowed(_)) => Err(eyre::Report::msg(
+                "Data is borrowed, call ensure_owned first",
+            )),
+            _ => Err(eyre::Report::msg("Can't convert data to f16")),
+        }
+    }
+
+    /// Widens half-precision data to `f32`, e.g. for code without fp16 support. The conversion
+    /// uses the F16C or NEON instructions when the CPU has them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data isn't `f16`, or if copying is denied by the copy policy.
+    pub fn f16_to_f32(&self) -> Result<Vec<f32>> {
+        let data = self.as_f16()?;
+
+        policy::check("ImageData::f16_to_f32", data.len() * 4)?;
+        accounting::record("ImageData::f16_to_f32", data.len() * 4, true);
+
+        let mut widened = vec![0.0; data.len()];
+        data.convert_to_f32_slice(&mut widened);
+
+        Ok(widened)
+    }
+
+    /// Narrows `f32` data to half precision, halving its size. Values outside of the `f16`
+    /// range become infinite, and precision is rounded to 11 significant bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if copying is denied by the copy policy.
+    pub fn from_f32_as_f16(data: &[f32]) -> Result<ImageData<'static>> {
+        policy::check("ImageData::from_f32_as_f16", data.len() * 2)?;
+        accounting::record("ImageData::from_f32_as_f16", data.len() * 2, true);
+
+        let mut narrowed = vec![f16::ZERO; data.len()];
+        narrowed.convert_from_f32_slice(data);
+
+        Ok(ImageData::F16(Cow::Owned(narrowed)))
+    }
+
     pub fn from_vec_u8(data: Vec<u8>) -> Self {
         Self::U8(Cow::from(data))
     }
@@ -100,6 +532,72 @@ impl ImageData<'_> {
     pub fn from_vec_f32(data: Vec<f32>) -> Self {
         Self::F32(Cow::from(data))
     }
+
+    pub fn from_vec_i16(data: Vec<i16>) -> Self {
+        Self::I16(Cow::from(data))
+    }
+
+    pub fn from_vec_i32(data: Vec<i32>) -> Self {
+        Self::I32(Cow::from(data))
+    }
+
+    pub fn from_vec_f64(data: Vec<f64>) -> Self {
+        Self::F64(Cow::from(data))
+    }
+
+    pub fn from_vec_f16(data: Vec<f16>) -> Self {
+        Self::F16(Cow::from(data))
+    }
+
+    /// Wraps `u8` pixel data held by an Arrow buffer without copying it.
+    #[cfg(feature = "arrow")]
+    pub fn from_arrow_buffer(buffer: arrow::buffer::Buffer) -> Self {
+        Self::ArrowBuffer(buffer)
+    }
+
+    /// Detaches the data from the lifetime of the buffer it borrows from, copying borrowed data.
+    /// Owned data and Arrow buffers are moved as is.
+    pub fn into_static(self) -> ImageData<'static> {
+        match self {
+            Self::U8(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::U8(Cow::Owned(data.into_owned()))
+            }
+            Self::U16(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::U16(Cow::Owned(data.into_owned()))
+            }
+            Self::F32(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::F32(Cow::Owned(data.into_owned()))
+            }
+            Self::I16(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::I16(Cow::Owned(data.into_owned()))
+            }
+            Self::I32(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::I32(Cow::Owned(data.into_owned()))
+            }
+            Self::F64(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::F64(Cow::Owned(data.into_owned()))
+            }
+            Self::F16(data) => {
+                accounting::record_cow("ImageData::into_static", &data);
+
+                ImageData::F16(Cow::Owned(data.into_owned()))
+            }
+            #[cfg(feature = "arrow")]
+            Self::ArrowBuffer(buffer) => ImageData::ArrowBuffer(buffer),
+        }
+    }
 }
 
 impl<'a> ImageData<'a> {
@@ -114,4 +612,111 @@ impl<'a> ImageData<'a> {
     pub fn from_slice_f32(data: &'a [f32]) -> Self {
         Self::F32(Cow::from(data))
     }
+
+    pub fn from_slice_i16(data: &'a [i16]) -> Self {
+        Self::I16(Cow::from(data))
+    }
+
+    pub fn from_slice_i32(data: &'a [i32]) -> Self {
+        Self::I32(Cow::from(data))
+    }
+
+    pub fn from_slice_f64(data: &'a [f64]) -> Self {
+        Self::F64(Cow::from(data))
+    }
+
+    pub fn from_slice_f16(data: &'a [f16]) -> Self {
+        Self::F16(Cow::from(data))
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_signed_and_f64_data() {
+        use crate::image::data::ImageData;
+
+        let disparity = vec![-3i16, 0, 7];
+        let mut data = ImageData::from_slice_i16(&disparity);
+
+        assert_eq!(data.as_i16().unwrap(), &[-3, 0, 7]);
+        assert!(data.as_u16().is_err());
+        assert!(data.try_mut_i16().is_err());
+
+        data.ensure_owned();
+        data.try_mut_i16().unwrap()[0] = -4;
+
+        assert_eq!(data.into_i16().unwrap(), vec![-4, 0, 7]);
+
+        let data = ImageData::from_vec_f64(vec![0.5, 1.5]);
+        let original_buffer_address = data.as_ptr();
+
+        assert_eq!(data.len(), 2);
+
+        let data = data.into_f64().unwrap();
+        assert_eq!(data.as_ptr() as *const u64, original_buffer_address);
+
+        let data = ImageData::from_vec_i32(vec![1, 2, 3]).into_static();
+        assert_eq!(data.as_i32().unwrap(), &[1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn test_denied_as_mut_u8_keeps_data() {
+        use crate::image::data::ImageData;
+        use fastformat_converter::policy::{self, CopyPolicy};
+
+        let pixels = (0..2 * 1024 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+        let buffer = arrow::buffer::Buffer::from_vec(pixels.clone());
+
+        // A buffer shared with another array can only be mutated by copying it.
+        let mut data = ImageData::ArrowBuffer(buffer.clone());
+
+        let result = {
+            let _policy = policy::scoped(CopyPolicy::UpTo(1024 * 1024));
+            data.as_mut_u8().map(|_| ())
+        };
+
+        assert!(result.is_err());
+        assert_eq!(data.as_u8().unwrap(), &pixels[..]);
+
+        drop(buffer);
+        data.as_mut_u8().unwrap()[0] = 255;
+        assert_eq!(data.as_u8().unwrap()[0], 255);
+    }
+
+    #[test]
+    fn test_f16_data() {
+        use crate::image::{data::ImageData, f16};
+        use fastformat_converter::policy::{self, CopyPolicy};
+
+        let depth = [0.5f32, 1.25, 65504.0, 1e6];
+        let data = ImageData::from_f32_as_f16(&depth).unwrap();
+
+        assert_eq!(data.len(), 4);
+        assert_eq!(data.as_f16().unwrap()[1], f16::from_f32(1.25));
+        assert!(data.as_f32().is_err());
+
+        let widened = data.f16_to_f32().unwrap();
+
+        assert_eq!(&widened[..3], &depth[..3]);
+        assert_eq!(widened[3], f32::INFINITY);
+        assert!(ImageData::from_vec_f32(depth.to_vec())
+            .f16_to_f32()
+            .is_err());
+
+        let depth = vec![0.5f32; 1024 * 1024];
+
+        let result = {
+            let _policy = policy::scoped(CopyPolicy::UpTo(1024 * 1024));
+            ImageData::from_f32_as_f16(&depth).map(|_| ())
+        };
+
+        assert!(result.is_err());
+
+        let half = [f16::ONE, f16::NEG_ONE];
+        let data = ImageData::from_slice_f16(&half);
+
+        assert!(data.is_borrowed());
+        assert_eq!(data.into_static().into_f16().unwrap(), half.to_vec());
+    }
 }
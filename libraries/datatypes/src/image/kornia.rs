@@ -0,0 +1,94 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Context, Report, Result};
+
+use kornia_image::ImageSize;
+
+fn check_channels(encoding: Encoding, channels: usize) -> Result<()> {
+    if encoding.channels() != channels {
+        return Err(Report::msg(format!(
+            "{} encoding has {} channels, kornia image has {}",
+            encoding,
+            encoding.channels(),
+            channels
+        )));
+    }
+
+    Ok(())
+}
+
+impl Image<'_> {
+    /// Converts the image into a kornia image with `C` channels, moving the pixel data without
+    /// copying it when it is owned.
+    ///
+    /// kornia images don't store the channel order, which is lost: keep the encoding around to
+    /// convert back with [`Image::from_kornia`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fastformat_datatypes::image::Image;
+    ///
+    /// let image = Image::new_rgb8(vec![0; 27], 3, 3, None).unwrap();
+    /// let image = image.into_kornia::<3>().unwrap();
+    ///
+    /// assert_eq!((image.width(), image.height()), (3, 3));
+    /// ```
+    pub fn into_kornia<const C: usize>(self) -> Result<kornia_image::Image<u8, C>> {
+        check_channels(self.encoding, C)?;
+
+        let size = ImageSize {
+            width: self.width as usize,
+            height: self.height as usize,
+        };
+
+        kornia_image::Image::new(size, self.data.into_u8()?).wrap_err(format!(
+            "Failed to create kornia image: width, height and {} encoding doesn't match data length.",
+            self.encoding
+        ))
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Creates an image borrowing the pixel data of a kornia image, without copying. The channel
+    /// order isn't stored in kornia images, so it must be given.
+    pub fn from_kornia<const C: usize>(
+        image: &'a kornia_image::Image<u8, C>,
+        encoding: Encoding,
+    ) -> Result<Self> {
+        check_channels(encoding, C)?;
+
+        Ok(Image {
+            data: ImageData::from_slice_u8(image.as_slice()),
+            width: image.width() as u32,
+            height: image.height() as u32,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_kornia_zero_copy() {
+        use crate::image::{Encoding, Image};
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let original_buffer_address = flat_image.as_ptr();
+
+        let image = Image::new_bgr8(flat_image, 3, 3, None).unwrap();
+        let kornia_image = image.into_kornia::<3>().unwrap();
+
+        assert_eq!(kornia_image.as_slice().as_ptr(), original_buffer_address);
+        assert_eq!(kornia_image.get_pixel(1, 0, 2).unwrap(), &5);
+
+        let image = Image::from_kornia(&kornia_image, Encoding::BGR8).unwrap();
+
+        assert!(image.is_borrowed());
+        assert_eq!(image.data.as_ptr() as *const u8, original_buffer_address);
+
+        assert!(Image::from_kornia(&kornia_image, Encoding::GRAY8).is_err());
+        assert!(image.into_kornia::<1>().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use crate::mmap::MappedFile;
+use eyre::{Report, Result};
+
+impl<'a> Image<'a> {
+    /// Creates a read-only view of an `Image` stored in a memory-mapped file.
+    ///
+    /// The pixel data is borrowed from the mapping without being read or copied, which lets
+    /// playback nodes step through multi-gigabyte recordings of raw frames. As with any view,
+    /// converting the image to another encoding copies the data on write.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The mapped file holding the pixel data.
+    /// * `offset` - The offset of the first byte of the image in the file.
+    /// * `width` - The width of the image.
+    /// * `height` - The height of the image.
+    /// * `encoding` - The encoding of the pixel data.
+    /// * `name` - An optional string slice representing the name of the image.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Image` if successful, or an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is too short to hold the image at `offset`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use fastformat_datatypes::image::{Encoding, Image};
+    /// use fastformat_datatypes::mmap::MappedFile;
+    ///
+    /// // Safety: the recording isn't modified while it's being played back.
+    /// let file = unsafe { MappedFile::open("recording.bgr8") }.unwrap();
+    ///
+    /// let frame_size = 1280 * 720 * 3;
+    /// for frame in 0..file.len() / frame_size {
+    ///     let image = Image::view_from_mapped_file(
+    ///         &file,
+    ///         frame * frame_size,
+    ///         1280,
+    ///         720,
+    ///         Encoding::BGR8,
+    ///         None,
+    ///     )
+    ///     .unwrap();
+    /// }
+    /// ```
+    pub fn view_from_mapped_file(
+        file: &'a MappedFile,
+        offset: usize,
+        width: u32,
+        height: u32,
+        encoding: Encoding,
+        name: Option<&str>,
+    ) -> Result<Self> {
+        let channels = match encoding {
+            Encoding::RGB8 | Encoding::BGR8 => 3,
+            Encoding::GRAY8 => 1,
+        };
+
+        let len = width as usize * height as usize * channels;
+        let end = offset
+            .checked_add(len)
+            .ok_or_else(|| Report::msg("Image offset and size overflow"))?;
+        let data = file.slice(offset..end)?;
+
+        Ok(Image {
+            data: ImageData::from_slice_u8(data),
+            width,
+            height,
+            encoding,
+            name: name.map(|s| s.to_string()),
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_view_from_mapped_file() {
+        use crate::image::{Encoding, Image};
+        use crate::mmap::MappedFile;
+
+        let path = std::env::temp_dir().join(format!(
+            "fastformat-test-view-from-mapped-file-{}",
+            std::process::id()
+        ));
+        std::fs::write(&path, (0..54).collect::<Vec<u8>>()).unwrap();
+
+        let file = unsafe { MappedFile::open(&path) }.unwrap();
+
+        let image =
+            Image::view_from_mapped_file(&file, 27, 3, 3, Encoding::BGR8, Some("camera.test"))
+                .unwrap();
+
+        assert_eq!(
+            image.data.as_ptr() as *const u8,
+            file.as_slice()[27..].as_ptr()
+        );
+        assert_eq!(image.data.as_u8().unwrap(), &(27..54).collect::<Vec<u8>>());
+
+        assert!(Image::view_from_mapped_file(&file, 28, 3, 3, Encoding::BGR8, None).is_err());
+
+        drop(image);
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
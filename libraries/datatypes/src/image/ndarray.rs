@@ -1,7 +1,7 @@
 use super::{encoding::Encoding, Image};
 use eyre::{Context, Report, Result};
 
-use fastformat_converter::ndarray::{Ndarray, NdarrayView, NdarrayViewMut};
+use fastformat_converter::ndarray::{into_row_major_vec, Ndarray, NdarrayView, NdarrayViewMut};
 
 pub type NdarrayImage = (Ndarray, Encoding, Option<String>);
 pub type NdarrayImageView<'a> = (NdarrayView<'a>, Encoding, Option<&'a str>);
@@ -14,7 +14,7 @@ impl Image<'_> {
                 let width = array.shape()[1] as u32;
                 let height = array.shape()[0] as u32;
 
-                let (data, _) = array.into_raw_vec_and_offset();
+                let data = into_row_major_vec("Image::from_ndarray", array)?;
 
                 Self::new_bgr8(data, width, height, name.as_deref())
             }
@@ -22,7 +22,7 @@ impl Image<'_> {
                 let width = array.shape()[1] as u32;
                 let height = array.shape()[0] as u32;
 
-                let (data, _) = array.into_raw_vec_and_offset();
+                let data = into_row_major_vec("Image::from_ndarray", array)?;
 
                 Self::new_rgb8(data, width, height, name.as_deref())
             }
@@ -30,7 +30,7 @@ impl Image<'_> {
                 let width = array.shape()[1] as u32;
                 let height = array.shape()[0] as u32;
 
-                let (data, _) = array.into_raw_vec_and_offset();
+                let data = into_row_major_vec("Image::from_ndarray", array)?;
 
                 Self::new_gray8(data, width, height, name.as_deref())
             }
@@ -72,7 +72,7 @@ impl Image<'_> {
 }
 
 impl<'a> Image<'a> {
-    pub fn to_ndarray_view(&'a self) -> Result<(NdarrayView<'a>, Encoding, Option<&str>)> {
+    pub fn to_ndarray_view(&'a self) -> Result<NdarrayImageView<'a>> {
         match self.encoding {
             Encoding::BGR8 => {
                 let array = ndarray::ArrayView3::from_shape(
@@ -122,9 +122,7 @@ impl<'a> Image<'a> {
         }
     }
 
-    pub fn to_ndarray_view_mut(
-        &'a mut self,
-    ) -> Result<(NdarrayViewMut<'a>, Encoding, Option<&str>)> {
+    pub fn to_ndarray_view_mut(&'a mut self) -> Result<NdarrayImageViewMut<'a>> {
         match self.encoding {
             Encoding::BGR8 => {
                 let array = ndarray::ArrayViewMut3::from_shape(
@@ -241,4 +239,32 @@ mod tests {
         assert_eq!(image_buffer_address, ndarray_buffer_address);
         assert_eq!(ndarray_buffer_address, final_image_buffer_address);
     }
+    #[test]
+    fn test_gray8_from_sliced_ndarray() {
+        use crate::image::{Encoding, Image};
+        use fastformat_converter::ndarray::Ndarray;
+        use ndarray::s;
+
+        let array = ndarray::Array2::from_shape_vec((4, 4), (0..16).collect::<Vec<u8>>()).unwrap();
+
+        // Every other column: not in standard layout, the image is a copy.
+        let columns = array.clone().slice_move(s![.., ..;2]);
+        let image = Image::from_ndarray((Ndarray::U8IX2(columns), Encoding::GRAY8, None)).unwrap();
+
+        assert_eq!((image.width, image.height), (2, 4));
+        assert_eq!(image.data.as_u8().unwrap(), &[0, 2, 4, 6, 8, 10, 12, 14]);
+
+        // Rows 1 and 2: in standard layout, but starting at an offset in the buffer.
+        let rows = array.clone().slice_move(s![1..3, ..]);
+        let image = Image::from_ndarray((Ndarray::U8IX2(rows), Encoding::GRAY8, None)).unwrap();
+
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(image.data.as_u8().unwrap(), &(4..12).collect::<Vec<u8>>());
+
+        let transposed = array.reversed_axes();
+        let image =
+            Image::from_ndarray((Ndarray::U8IX2(transposed), Encoding::GRAY8, None)).unwrap();
+
+        assert_eq!(&image.data.as_u8().unwrap()[..4], &[0, 4, 8, 12]);
+    }
 }
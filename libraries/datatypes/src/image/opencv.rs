@@ -0,0 +1,161 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Report, Result};
+
+use fastformat_converter::{accounting, policy};
+use opencv::{
+    boxed_ref::{BoxedRef, BoxedRefMut},
+    core::{Mat, Vec3b, CV_8UC1, CV_8UC3},
+    prelude::*,
+};
+
+fn opencv_type(encoding: Encoding) -> i32 {
+    match encoding {
+        Encoding::RGB8 | Encoding::BGR8 => CV_8UC3,
+        Encoding::GRAY8 => CV_8UC1,
+    }
+}
+
+impl Image<'_> {
+    /// Wraps the pixel data in an OpenCV `Mat`, without copying. The `Mat` borrows the image, so
+    /// it can't outlive it.
+    ///
+    /// OpenCV doesn't track the channel order: a `RGB8` image gives a `CV_8UC3` `Mat` that
+    /// OpenCV functions will treat as BGR.
+    pub fn to_opencv_mat(&self) -> Result<BoxedRef<'_, Mat>> {
+        let data = self.data.as_u8()?;
+
+        let mat = match self.encoding {
+            Encoding::RGB8 | Encoding::BGR8 => {
+                Mat::new_rows_cols_with_bytes::<Vec3b>(self.height as i32, self.width as i32, data)
+            }
+            Encoding::GRAY8 => {
+                Mat::new_rows_cols_with_data::<u8>(self.height as i32, self.width as i32, data)
+            }
+        };
+
+        mat.map_err(|error| {
+            Report::new(error).wrap_err(format!(
+                "Failed to wrap data into a Mat: width, height and {} encoding doesn't match data length.",
+                self.encoding
+            ))
+        })
+    }
+
+    /// Wraps the pixel data in a mutable OpenCV `Mat`, without copying, so that OpenCV functions
+    /// can write into the image in place.
+    ///
+    /// Fails if the pixel data is borrowed, see [`Image::try_mut`].
+    pub fn to_opencv_mat_mut(&mut self) -> Result<BoxedRefMut<'_, Mat>> {
+        let (width, height, encoding) = (self.width as i32, self.height as i32, self.encoding);
+        let data = self.try_mut()?.try_mut_u8()?;
+
+        let mat = match encoding {
+            Encoding::RGB8 | Encoding::BGR8 => {
+                Mat::new_rows_cols_with_bytes_mut::<Vec3b>(height, width, data)
+            }
+            Encoding::GRAY8 => Mat::new_rows_cols_with_data_mut::<u8>(height, width, data),
+        };
+
+        mat.map_err(|error| {
+            Report::new(error).wrap_err(format!(
+                "Failed to wrap data into a Mat: width, height and {} encoding doesn't match data length.",
+                encoding
+            ))
+        })
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Creates an image borrowing the data of an OpenCV `Mat`, e.g. the frame of a
+    /// `VideoCapture`. The channel order isn't stored in the `Mat`, so it must be given: OpenCV
+    /// produces `BGR8` by default.
+    ///
+    /// A `Mat` whose rows are padded, e.g. a region of interest of a larger `Mat`, isn't
+    /// continuous: its rows are then copied into an owned image, subject to the copy policy.
+    pub fn from_opencv_mat(mat: &'a impl MatTraitConst, encoding: Encoding) -> Result<Self> {
+        if mat.dims() != 2 || mat.typ() != opencv_type(encoding) {
+            return Err(Report::msg(format!(
+                "Mat type {} with {} dimensions doesn't match {} encoding",
+                mat.typ(),
+                mat.dims(),
+                encoding
+            )));
+        }
+
+        let width = mat.cols() as u32;
+        let height = mat.rows() as u32;
+
+        let data = match mat.is_continuous() {
+            true => {
+                let data = mat.data_bytes()?;
+
+                accounting::record("Image::from_opencv_mat", data.len(), false);
+
+                ImageData::from_slice_u8(data)
+            }
+            false => {
+                let row_len = width as usize * encoding.channels();
+                let len = row_len * height as usize;
+
+                policy::check("Image::from_opencv_mat", len)?;
+                accounting::record("Image::from_opencv_mat", len, true);
+
+                let mut data = Vec::with_capacity(len);
+                for row in 0..mat.rows() {
+                    // SAFETY: `row` is in bounds and every row of a 2D `Mat` of this type holds
+                    // `row_len` bytes.
+                    data.extend_from_slice(unsafe {
+                        std::slice::from_raw_parts(mat.ptr(row)?, row_len)
+                    });
+                }
+
+                ImageData::from_vec_u8(data)
+            }
+        };
+
+        Ok(Image {
+            data,
+            width,
+            height,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_opencv_mat_zero_copy() {
+        use crate::image::{Encoding, Image};
+        use opencv::{
+            core::{Mat, Rect},
+            prelude::*,
+        };
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let original_buffer_address = flat_image.as_ptr();
+
+        let image = Image::new_bgr8(flat_image, 3, 3, None).unwrap();
+        let mat = image.to_opencv_mat().unwrap();
+
+        assert_eq!(mat.data(), original_buffer_address);
+        assert_eq!((mat.rows(), mat.cols(), mat.channels()), (3, 3, 3));
+
+        let image = Image::from_opencv_mat(&mat, Encoding::BGR8).unwrap();
+
+        assert!(image.is_borrowed());
+        assert_eq!(image.data.as_ptr() as *const u8, original_buffer_address);
+
+        // A region of interest has padded rows and is copied.
+        let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2)).unwrap();
+        let image = Image::from_opencv_mat(&roi, Encoding::BGR8).unwrap();
+
+        assert_eq!(
+            image.data.as_u8().unwrap(),
+            &[12, 13, 14, 15, 16, 17, 21, 22, 23, 24, 25, 26]
+        );
+        assert!(Image::from_opencv_mat(&roi, Encoding::GRAY8).is_err());
+    }
+}
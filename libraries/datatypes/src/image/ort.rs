@@ -0,0 +1,138 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Report, Result};
+
+use ort::value::{Tensor, TensorRef, TensorValueTypeMarker, Value};
+
+/// Returns the `(height, width)` of an `NCHW` or `NHWC` shape, with or without the batch
+/// dimension, checking the batch size and channel count, and that the height and width fit in
+/// a `u32`.
+fn image_shape(shape: &[i64], channels: usize, nchw: bool) -> Result<(u32, u32)> {
+    let dims = match shape {
+        [1, c, h, w] | [c, h, w] if nchw => Some((*c, *h, *w)),
+        [1, h, w, c] | [h, w, c] if !nchw => Some((*c, *h, *w)),
+        _ => None,
+    };
+
+    match dims.and_then(|(c, h, w)| Some((c, u32::try_from(h).ok()?, u32::try_from(w).ok()?))) {
+        Some((c, h, w)) if c == channels as i64 => Ok((h, w)),
+        _ => Err(Report::msg(format!(
+            "Tensor of shape {:?} isn't a single {} image with {} channels",
+            shape,
+            if nchw { "NCHW" } else { "NHWC" },
+            channels
+        ))),
+    }
+}
+
+impl Image<'_> {
+    /// Wraps the pixel data in a `u8` tensor of shape `[1, height, width, channels]` (NHWC),
+    /// without copying, for models taking raw images.
+    pub fn to_ort_nhwc(&self) -> Result<TensorRef<'_, u8>> {
+        let shape = [
+            1,
+            self.height as usize,
+            self.width as usize,
+            self.encoding.channels(),
+        ];
+
+        Ok(TensorRef::from_array_view((shape, self.data.as_u8()?))?)
+    }
+
+    /// Creates an `f32` tensor of shape `[1, channels, height, width]` (NCHW) with values scaled
+    /// to `[0, 1]`, the input layout of most vision models.
+    ///
+    /// Channels keep the order of the encoding: convert with [`Image::into_rgb8`] or
+    /// [`Image::into_bgr8`] first if the model expects another order.
+    pub fn to_ort_nchw(&self) -> Result<Tensor<f32>> {
+        let data = self.data.as_u8()?;
+        let channels = self.encoding.channels();
+        let plane = self.width as usize * self.height as usize;
+
+        if Some(data.len()) != self.encoding.expected_len(self.width, self.height) {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                self.encoding
+            )));
+        }
+
+        let mut nchw = vec![0.0; data.len()];
+        for (index, pixel) in data.chunks_exact(channels).enumerate() {
+            for (channel, &value) in pixel.iter().enumerate() {
+                nchw[channel * plane + index] = value as f32 / 255.0;
+            }
+        }
+
+        let shape = [1, channels, self.height as usize, self.width as usize];
+
+        Ok(Tensor::from_array((shape, nchw))?)
+    }
+
+    /// Creates an image from an `f32` model output of shape `[1, channels, height, width]` or
+    /// `[channels, height, width]` (NCHW) with values in `[0, 1]`, e.g. from a segmentation or
+    /// enhancement model. Values are clamped and rounded to `u8`.
+    pub fn from_ort_nchw<Type: TensorValueTypeMarker + ?Sized>(
+        value: &Value<Type>,
+        encoding: Encoding,
+    ) -> Result<Image<'static>> {
+        let (shape, data) = value.try_extract_tensor::<f32>()?;
+
+        let channels = encoding.channels();
+        let (height, width) = image_shape(shape, channels, true)?;
+        let plane = width as usize * height as usize;
+
+        let mut hwc = vec![0; data.len()];
+        for (channel, values) in data.chunks_exact(plane.max(1)).enumerate() {
+            for (index, &value) in values.iter().enumerate() {
+                hwc[index * channels + channel] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        }
+
+        Ok(Image {
+            data: ImageData::from_vec_u8(hwc),
+            width,
+            height,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Creates an image borrowing a `u8` model output of shape `[1, height, width, channels]` or
+    /// `[height, width, channels]` (NHWC), without copying.
+    pub fn from_ort_nhwc<Type: TensorValueTypeMarker + ?Sized>(
+        value: &'a Value<Type>,
+        encoding: Encoding,
+    ) -> Result<Self> {
+        let (shape, data) = value.try_extract_tensor::<u8>()?;
+
+        let (height, width) = image_shape(shape, encoding.channels(), false)?;
+
+        Ok(Image {
+            data: ImageData::from_slice_u8(data),
+            width,
+            height,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_image_shape() {
+        use crate::image::ort::image_shape;
+
+        assert_eq!(image_shape(&[1, 3, 2, 4], 3, true).unwrap(), (2, 4));
+        assert_eq!(image_shape(&[2, 4, 1], 1, false).unwrap(), (2, 4));
+
+        assert!(image_shape(&[2, 3, 2, 4], 3, true).is_err());
+        assert!(image_shape(&[1, 3, 2, 4], 3, false).is_err());
+        assert!(image_shape(&[1, 3, -2, 4], 3, true).is_err());
+        assert!(image_shape(&[1, 3, 1 << 32, 4], 3, true).is_err());
+    }
+}
@@ -33,7 +33,7 @@ impl Image<'_> {
     /// let image = Image::new_rgb8(data, 3, 3, Some("example")).unwrap();
     /// ```
     pub fn new_rgb8(data: Vec<u8>, width: u32, height: u32, name: Option<&str>) -> Result<Self> {
-        if data.len() != (width * height * 3) as usize {
+        if Encoding::RGB8.expected_len(width, height) != Some(data.len()) {
             return Err(Report::msg("Invalid pixel data length."));
         }
 
@@ -43,6 +43,8 @@ impl Image<'_> {
             height,
             encoding: Encoding::RGB8,
             name: name.map(|s| s.to_string()),
+            header: None,
+            capture: None,
         })
     }
 }
@@ -0,0 +1,316 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use crate::header::Header;
+use eyre::{Context, Report, Result};
+
+/// CDR encapsulation header of little-endian messages.
+const CDR_LE: [u8; 4] = [0x00, 0x01, 0x00, 0x00];
+/// CDR encapsulation header of big-endian messages.
+const CDR_BE: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+
+/// Writes a little-endian CDR message. Alignment is relative to the end of the encapsulation
+/// header.
+struct CdrWriter {
+    bytes: Vec<u8>,
+}
+
+impl CdrWriter {
+    fn new(capacity: usize) -> Self {
+        let mut bytes = Vec::with_capacity(capacity + CDR_LE.len());
+        bytes.extend_from_slice(&CDR_LE);
+
+        Self { bytes }
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let position = self.bytes.len() - CDR_LE.len();
+        let padding = position.next_multiple_of(alignment) - position;
+
+        self.bytes.resize(self.bytes.len() + padding, 0);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.align(4);
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.align(4);
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32 + 1);
+        self.bytes.extend_from_slice(value.as_bytes());
+        self.bytes.push(0);
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.u32(value.len() as u32);
+        self.bytes.extend_from_slice(value);
+    }
+}
+
+/// Reads a CDR message, borrowing byte sequences from it.
+struct CdrReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    little_endian: bool,
+}
+
+impl<'a> CdrReader<'a> {
+    fn new(bytes: &'a [u8]) -> Result<Self> {
+        let little_endian = match bytes.get(..4) {
+            Some(header) if header == CDR_LE => true,
+            Some(header) if header == CDR_BE => false,
+            _ => return Err(Report::msg("Invalid CDR encapsulation header")),
+        };
+
+        Ok(Self {
+            bytes: &bytes[4..],
+            position: 0,
+            little_endian,
+        })
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .position
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or_else(|| Report::msg("CDR message is truncated"))?;
+
+        self.position += len;
+
+        Ok(bytes)
+    }
+
+    fn align(&mut self, alignment: usize) -> Result<()> {
+        let padding = self.position.next_multiple_of(alignment) - self.position;
+
+        self.take(padding).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.align(4)?;
+
+        let bytes = self.take(4)?.try_into()?;
+
+        Ok(match self.little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(self.u32()? as i32)
+    }
+
+    fn string(&mut self) -> Result<&'a str> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+
+        // The length includes the NUL terminator.
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+
+        std::str::from_utf8(bytes).wrap_err("CDR string is not valid UTF-8")
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.u32()? as usize;
+
+        self.take(len)
+    }
+}
+
+impl Image<'_> {
+    /// Serializes the `Image` as a ROS 2 `sensor_msgs/msg/Image` in little-endian CDR, ready to
+    /// be published by a ROS 2 bridge.
+    ///
+    /// The header stamp and frame id come from [`Image::header`], the sequence number has no
+    /// ROS 2 equivalent and is dropped. The name is not serialized either.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data isn't `u8` or doesn't match the width, height and encoding,
+    /// or if the timestamp or row size overflow the fields of the ROS 2 message.
+    pub fn to_ros2_cdr(&self) -> Result<Vec<u8>> {
+        let data = self.data.as_u8()?;
+
+        if Some(data.len()) != self.encoding.expected_len(self.width, self.height) {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                self.encoding
+            )));
+        }
+
+        let (timestamp_ns, frame_id) = self
+            .header
+            .as_ref()
+            .map_or((0, ""), |header| (header.timestamp_ns, &header.frame_id));
+
+        let sec = i32::try_from(timestamp_ns / 1_000_000_000).map_err(|_| {
+            Report::msg(format!(
+                "Timestamp {} ns doesn't fit in the i32 seconds of a ROS 2 stamp.",
+                timestamp_ns
+            ))
+        })?;
+        let step = self
+            .width
+            .checked_mul(self.encoding.channels() as u32)
+            .ok_or_else(|| {
+                Report::msg(format!(
+                    "Row of a {} pixels wide {} image doesn't fit in the u32 step of a ROS 2 image.",
+                    self.width, self.encoding
+                ))
+            })?;
+
+        let mut writer = CdrWriter::new(data.len() + frame_id.len() + 64);
+
+        writer.i32(sec);
+        writer.u32((timestamp_ns % 1_000_000_000) as u32);
+        writer.string(frame_id);
+        writer.u32(self.height);
+        writer.u32(self.width);
+        writer.string(match self.encoding {
+            Encoding::RGB8 => "rgb8",
+            Encoding::BGR8 => "bgr8",
+            Encoding::GRAY8 => "mono8",
+        });
+        writer.u8(0);
+        writer.u32(step);
+        writer.bytes(data);
+
+        Ok(writer.bytes)
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Deserializes a ROS 2 `sensor_msgs/msg/Image` from CDR.
+    ///
+    /// The pixel data is borrowed from `bytes` when its rows aren't padded, and copied without
+    /// the padding otherwise. A header is set unless both the stamp and the frame id are empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message is truncated or if its encoding has no matching
+    /// `Encoding`, e.g. `yuv422`.
+    pub fn from_ros2_cdr(bytes: &'a [u8]) -> Result<Self> {
+        let mut reader = CdrReader::new(bytes)?;
+
+        let sec = reader.i32()?;
+        let nanosec = reader.u32()?;
+        let frame_id = reader.string()?;
+        let height = reader.u32()?;
+        let width = reader.u32()?;
+        let encoding = reader.string()?.parse::<Encoding>()?;
+        let _is_bigendian = reader.u8()?;
+        let step = reader.u32()? as usize;
+        let data = reader.bytes()?;
+
+        let header = match (sec, nanosec, frame_id) {
+            (0, 0, "") => None,
+            _ => Some(Header {
+                timestamp_ns: sec.max(0) as u64 * 1_000_000_000 + nanosec as u64,
+                frame_id: frame_id.to_string(),
+                sequence: 0,
+            }),
+        };
+
+        let row_len = width as usize * encoding.channels();
+        if step != row_len {
+            let image = Image::builder()
+                .width(width)
+                .height(height)
+                .encoding(encoding)
+                .stride(step)
+                .data(data.to_vec())
+                .build()?;
+
+            return Ok(Image { header, ..image });
+        }
+
+        let image = Image {
+            data: ImageData::from_slice_u8(data),
+            width,
+            height,
+            encoding,
+            name: None,
+            header,
+            capture: None,
+        };
+
+        if let Some(issue) = image.validate().into_iter().next() {
+            return Err(Report::msg(format!("Invalid ROS 2 image: {}", issue)));
+        }
+
+        Ok(image)
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_ros2_cdr_round_trip() {
+        use crate::header::Header;
+        use crate::image::Image;
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let header = Header::new(1_700_000_000_123_456_789, "camera_front", 0);
+
+        let image = Image::new_bgr8(flat_image.clone(), 3, 3, None)
+            .unwrap()
+            .with_header(header.clone());
+
+        let bytes = image.to_ros2_cdr().unwrap();
+        let image = Image::from_ros2_cdr(&bytes).unwrap();
+
+        assert_eq!(image.encoding, crate::image::Encoding::BGR8);
+        assert_eq!(image.header, Some(header));
+        assert_eq!(image.data.as_u8().unwrap(), &flat_image);
+        assert!(image.is_borrowed());
+
+        assert!(Image::from_ros2_cdr(&bytes[..bytes.len() - 1]).is_err());
+
+        let image = Image::new_gray8(vec![0; 4], 2, 2, None)
+            .unwrap()
+            .with_header(Header::new(u64::MAX, "camera_front", 0));
+
+        assert!(image.to_ros2_cdr().is_err());
+
+        let image = Image::new_rgb8(vec![], u32::MAX / 2, 0, None).unwrap();
+
+        assert!(image.to_ros2_cdr().is_err());
+    }
+
+    #[test]
+    fn test_ros2_cdr_padded_rows() {
+        use crate::image::Image;
+
+        // A 2x2 mono8 image with rows padded to 4 bytes, as written by a ROS 2 camera driver.
+        let mut bytes = vec![0x00, 0x01, 0x00, 0x00];
+        bytes.extend_from_slice(&[0; 8]); // stamp
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0, 0, 0]); // empty frame id and padding
+        bytes.extend_from_slice(&2u32.to_le_bytes()); // height
+        bytes.extend_from_slice(&2u32.to_le_bytes()); // width
+        bytes.extend_from_slice(&6u32.to_le_bytes());
+        bytes.extend_from_slice(b"mono8\0");
+        bytes.push(0); // is_bigendian
+        bytes.push(0); // padding
+        bytes.extend_from_slice(&4u32.to_le_bytes()); // step
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 0, 0, 3, 4, 0, 0]);
+
+        let image = Image::from_ros2_cdr(&bytes).unwrap();
+
+        assert_eq!(image.header, None);
+        assert_eq!(image.data.as_u8().unwrap(), &[1, 2, 3, 4]);
+    }
+}
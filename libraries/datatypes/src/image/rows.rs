@@ -0,0 +1,241 @@
+use super::Image;
+use eyre::{Report, Result};
+
+/// A band of consecutive rows of an `Image`, handed out by [`Image::process_rows_mut`].
+#[derive(Debug)]
+pub struct RowBand<'b> {
+    data: &'b mut [u8],
+    first_row: u32,
+    width: u32,
+    stride: usize,
+}
+
+impl RowBand<'_> {
+    /// Index of the first row of the band in the image.
+    pub fn first_row(&self) -> u32 {
+        self.first_row
+    }
+
+    /// Number of rows in the band. The last band of an image may be shorter than the others.
+    pub fn height(&self) -> u32 {
+        (self.data.len() / self.stride) as u32
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Number of bytes between the start of two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The pixel data of the band, row after row.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    /// Returns the row at index `row` of the band, or `None` if it is outside of the band.
+    pub fn row_mut(&mut self, row: u32) -> Option<&mut [u8]> {
+        self.data.chunks_exact_mut(self.stride).nth(row as usize)
+    }
+
+    /// Iterates over the rows of the band.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        self.data.chunks_exact_mut(self.stride)
+    }
+}
+
+/// Number of bytes in a band of `chunk_height` rows. Bands taller than the image hold the whole
+/// image, and an image without columns has no bands, as it has no pixel data.
+fn band_len(stride: usize, chunk_height: u32) -> usize {
+    stride.saturating_mul(chunk_height as usize).max(1)
+}
+
+impl Image<'_> {
+    /// Splits the pixel data into bands of `chunk_height` rows and calls `process` on each of
+    /// them in order, so that filters can work band by band without any stride math.
+    ///
+    /// Borrowed data is copied first, subject to the copy policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `chunk_height` is zero, if the data isn't `u8`, or if its length
+    /// doesn't match the width, height and encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fastformat_datatypes::image::Image;
+    ///
+    /// let mut image = Image::new_gray8(vec![0; 16], 4, 4, None).unwrap();
+    ///
+    /// image
+    ///     .process_rows_mut(2, |mut band| {
+    ///         let value = band.first_row() as u8;
+    ///         band.data_mut().fill(value);
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&image.data.as_u8().unwrap()[..], &[[0; 8], [2; 8]].concat()[..]);
+    /// ```
+    pub fn process_rows_mut(
+        &mut self,
+        chunk_height: u32,
+        mut process: impl FnMut(RowBand<'_>),
+    ) -> Result<()> {
+        let (width, stride) = (self.width, self.stride());
+        let data = self.rows_data_mut(chunk_height)?;
+
+        for (index, data) in data.chunks_mut(band_len(stride, chunk_height)).enumerate() {
+            process(RowBand {
+                data,
+                first_row: index as u32 * chunk_height,
+                width,
+                stride,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Like [`Image::process_rows_mut`], but processes the bands in parallel on the rayon thread
+    /// pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_process_rows_mut(
+        &mut self,
+        chunk_height: u32,
+        process: impl Fn(RowBand<'_>) + Send + Sync,
+    ) -> Result<()> {
+        use rayon::prelude::*;
+
+        let (width, stride) = (self.width, self.stride());
+        let data = self.rows_data_mut(chunk_height)?;
+
+        data.par_chunks_mut(band_len(stride, chunk_height))
+            .enumerate()
+            .for_each(|(index, data)| {
+                process(RowBand {
+                    data,
+                    first_row: index as u32 * chunk_height,
+                    width,
+                    stride,
+                })
+            });
+
+        Ok(())
+    }
+
+    /// Number of bytes in a row of pixels.
+    fn stride(&self) -> usize {
+        self.width as usize * self.encoding.channels()
+    }
+
+    /// Returns the pixel data for row processing, checking its length first.
+    fn rows_data_mut(&mut self, chunk_height: u32) -> Result<&mut [u8]> {
+        if chunk_height == 0 {
+            return Err(Report::msg("Can't process rows in bands of 0 rows"));
+        }
+
+        let expected_len = self.encoding.expected_len(self.width, self.height);
+        let data = self.data.as_mut_u8()?;
+
+        if Some(data.len()) != expected_len {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                self.encoding
+            )));
+        }
+
+        Ok(data)
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_process_rows_mut() {
+        use crate::image::Image;
+
+        let mut image = Image::new_rgb8(vec![0; 5 * 2 * 3], 2, 5, None).unwrap();
+
+        let mut bands = Vec::new();
+        image
+            .process_rows_mut(2, |mut band| {
+                bands.push((band.first_row(), band.height()));
+
+                for (row, data) in band.rows_mut().enumerate() {
+                    data.fill(row as u8 + 1);
+                }
+            })
+            .unwrap();
+
+        assert_eq!(bands, vec![(0, 2), (2, 2), (4, 1)]);
+        assert_eq!(
+            image.data.as_u8().unwrap(),
+            &[[1; 6], [2; 6], [1; 6], [2; 6], [1; 6]].concat()
+        );
+        assert!(image.process_rows_mut(0, |_| {}).is_err());
+
+        let mut bands = Vec::new();
+        image
+            .process_rows_mut(u32::MAX, |band| {
+                bands.push((band.first_row(), band.height()))
+            })
+            .unwrap();
+
+        assert_eq!(bands, vec![(0, 5)]);
+    }
+
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn test_denied_process_rows_mut_keeps_data() {
+        use crate::image::{data::ImageData, Encoding, Image};
+        use fastformat_converter::policy::{self, CopyPolicy};
+
+        let pixels = (0..2 * 1024 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+        let buffer = arrow::buffer::Buffer::from_vec(pixels.clone());
+
+        // A buffer shared with another array can only be mutated by copying it.
+        let mut image = Image {
+            data: ImageData::ArrowBuffer(buffer.clone()),
+            width: 1024,
+            height: 2048,
+            encoding: Encoding::GRAY8,
+            name: None,
+            header: None,
+            capture: None,
+        };
+
+        let result = {
+            let _policy = policy::scoped(CopyPolicy::UpTo(1024 * 1024));
+            image.process_rows_mut(16, |_| {})
+        };
+
+        assert!(result.is_err());
+        assert_eq!(image.data.as_u8().unwrap(), &pixels[..]);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_process_rows_mut() {
+        use crate::image::Image;
+
+        let mut image = Image::new_gray8(vec![0; 64 * 64], 64, 64, None).unwrap();
+
+        image
+            .par_process_rows_mut(8, |mut band| {
+                let first_row = band.first_row();
+
+                for (row, data) in band.rows_mut().enumerate() {
+                    data.fill((first_row as usize + row) as u8);
+                }
+            })
+            .unwrap();
+
+        let data = image.data.as_u8().unwrap();
+        assert!(data
+            .chunks_exact(64)
+            .enumerate()
+            .all(|(row, data)| data.iter().all(|&value| value as usize == row)));
+    }
+}
@@ -0,0 +1,157 @@
+use super::{capture::CaptureMetadata, encoding::Encoding, Image};
+use crate::header::Header;
+
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Pixel data, serialized as a byte string so that binary formats (e.g. CBOR) store it raw.
+struct Bytes<'b>(&'b [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Owned counterpart of [`Bytes`]. Self-describing formats without byte strings (e.g. JSON)
+/// give back a sequence of numbers instead, which is accepted as well.
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("pixel data as bytes")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, data: &[u8]) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(data.to_vec()))
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, data: Vec<u8>) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(data))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<ByteBuf, A::Error> {
+                let mut data = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(value) = seq.next_element()? {
+                    data.push(value);
+                }
+
+                Ok(ByteBuf(data))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+#[derive(Serialize)]
+struct ImageRef<'b> {
+    width: u32,
+    height: u32,
+    encoding: Encoding,
+    name: &'b Option<String>,
+    header: &'b Option<Header>,
+    capture: &'b Option<CaptureMetadata>,
+    data: Bytes<'b>,
+}
+
+#[derive(Deserialize)]
+struct ImageOwned {
+    width: u32,
+    height: u32,
+    encoding: Encoding,
+    name: Option<String>,
+    header: Option<Header>,
+    capture: Option<CaptureMetadata>,
+    data: ByteBuf,
+}
+
+impl Serialize for Image<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let data = match self.encoding {
+            Encoding::RGB8 | Encoding::BGR8 | Encoding::GRAY8 => {
+                self.data.as_u8().map_err(S::Error::custom)?
+            }
+        };
+
+        ImageRef {
+            width: self.width,
+            height: self.height,
+            encoding: self.encoding,
+            name: &self.name,
+            header: &self.header,
+            capture: &self.capture,
+            data: Bytes(data),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Image<'_> {
+    /// Deserializes into an owned `Image`, validated like [`Image::builder`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let image = ImageOwned::deserialize(deserializer)?;
+
+        let builder = Image::builder()
+            .width(image.width)
+            .height(image.height)
+            .encoding(image.encoding)
+            .data(image.data.0);
+
+        let builder = match image.name {
+            Some(name) => builder.name(&name),
+            None => builder,
+        };
+        let builder = match image.header {
+            Some(header) => builder.header(header),
+            None => builder,
+        };
+        let builder = match image.capture {
+            Some(capture) => builder.capture(capture),
+            None => builder,
+        };
+
+        builder.build().map_err(D::Error::custom)
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_serde_json_round_trip() {
+        use crate::header::Header;
+        use crate::image::{CaptureMetadata, Image};
+
+        let image = Image::new_rgb8((0..27).collect(), 3, 3, Some("camera.test"))
+            .unwrap()
+            .with_header(Header::new(1, "camera_front", 2))
+            .with_capture(CaptureMetadata {
+                exposure_time_ns: Some(10_000_000),
+                iso: Some(400),
+                ..Default::default()
+            });
+
+        let json = serde_json::to_string(&image).unwrap();
+        let final_image = serde_json::from_str::<Image>(&json).unwrap();
+
+        assert_eq!(final_image.encoding, image.encoding);
+        assert_eq!(final_image.name, image.name);
+        assert_eq!(final_image.header, image.header);
+        assert_eq!(final_image.capture, image.capture);
+        assert_eq!(
+            final_image.data.as_u8().unwrap(),
+            image.data.as_u8().unwrap()
+        );
+
+        let json = json.replace("\"width\":3", "\"width\":4");
+
+        assert!(serde_json::from_str::<Image>(&json).is_err());
+    }
+}
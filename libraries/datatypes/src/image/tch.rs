@@ -0,0 +1,124 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Context, Report, Result};
+
+use fastformat_converter::{accounting, policy};
+use tch::{Device, Kind, Tensor};
+
+impl Image<'_> {
+    fn tch_size(&self) -> Result<[i64; 3]> {
+        let len = self.data.as_u8()?.len();
+
+        if Some(len) != self.encoding.expected_len(self.width, self.height) {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                self.encoding
+            )));
+        }
+
+        Ok([
+            self.height as i64,
+            self.width as i64,
+            self.encoding.channels() as i64,
+        ])
+    }
+
+    /// Copies the image into a `Uint8` libtorch tensor of shape `[height, width, channels]` on the
+    /// CPU.
+    ///
+    /// See [`Image::to_tch_no_copy`] to share the pixel data instead.
+    pub fn to_tch(&self) -> Result<Tensor> {
+        let size = self.tch_size()?;
+        let data = self.data.as_u8()?;
+
+        policy::check("Image::to_tch", data.len())?;
+        accounting::record("Image::to_tch", data.len(), true);
+
+        Tensor::f_from_data_size(data, &size, Kind::Uint8).wrap_err("Failed to create tch tensor")
+    }
+
+    /// Wraps the pixel data in a `Uint8` libtorch tensor of shape `[height, width, channels]`,
+    /// without copying it.
+    ///
+    /// # Safety
+    ///
+    /// libtorch tensors don't track the lifetime of the data they wrap: the tensor, and every
+    /// view of it, must be dropped before the image, and must not be resized.
+    pub unsafe fn to_tch_no_copy(&self) -> Result<Tensor> {
+        let size = self.tch_size()?;
+        let data = self.data.as_u8()?;
+
+        accounting::record("Image::to_tch_no_copy", data.len(), false);
+
+        // SAFETY: the data is initialized and `size` matches its length, the caller guarantees
+        // that it outlives the tensor.
+        unsafe { Tensor::f_from_blob(data.as_ptr(), &size, &[], Kind::Uint8, Device::Cpu) }
+            .wrap_err("Failed to create tch tensor")
+    }
+
+    /// Copies a `Uint8` libtorch tensor of shape `[height, width, channels]` into an image, moving
+    /// it to the CPU first if needed. Non-contiguous tensors, e.g. permuted from `CHW`, are
+    /// made contiguous.
+    pub fn from_tch(tensor: &Tensor, encoding: Encoding) -> Result<Image<'static>> {
+        let kind = tensor.f_kind()?;
+
+        let (height, width) = match tensor.size()[..] {
+            [height, width, channels]
+                if kind == Kind::Uint8 && channels == encoding.channels() as i64 =>
+            {
+                (height as u32, width as u32)
+            }
+            ref size => {
+                return Err(Report::msg(format!(
+                    "tch tensor of kind {:?} and shape {:?} doesn't match {} encoding",
+                    kind, size, encoding
+                )))
+            }
+        };
+
+        let numel = tensor.numel();
+
+        policy::check("Image::from_tch", numel)?;
+        accounting::record("Image::from_tch", numel, true);
+
+        let mut data = vec![0; numel];
+        tensor
+            .f_to_device(Device::Cpu)?
+            .f_contiguous()?
+            .f_copy_data(&mut data, numel)?;
+
+        Ok(Image {
+            data: ImageData::from_vec_u8(data),
+            width,
+            height,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_tch_round_trip() {
+        use crate::image::{Encoding, Image};
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let image = Image::new_bgr8(flat_image.clone(), 3, 3, None).unwrap();
+
+        let tensor = image.to_tch().unwrap();
+        assert_eq!(tensor.size(), [3, 3, 3]);
+
+        // SAFETY: the tensor is dropped at the end of the scope, before the image.
+        let shared = unsafe { image.to_tch_no_copy() }.unwrap();
+        assert_eq!(
+            shared.data_ptr() as *const u8,
+            image.data.as_u8().unwrap().as_ptr()
+        );
+
+        let image = Image::from_tch(&tensor, Encoding::BGR8).unwrap();
+        assert_eq!(image.data.as_u8().unwrap(), &flat_image);
+
+        assert!(Image::from_tch(&tensor, Encoding::GRAY8).is_err());
+    }
+}
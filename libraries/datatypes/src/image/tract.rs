@@ -0,0 +1,121 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Context, Report, Result};
+
+use fastformat_converter::{accounting, policy};
+use tract_data::{
+    internal::tract_ndarray::{Array3, Axis},
+    prelude::Tensor,
+};
+
+impl Image<'_> {
+    fn to_tract_array(&self) -> Result<Array3<u8>> {
+        let data = self.data.as_u8()?;
+        let shape = (
+            self.height as usize,
+            self.width as usize,
+            self.encoding.channels(),
+        );
+
+        policy::check("Image::to_tract", data.len())?;
+        accounting::record("Image::to_tract", data.len(), true);
+
+        Array3::from_shape_vec(shape, data.to_vec()).wrap_err(format!(
+            "Failed to create tract tensor: width, height and {} encoding doesn't match data length.",
+            self.encoding
+        ))
+    }
+
+    /// Copies the image into a `u8` tract tensor of shape `[height, width, channels]`.
+    ///
+    /// tract tensors own their aligned storage, so the pixel data is always copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fastformat_datatypes::image::Image;
+    ///
+    /// let image = Image::new_rgb8(vec![0; 27], 3, 3, None).unwrap();
+    /// let tensor = image.to_tract().unwrap();
+    ///
+    /// assert_eq!(tensor.shape(), [3, 3, 3]);
+    /// ```
+    pub fn to_tract(&self) -> Result<Tensor> {
+        Ok(self.to_tract_array()?.into())
+    }
+
+    /// Creates an `f32` tract tensor of shape `[1, channels, height, width]` (NCHW) with values
+    /// scaled to `[0, 1]`, the input layout of most vision models.
+    ///
+    /// Channels keep the order of the encoding: convert with [`Image::into_rgb8`] or
+    /// [`Image::into_bgr8`] first if the model expects another order.
+    pub fn to_tract_nchw(&self) -> Result<Tensor> {
+        let nchw = self
+            .to_tract_array()?
+            .permuted_axes([2, 0, 1])
+            .mapv(|value| value as f32 / 255.0)
+            .insert_axis(Axis(0));
+
+        Ok(nchw.into())
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Creates an image borrowing a `u8` tract tensor of shape `[height, width, channels]` or
+    /// `[1, height, width, channels]`, e.g. a model output, without copying.
+    pub fn from_tract(tensor: &'a Tensor, encoding: Encoding) -> Result<Self> {
+        let (height, width) = match tensor.shape() {
+            [1, height, width, channels] | [height, width, channels]
+                if *channels == encoding.channels() =>
+            {
+                (*height as u32, *width as u32)
+            }
+            shape => {
+                return Err(Report::msg(format!(
+                    "tract tensor of shape {:?} isn't a single {} image",
+                    shape, encoding
+                )))
+            }
+        };
+
+        let data = tensor
+            .try_as_plain_ram()
+            .and_then(|view| view.as_slice::<u8>())
+            .map_err(Report::msg)?;
+
+        Ok(Image {
+            data: ImageData::from_slice_u8(data),
+            width,
+            height,
+            encoding,
+            name: None,
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_tract_round_trip() {
+        use crate::image::{Encoding, Image};
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let image = Image::new_bgr8(flat_image.clone(), 3, 3, None).unwrap();
+
+        let tensor = image.to_tract().unwrap();
+        let image = Image::from_tract(&tensor, Encoding::BGR8).unwrap();
+
+        assert!(image.is_borrowed());
+        assert_eq!(image.data.as_u8().unwrap(), &flat_image);
+
+        assert!(Image::from_tract(&tensor, Encoding::GRAY8).is_err());
+
+        let nchw = image.to_tract_nchw().unwrap();
+        let nchw = nchw.try_as_plain_ram().unwrap().as_slice::<f32>().unwrap();
+
+        // Blue plane first, then green and red.
+        assert_eq!(nchw[1], 3.0 / 255.0);
+        assert_eq!(nchw[9], 1.0 / 255.0);
+        assert!(Image::from_tract(&image.to_tract_nchw().unwrap(), Encoding::BGR8).is_err());
+    }
+}
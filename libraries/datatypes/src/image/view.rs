@@ -0,0 +1,167 @@
+use super::{data::ImageData, Image};
+use eyre::{Report, Result};
+
+/// A primitive type pixel data can be stored as.
+pub trait Primitive: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    fn slice<'b>(data: &'b ImageData) -> Result<&'b [Self]>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $as_slice:ident),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Primitive for $ty {
+                fn slice<'b>(data: &'b ImageData) -> Result<&'b [Self]> {
+                    data.$as_slice()
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(
+    u8 => as_u8,
+    u16 => as_u16,
+    f32 => as_f32,
+    i16 => as_i16,
+    i32 => as_i32,
+    f64 => as_f64,
+    half::f16 => as_f16,
+);
+
+/// A read-only view of the pixels of an `Image`, typed by the primitive type `T` of its data
+/// and its number of channels `C`, e.g. `ImageView<u8, 3>` for RGB8 and BGR8 images.
+///
+/// The type and channels are checked once when creating the view with [`Image::view`], so
+/// pixel accessors don't need to match on the encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageView<'b, T, const C: usize> {
+    data: &'b [T],
+    width: u32,
+    height: u32,
+}
+
+impl<'b, T: Primitive, const C: usize> ImageView<'b, T, C> {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The pixel data, row after row.
+    pub fn data(&self) -> &'b [T] {
+        self.data
+    }
+
+    /// Returns the channels of the pixel at column `x` and row `y`, or `None` if it is outside
+    /// of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&'b [T; C]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let start = (y as usize * self.width as usize + x as usize) * C;
+
+        self.data.get(start..start + C)?.try_into().ok()
+    }
+
+    /// Iterates over the rows of the image. An image without columns has no pixel data, and so
+    /// no rows.
+    pub fn rows(&self) -> impl Iterator<Item = &'b [T]> {
+        // `chunks_exact` panics on a zero chunk size, and an empty `data` has no chunks anyway.
+        self.data.chunks_exact((self.width as usize * C).max(1))
+    }
+
+    /// Iterates over the pixels of the image, row after row.
+    pub fn pixels(&self) -> impl Iterator<Item = &'b [T; C]> {
+        self.data
+            .chunks_exact(C)
+            .filter_map(|pixel| pixel.try_into().ok())
+    }
+}
+
+impl Image<'_> {
+    /// Returns a typed view of the pixels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding doesn't have `C` channels, if the data isn't stored as
+    /// `T`, or if its length doesn't match the width, height and encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fastformat_datatypes::image::Image;
+    ///
+    /// let image = Image::new_rgb8(vec![0; 27], 3, 3, None).unwrap();
+    /// let view = image.view::<u8, 3>().unwrap();
+    ///
+    /// assert_eq!(view.pixel(2, 2), Some(&[0, 0, 0]));
+    /// assert!(image.view::<u8, 1>().is_err());
+    /// ```
+    pub fn view<T: Primitive, const C: usize>(&self) -> Result<ImageView<'_, T, C>> {
+        if self.encoding.channels() != C {
+            return Err(Report::msg(format!(
+                "{} encoding has {} channels, not {}",
+                self.encoding,
+                self.encoding.channels(),
+                C
+            )));
+        }
+
+        let data = T::slice(&self.data)?;
+
+        if Some(data.len()) != self.encoding.expected_len(self.width, self.height) {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                self.encoding
+            )));
+        }
+
+        Ok(ImageView {
+            data,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_view() {
+        use crate::image::Image;
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let image = Image::new_bgr8(flat_image, 3, 3, None).unwrap();
+
+        let view = image.view::<u8, 3>().unwrap();
+
+        assert_eq!(view.pixel(1, 0), Some(&[3, 4, 5]));
+        assert_eq!(view.pixel(0, 2), Some(&[18, 19, 20]));
+        assert_eq!(view.pixel(3, 0), None);
+        assert_eq!(view.rows().count(), 3);
+        assert_eq!(view.pixels().count(), 9);
+
+        assert!(image.view::<f32, 3>().is_err());
+        assert!(image.view::<u8, 1>().is_err());
+
+        let image = Image::new_gray8(vec![7; 4], 2, 2, None).unwrap();
+        let view = image.view::<u8, 1>().unwrap();
+
+        assert!(view.pixels().all(|&[value]| value == 7));
+
+        let image = Image::new_gray8(vec![], 0, 2, None).unwrap();
+        let view = image.view::<u8, 1>().unwrap();
+
+        assert_eq!(view.rows().count(), 0);
+        assert_eq!(view.pixels().count(), 0);
+    }
+}
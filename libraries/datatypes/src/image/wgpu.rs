@@ -0,0 +1,282 @@
+use super::{data::ImageData, encoding::Encoding, Image};
+use eyre::{Context, Report, Result};
+
+use std::borrow::Cow;
+
+/// Returns the `wgpu::TextureFormat` an image of the given encoding is uploaded as.
+///
+/// There are no 3-channel texture formats, so RGB8 and BGR8 images are stored in 4-channel
+/// textures with an opaque alpha channel.
+pub fn texture_format(encoding: Encoding) -> wgpu::TextureFormat {
+    match encoding {
+        Encoding::RGB8 => wgpu::TextureFormat::Rgba8Unorm,
+        Encoding::BGR8 => wgpu::TextureFormat::Bgra8Unorm,
+        Encoding::GRAY8 => wgpu::TextureFormat::R8Unorm,
+    }
+}
+
+/// Rounds a row size up to the 256-byte alignment required for texture to buffer copies.
+fn padded_bytes_per_row(bytes_per_row: u32) -> u32 {
+    bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT
+}
+
+/// Adds an opaque alpha channel after every 3-byte pixel.
+fn expand_to_4_channels(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(3)
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], u8::MAX])
+        .collect()
+}
+
+impl Image<'_> {
+    /// Uploads the `Image` into a new `wgpu::Texture`.
+    ///
+    /// The texture format is given by [`texture_format`]. GRAY8 data is uploaded as is, while
+    /// RGB8 and BGR8 data is first expanded to 4 channels. The texture can be sampled, and copied
+    /// from and to, so it can be read back with [`Image::from_wgpu_texture`].
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device that creates the texture.
+    /// * `queue` - The queue used to write the pixel data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the texture if successful, or an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel data doesn't match the width, height and encoding, or if
+    /// the image is too large.
+    pub fn to_wgpu_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<wgpu::Texture> {
+        let data = self.data.as_u8()?;
+
+        let len = self
+            .encoding
+            .expected_len(self.width, self.height)
+            .ok_or_else(|| {
+                Report::msg(format!(
+                    "A {}x{} {} image doesn't fit in memory",
+                    self.width, self.height, self.encoding
+                ))
+            })?;
+
+        if data.len() != len {
+            return Err(Report::msg(format!(
+                "Width, height and {} encoding doesn't match data length.",
+                self.encoding
+            )));
+        }
+
+        let texels = match self.encoding {
+            Encoding::RGB8 | Encoding::BGR8 => Cow::Owned(expand_to_4_channels(data)),
+            Encoding::GRAY8 => Cow::Borrowed(data),
+        };
+
+        let format = texture_format(self.encoding);
+        let bytes_per_pixel = format.block_copy_size(None).unwrap_or(1);
+        let bytes_per_row = self.width.checked_mul(bytes_per_pixel).ok_or_else(|| {
+            Report::msg(format!(
+                "A row of {} pixels doesn't fit in a texture",
+                self.width
+            ))
+        })?;
+
+        let size = wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: self.name.as_deref(),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            texture.as_image_copy(),
+            &texels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(self.height),
+            },
+            size,
+        );
+
+        Ok(texture)
+    }
+}
+
+impl Image<'_> {
+    /// Reads a `wgpu::Texture` back into a new `Image`.
+    ///
+    /// The texture is copied into a staging buffer with rows padded to 256 bytes, then the padding
+    /// (and the alpha channel of 4-channel textures) is stripped. This call blocks until the GPU
+    /// has finished the copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device that owns the texture.
+    /// * `queue` - The queue used to submit the copy.
+    /// * `texture` - A 2D texture created with `COPY_SRC` usage.
+    /// * `name` - An optional string slice representing the name of the image.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Image` if successful, or an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the texture format has no matching encoding, or if mapping the staging
+    /// buffer fails.
+    pub fn from_wgpu_texture(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+        name: Option<&str>,
+    ) -> Result<Self> {
+        let encoding = match texture.format() {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Encoding::RGB8,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => Encoding::BGR8,
+            wgpu::TextureFormat::R8Unorm => Encoding::GRAY8,
+            format => {
+                return Err(Report::msg(format!(
+                    "Texture format {:?} has no matching Image encoding.",
+                    format
+                )))
+            }
+        };
+
+        let size = texture.size();
+        let (width, height) = (size.width, size.height);
+
+        let bytes_per_pixel = texture.format().block_copy_size(None).unwrap_or(1);
+        let bytes_per_row = width * bytes_per_pixel;
+        let padded_bytes_per_row = padded_bytes_per_row(bytes_per_row);
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: name,
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        device
+            .poll(wgpu::PollType::Wait)
+            .wrap_err("Failed to wait for the texture copy")?;
+        receiver
+            .recv()
+            .wrap_err("Texture read back was cancelled")?
+            .wrap_err("Failed to map the texture read back buffer")?;
+
+        let data = {
+            let mapped = slice.get_mapped_range();
+
+            let rows = mapped
+                .chunks_exact(padded_bytes_per_row as usize)
+                .map(|row| &row[..bytes_per_row as usize]);
+
+            match encoding {
+                Encoding::RGB8 | Encoding::BGR8 => rows
+                    .flat_map(|row| row.chunks_exact(4).flat_map(|pixel| &pixel[..3]))
+                    .copied()
+                    .collect::<Vec<u8>>(),
+                Encoding::GRAY8 => rows.flatten().copied().collect::<Vec<u8>>(),
+            }
+        };
+        buffer.unmap();
+
+        Ok(Image {
+            data: ImageData::from_vec_u8(data),
+            width,
+            height,
+            encoding,
+            name: name.map(|s| s.to_string()),
+            header: None,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_padded_bytes_per_row() {
+        use super::padded_bytes_per_row;
+
+        assert_eq!(padded_bytes_per_row(0), 0);
+        assert_eq!(padded_bytes_per_row(12), 256);
+        assert_eq!(padded_bytes_per_row(256), 256);
+        assert_eq!(padded_bytes_per_row(1280 * 4), 1280 * 4);
+        assert_eq!(padded_bytes_per_row(1281 * 4), 5376);
+    }
+
+    #[test]
+    fn test_wgpu_texture_round_trip() {
+        use crate::image::Image;
+
+        let instance = wgpu::Instance::default();
+
+        // Skip the round trip on machines without any adapter, e.g. CI runners.
+        let Ok(adapter) =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+        else {
+            return;
+        };
+
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let image = Image::new_rgb8(flat_image.clone(), 3, 3, Some("camera.test")).unwrap();
+
+        let texture = image.to_wgpu_texture(&device, &queue).unwrap();
+        let final_image =
+            Image::from_wgpu_texture(&device, &queue, &texture, Some("camera.test")).unwrap();
+
+        assert_eq!(final_image.encoding, crate::image::Encoding::RGB8);
+        assert_eq!(final_image.data.as_u8().unwrap(), &flat_image);
+
+        // `width * height * channels` overflows `u32`.
+        let mut image = Image::new_rgb8(flat_image, 3, 3, None).unwrap();
+        image.width = 65536;
+        image.height = 65536;
+        assert!(image.to_wgpu_texture(&device, &queue).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::header::Header;
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "pyo3")]
+mod python;
+
+/// A value of a [`KeyValueMap`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl Value {
+    /// Name of the type of the value, as serialized in the `"types"` field.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Int(_) => "int",
+            Self::Float(_) => "float",
+            Self::String(_) => "string",
+            Self::Bytes(_) => "bytes",
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+/// String keys with typed values, for configuration, status and diagnostics messages that
+/// don't deserve a datatype of their own.
+///
+/// Keys are kept sorted, so that two maps with the same entries serialize the same way.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyValueMap {
+    pub entries: BTreeMap<String, Value>,
+
+    pub header: Option<Header>,
+}
+
+impl KeyValueMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches a `Header` to the map, e.g. to timestamp a status report.
+    pub fn with_header(mut self, header: Header) -> Self {
+        self.header = Some(header);
+
+        self
+    }
+
+    /// Adds an entry to the map, e.g. `map.with("fps", 30_i64)`.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.insert(key, value);
+
+        self
+    }
+
+    /// Inserts an entry, returning the previous value of `key` if any.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<Value>) -> Option<Value> {
+        self.entries.insert(key.into(), value.into())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.get(key)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.entries.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for KeyValueMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(entries: I) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+            header: None,
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_key_value_map() {
+        use crate::key_value_map::{KeyValueMap, Value};
+
+        let mut map = KeyValueMap::new()
+            .with("fps", 30_i64)
+            .with("exposure_s", 0.01)
+            .with("camera", "front");
+
+        assert_eq!(map.get("fps"), Some(&Value::Int(30)));
+        assert_eq!(map.get("camera").and_then(Value::as_str), Some("front"));
+        assert_eq!(map.get("fps").and_then(Value::as_f64), None);
+
+        assert_eq!(
+            map.insert("fps", 60_i64),
+            Some(Value::Int(30)),
+            "inserting an existing key replaces its value"
+        );
+        assert_eq!(
+            map.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec!["camera", "exposure_s", "fps"]
+        );
+
+        let other = [("camera", Value::from("front"))]
+            .into_iter()
+            .collect::<KeyValueMap>();
+        map.remove("fps");
+        map.remove("exposure_s");
+
+        assert_eq!(map, other);
+    }
+}
@@ -0,0 +1,233 @@
+use super::{KeyValueMap, Value};
+use crate::header;
+use eyre::{OptionExt, Report, Result};
+use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+
+use std::sync::OnceLock;
+
+/// Arrow layout of a `KeyValueMap`, built once and shared by every `into_arrow` call.
+///
+/// `"keys"` and `"types"` have one entry per key. Values are stored in the array of their type,
+/// in key order, bytes concatenated and split by `"bytes_offsets"`.
+fn union_fields() -> arrow::datatypes::UnionFields {
+    use arrow::datatypes::DataType::{Float64, Int64, UInt64, UInt8, Utf8};
+
+    static UNION_FIELDS: OnceLock<arrow::datatypes::UnionFields> = OnceLock::new();
+
+    UNION_FIELDS
+        .get_or_init(|| {
+            FastFormatArrowBuilder::schema(
+                &[
+                    &[("keys", Utf8, false), ("types", Utf8, false)][..],
+                    &header::fields(),
+                    &[
+                        ("ints", Int64, false),
+                        ("floats", Float64, false),
+                        ("strings", Utf8, false),
+                        ("bytes", UInt8, false),
+                        ("bytes_offsets", UInt64, false),
+                    ],
+                ]
+                .concat(),
+            )
+        })
+        .clone()
+}
+
+impl KeyValueMap {
+    /// Extracts raw data from an Arrow `ArrayData` produced by [`KeyValueMap::into_arrow`].
+    pub fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        use arrow::datatypes::{Float64Type, Int64Type, UInt64Type, UInt8Type};
+
+        let raw_data = FastFormatArrowRawData::new(array_data)?
+            .load_utf("keys")?
+            .load_utf("types")?
+            .load_primitive::<Int64Type>("ints")?
+            .load_primitive::<Float64Type>("floats")?
+            .load_utf("strings")?
+            .load_primitive::<UInt8Type>("bytes")?
+            .load_primitive::<UInt64Type>("bytes_offsets")?;
+
+        header::load(raw_data)
+    }
+
+    /// Reads a `KeyValueMap` from `FastFormatArrowRawData`. The entries are always copied: maps
+    /// are small, and their strings can't be borrowed from the Arrow buffers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a type is unknown, or if the number of values of a type doesn't match
+    /// the number of keys of that type.
+    pub fn view_from_raw_data(raw_data: &FastFormatArrowRawData) -> Result<Self> {
+        use arrow::datatypes::{Float64Type, Int64Type, UInt64Type, UInt8Type};
+
+        let keys = raw_data.utf8_array("keys")?;
+        let types = raw_data.utf8_array("types")?;
+
+        if keys.len() != types.len() {
+            return Err(Report::msg("Keys and types don't have the same length"));
+        }
+
+        let mut ints = raw_data
+            .primitive_array_view::<Int64Type>("ints")?
+            .iter()
+            .copied();
+        let mut floats = raw_data
+            .primitive_array_view::<Float64Type>("floats")?
+            .iter()
+            .copied();
+        let mut strings = raw_data.utf8_array("strings")?.into_iter();
+        let bytes = raw_data.primitive_array_view::<UInt8Type>("bytes")?;
+        let mut bytes_offsets = raw_data
+            .primitive_array_view::<UInt64Type>("bytes_offsets")?
+            .windows(2);
+
+        let entries = keys
+            .into_iter()
+            .zip(types)
+            .map(|(key, kind)| {
+                let value = match kind.as_str() {
+                    "int" => ints.next().map(Value::Int),
+                    "float" => floats.next().map(Value::Float),
+                    "string" => strings.next().map(Value::String),
+                    "bytes" => bytes_offsets
+                        .next()
+                        .and_then(|offsets| bytes.get(offsets[0] as usize..offsets[1] as usize))
+                        .map(|value| Value::Bytes(value.to_vec())),
+                    _ => return Err(Report::msg(format!("Unknown type {} of key {}", kind, key))),
+                }
+                .ok_or_eyre(Report::msg(format!(
+                    "Missing {} value of key {}",
+                    kind, key
+                )))?;
+
+                Ok((key, value))
+            })
+            .collect::<Result<_>>()?;
+
+        for (kind, leftover) in [
+            ("int", ints.next().is_some()),
+            ("float", floats.next().is_some()),
+            ("string", strings.next().is_some()),
+            ("bytes", bytes_offsets.next().is_some()),
+        ] {
+            if leftover {
+                return Err(Report::msg(format!(
+                    "Malformed KeyValueMap: more {} values than {} keys",
+                    kind, kind
+                )));
+            }
+        }
+
+        Ok(Self {
+            entries,
+            header: header::read(raw_data)?,
+        })
+    }
+
+    /// Reads a `KeyValueMap` from `FastFormatArrowRawData`, see
+    /// [`KeyValueMap::view_from_raw_data`].
+    pub fn from_raw_data(raw_data: FastFormatArrowRawData) -> Result<Self> {
+        Self::view_from_raw_data(&raw_data)
+    }
+
+    /// Converts Arrow `ArrayData` produced by [`KeyValueMap::into_arrow`] back into a
+    /// `KeyValueMap`.
+    pub fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        Self::from_raw_data(Self::raw_data(array_data)?)
+    }
+
+    /// Converts a `KeyValueMap` into Arrow `ArrayData`. Each value is stored in the array of its
+    /// type, in key order, and the bytes values are concatenated and split by `"bytes_offsets"`.
+    pub fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        use arrow::datatypes::{
+            DataType::{Float64, Int64, UInt64, UInt8, Utf8},
+            Float64Type, Int64Type, UInt64Type, UInt8Type,
+        };
+
+        let mut keys = Vec::with_capacity(self.len());
+        let mut types = Vec::with_capacity(self.len());
+        let (mut ints, mut floats, mut strings) = (Vec::new(), Vec::new(), Vec::new());
+        let (mut bytes, mut bytes_offsets) = (Vec::new(), vec![0]);
+
+        for (key, value) in self.entries {
+            types.push(value.type_name().to_string());
+            keys.push(key);
+
+            match value {
+                Value::Int(value) => ints.push(value),
+                Value::Float(value) => floats.push(value),
+                Value::String(value) => strings.push(value),
+                Value::Bytes(value) => {
+                    bytes.extend_from_slice(&value);
+                    bytes_offsets.push(bytes.len() as u64);
+                }
+            }
+        }
+
+        let builder = FastFormatArrowBuilder::with_schema(union_fields())
+            .push_utf_array("keys", keys, Utf8, false)
+            .push_utf_array("types", types, Utf8, false);
+
+        header::push(builder, self.header.as_ref())
+            .push_primitive_array::<Int64Type>("ints", ints, Int64, false)
+            .push_primitive_array::<Float64Type>("floats", floats, Float64, false)
+            .push_utf_array("strings", strings, Utf8, false)
+            .push_primitive_array::<UInt8Type>("bytes", bytes, UInt8, false)
+            .push_primitive_array::<UInt64Type>("bytes_offsets", bytes_offsets, UInt64, false)
+            .into_arrow()
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_key_value_map_arrow() {
+        use crate::{
+            header::{self, Header},
+            key_value_map::{arrow::union_fields, KeyValueMap, Value},
+        };
+        use arrow::datatypes::{
+            DataType::{Float64, Int64, UInt64, UInt8, Utf8},
+            Float64Type, Int64Type, UInt64Type, UInt8Type,
+        };
+        use fastformat_converter::arrow::FastFormatArrowBuilder;
+
+        let map = KeyValueMap::new()
+            .with("fps", 30_i64)
+            .with("temperature_c", 41.5)
+            .with("status", "ok")
+            .with("calibration", vec![1, 2, 3])
+            .with("thumbnail", Vec::new())
+            .with_header(Header::new(1_700_000_000_000_000_000, "camera_front", 7));
+
+        let array_data = map.clone().into_arrow().unwrap();
+
+        assert_eq!(KeyValueMap::from_arrow(array_data).unwrap(), map);
+
+        let empty = KeyValueMap::new().into_arrow().unwrap();
+
+        assert!(KeyValueMap::from_arrow(empty).unwrap().is_empty());
+
+        let raw_data = KeyValueMap::raw_data(map.into_arrow().unwrap()).unwrap();
+        let map = KeyValueMap::view_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(map.get("calibration"), Some(&Value::Bytes(vec![1, 2, 3])));
+
+        let builder = FastFormatArrowBuilder::with_schema(union_fields())
+            .push_utf_array("keys", vec!["fps".to_string()], Utf8, false)
+            .push_utf_array("types", vec!["int".to_string()], Utf8, false);
+        let malformed = header::push(builder, None)
+            .push_primitive_array::<Int64Type>("ints", vec![30, 60], Int64, false)
+            .push_primitive_array::<Float64Type>("floats", vec![], Float64, false)
+            .push_utf_array("strings", vec![], Utf8, false)
+            .push_primitive_array::<UInt8Type>("bytes", vec![], UInt8, false)
+            .push_primitive_array::<UInt64Type>("bytes_offsets", vec![0], UInt64, false)
+            .into_arrow()
+            .unwrap();
+
+        assert_eq!(
+            KeyValueMap::from_arrow(malformed).unwrap_err().to_string(),
+            "Malformed KeyValueMap: more int values than int keys"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use super::{KeyValueMap, Value};
+
+use pyo3::{
+    exceptions::PyTypeError,
+    prelude::*,
+    types::{PyBytes, PyDict, PyFloat, PyString},
+};
+
+impl ToPyObject for Value {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        match self {
+            Self::Int(value) => value.to_object(py),
+            Self::Float(value) => value.to_object(py),
+            Self::String(value) => value.to_object(py),
+            Self::Bytes(value) => PyBytes::new_bound(py, value).into_py(py),
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for Value {
+    /// Converts `int`, `float`, `str` and `bytes`. Booleans are `int`s in Python, and become
+    /// `Value::Int`.
+    fn extract_bound(value: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(bytes) = value.downcast::<PyBytes>() {
+            Ok(Self::Bytes(bytes.as_bytes().to_vec()))
+        } else if let Ok(string) = value.downcast::<PyString>() {
+            Ok(Self::String(string.to_str()?.to_string()))
+        } else if let Ok(float) = value.downcast::<PyFloat>() {
+            Ok(Self::Float(float.value()))
+        } else if let Ok(int) = value.extract::<i64>() {
+            Ok(Self::Int(int))
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "Unsupported KeyValueMap value of type {}, expected int, float, str or bytes",
+                value.get_type().name()?
+            )))
+        }
+    }
+}
+
+/// Converts the entries of the map to a Python `dict`. The header isn't part of the `dict`.
+impl ToPyObject for KeyValueMap {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let dict = PyDict::new_bound(py);
+
+        for (key, value) in self.iter() {
+            // Inserting a `str` key in a new `dict` can't fail.
+            let _ = dict.set_item(key, value);
+        }
+
+        dict.into_py(py)
+    }
+}
+
+impl IntoPy<PyObject> for KeyValueMap {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'py> FromPyObject<'py> for KeyValueMap {
+    fn extract_bound(dict: &Bound<'py, PyAny>) -> PyResult<Self> {
+        dict.downcast::<PyDict>()?
+            .iter()
+            .map(|(key, value)| Ok((key.extract::<String>()?, value.extract::<Value>()?)))
+            .collect()
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_key_value_map_python() {
+        use crate::key_value_map::{KeyValueMap, Value};
+        use pyo3::{prelude::*, types::PyDict};
+
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let map = KeyValueMap::new()
+                .with("fps", 30_i64)
+                .with("gain", 1.5)
+                .with("status", "ok")
+                .with("blob", vec![0, 255]);
+
+            let dict = map.to_object(py);
+            let dict = dict.downcast_bound::<PyDict>(py).unwrap();
+
+            assert_eq!(dict.len(), 4);
+            assert_eq!(
+                dict.get_item("blob")
+                    .unwrap()
+                    .unwrap()
+                    .extract::<Vec<u8>>()
+                    .unwrap(),
+                vec![0, 255]
+            );
+
+            assert_eq!(dict.extract::<KeyValueMap>().unwrap(), map);
+
+            dict.set_item("enabled", true).unwrap();
+            assert_eq!(
+                dict.extract::<KeyValueMap>().unwrap().get("enabled"),
+                Some(&Value::Int(1))
+            );
+
+            dict.set_item("nested", PyDict::new_bound(py)).unwrap();
+            assert!(dict.extract::<KeyValueMap>().is_err());
+        });
+    }
+}
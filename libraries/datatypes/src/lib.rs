@@ -1,2 +1,18 @@
+pub mod any;
 pub mod bbox;
+pub mod drops;
+pub mod header;
+pub use fastformat_core::validation;
 pub mod image;
+pub mod key_value_map;
+pub mod pool;
+pub mod sync;
+pub mod tiled_image;
+pub mod tracking;
+pub mod units;
+
+#[cfg(feature = "memmap2")]
+pub mod mmap;
+
+#[cfg(feature = "arrow")]
+pub mod owned_view;
@@ -0,0 +1,56 @@
+use eyre::{Context, Report, Result};
+
+use std::{fs::File, ops::Range, path::Path};
+
+/// A read-only memory mapping of a file.
+///
+/// Datatypes can borrow their buffers straight from the mapping (see
+/// `Image::view_from_mapped_file`), so dataset playback nodes can stream recordings much larger
+/// than RAM: pages are only loaded when a frame is read, and the OS can evict them again.
+#[derive(Debug)]
+pub struct MappedFile {
+    mmap: memmap2::Mmap,
+}
+
+impl MappedFile {
+    /// Maps the whole file at `path` into memory.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process, while the
+    /// mapping or any view borrowed from it is alive. Doing so is undefined behavior.
+    pub unsafe fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        let file =
+            File::open(path).wrap_err(format!("Failed to open {} for mapping", path.display()))?;
+        let mmap =
+            memmap2::Mmap::map(&file).wrap_err(format!("Failed to map {}", path.display()))?;
+
+        Ok(Self { mmap })
+    }
+
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mmap.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Returns the bytes in `range`, or an error if it goes past the end of the file.
+    pub fn slice(&self, range: Range<usize>) -> Result<&[u8]> {
+        let len = self.mmap.len();
+
+        self.mmap.get(range.clone()).ok_or_else(|| {
+            Report::msg(format!(
+                "Range {:?} is out of bounds of the mapped file ({} bytes)",
+                range, len
+            ))
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{bbox::BBox, image::Image, key_value_map::KeyValueMap, tiled_image::TiledImage};
+use fastformat_converter::arrow::FastFormatArrowRawData;
+
+use eyre::Result;
+
+use std::{mem::ManuallyDrop, ptr::NonNull};
+
+/// A datatype that can be viewed without copying from the raw data of an Arrow message.
+pub trait Viewable {
+    /// The view, borrowing from the raw data.
+    type View<'a>;
+
+    fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData>;
+
+    fn view_from_raw_data(raw_data: &FastFormatArrowRawData) -> Result<Self::View<'_>>;
+
+    /// Shortens the lifetime of a view. Implementations are the identity function, which only
+    /// compiles if the view is covariant in its lifetime.
+    fn shorten<'a, 'b: 'a>(view: &'a Self::View<'b>) -> &'a Self::View<'a>;
+}
+
+impl Viewable for Image<'_> {
+    type View<'a> = Image<'a>;
+
+    fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        Image::raw_data(array_data)
+    }
+
+    fn view_from_raw_data(raw_data: &FastFormatArrowRawData) -> Result<Image<'_>> {
+        Image::view_from_raw_data(raw_data)
+    }
+
+    fn shorten<'a, 'b: 'a>(view: &'a Image<'b>) -> &'a Image<'a> {
+        view
+    }
+}
+
+impl Viewable for BBox<'_> {
+    type View<'a> = BBox<'a>;
+
+    fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        BBox::raw_data(array_data)
+    }
+
+    fn view_from_raw_data(raw_data: &FastFormatArrowRawData) -> Result<BBox<'_>> {
+        BBox::view_from_raw_data(raw_data)
+    }
+
+    fn shorten<'a, 'b: 'a>(view: &'a BBox<'b>) -> &'a BBox<'a> {
+        view
+    }
+}
+
+impl Viewable for TiledImage<'_> {
+    type View<'a> = TiledImage<'a>;
+
+    fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        TiledImage::raw_data(array_data)
+    }
+
+    fn view_from_raw_data(raw_data: &FastFormatArrowRawData) -> Result<TiledImage<'_>> {
+        TiledImage::view_from_raw_data(raw_data)
+    }
+
+    fn shorten<'a, 'b: 'a>(view: &'a TiledImage<'b>) -> &'a TiledImage<'a> {
+        view
+    }
+}
+
+/// Maps are small: their view is a decoded copy.
+impl Viewable for KeyValueMap {
+    type View<'a> = KeyValueMap;
+
+    fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        KeyValueMap::raw_data(array_data)
+    }
+
+    fn view_from_raw_data(raw_data: &FastFormatArrowRawData) -> Result<KeyValueMap> {
+        KeyValueMap::view_from_raw_data(raw_data)
+    }
+
+    fn shorten<'a, 'b: 'a>(view: &'a KeyValueMap) -> &'a KeyValueMap {
+        view
+    }
+}
+
+/// A zero-copy view bundled with the raw data it borrows from, so that it has no external
+/// lifetime and can be returned from functions or stored in `'static` contexts.
+///
+/// # Example
+///
+/// ```
+/// use fastformat_datatypes::image::Image;
+/// use fastformat_datatypes::owned_view::OwnedView;
+///
+/// fn receive(array_data: arrow::array::ArrayData) -> OwnedView<Image<'static>> {
+///     OwnedView::from_arrow(array_data).unwrap()
+/// }
+///
+/// let image = Image::new_gray8(vec![0; 4], 2, 2, None).unwrap();
+/// let view = receive(image.into_arrow().unwrap());
+///
+/// assert_eq!(view.get().width, 2);
+/// ```
+pub struct OwnedView<T: Viewable> {
+    view: ManuallyDrop<T::View<'static>>,
+    // Boxed so that its address doesn't change when the `OwnedView` moves. Kept as a raw pointer
+    // because `view` borrows from it, which a `Box` would not allow.
+    raw_data: NonNull<FastFormatArrowRawData>,
+}
+
+impl<T: Viewable> OwnedView<T> {
+    /// Extracts the raw data of an Arrow message and creates a view of it.
+    pub fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        Self::from_raw_data(T::raw_data(array_data)?)
+    }
+
+    /// Takes ownership of `raw_data` and creates a view of it.
+    pub fn from_raw_data(raw_data: FastFormatArrowRawData) -> Result<Self> {
+        let raw_data = NonNull::from(Box::leak(Box::new(raw_data)));
+
+        // SAFETY: `raw_data` is never mutated and outlives `view`: it is only freed in `drop`,
+        // after `view`.
+        match T::view_from_raw_data(unsafe { raw_data.as_ref() }) {
+            Ok(view) => Ok(Self {
+                view: ManuallyDrop::new(view),
+                raw_data,
+            }),
+            Err(error) => {
+                // SAFETY: the view failed, so nothing borrows `raw_data` anymore.
+                drop(unsafe { Box::from_raw(raw_data.as_ptr()) });
+
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns the view, borrowed for no longer than the `OwnedView` lives.
+    pub fn get(&self) -> &T::View<'_> {
+        T::shorten(&self.view)
+    }
+}
+
+impl<T: Viewable> Drop for OwnedView<T> {
+    fn drop(&mut self) {
+        // SAFETY: the view is dropped before the raw data it borrows from, and neither is used
+        // afterwards.
+        unsafe {
+            ManuallyDrop::drop(&mut self.view);
+            drop(Box::from_raw(self.raw_data.as_ptr()));
+        }
+    }
+}
+
+// SAFETY: the raw data is owned by the `OwnedView` and only shared with the view, so the
+// `OwnedView` can move across threads as long as the view and the raw data can.
+unsafe impl<T: Viewable> Send for OwnedView<T>
+where
+    T::View<'static>: Send,
+    FastFormatArrowRawData: Send,
+{
+}
+
+// SAFETY: `get` only hands out shared references to the view.
+unsafe impl<T: Viewable> Sync for OwnedView<T>
+where
+    T::View<'static>: Sync,
+    FastFormatArrowRawData: Sync,
+{
+}
+
+mod tests {
+    #[test]
+    fn test_owned_view_zero_copy() {
+        use crate::bbox::BBox;
+        use crate::image::Image;
+        use crate::owned_view::OwnedView;
+
+        let flat_image = vec![0; 27];
+        let original_buffer_address = flat_image.as_ptr() as *const u64;
+
+        let image = Image::new_rgb8(flat_image, 3, 3, Some("camera.test")).unwrap();
+        let view = OwnedView::<Image>::from_arrow(image.into_arrow().unwrap()).unwrap();
+
+        // Moving the view, e.g. to another thread, doesn't invalidate it.
+        let view = std::thread::spawn(move || view).join().unwrap();
+
+        assert_eq!(view.get().data.as_ptr(), original_buffer_address);
+        assert!(view.get().is_borrowed());
+        assert_eq!(view.get().name.as_deref(), Some("camera.test"));
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+        let original_buffer_address = bbox.data.as_ptr();
+
+        let view = OwnedView::<BBox>::from_arrow(bbox.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(view.get().data.as_ptr(), original_buffer_address);
+    }
+}
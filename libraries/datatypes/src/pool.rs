@@ -0,0 +1,215 @@
+use crate::image::{data::ImageData, Image};
+
+use std::{
+    borrow::Cow,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+};
+
+/// A pool of reusable `Vec<u8>` buffers.
+///
+/// Camera loops running at 30-60 Hz allocate and free a full frame every iteration. A
+/// `BufferPool` keeps the released buffers around so that the next frame of the same size can
+/// reuse their capacity instead of going through the allocator again.
+///
+/// The pool is cheap to clone: all clones share the same set of buffers.
+///
+/// # Example
+///
+/// ```
+/// use fastformat_datatypes::image::Image;
+/// use fastformat_datatypes::pool::BufferPool;
+///
+/// let pool = BufferPool::new(4);
+///
+/// let buffer = pool.take(27);
+/// let image = Image::new_bgr8(buffer.into_inner(), 3, 3, None).unwrap();
+///
+/// // Once the frame has been processed, hand its buffer back.
+/// pool.recycle_image(image);
+/// assert_eq!(pool.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BufferPool {
+    buffers: Arc<Mutex<Vec<Vec<u8>>>>,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    /// Creates an empty pool that keeps at most `max_buffers` idle buffers.
+    pub fn new(max_buffers: usize) -> Self {
+        Self {
+            buffers: Arc::new(Mutex::new(Vec::with_capacity(max_buffers))),
+            max_buffers,
+        }
+    }
+
+    /// Number of idle buffers currently held by the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hands out a zero-filled buffer of `len` bytes.
+    ///
+    /// The smallest idle buffer with enough capacity is reused. A new buffer is only allocated
+    /// when none fits. The buffer goes back to the pool when the returned guard is dropped,
+    /// unless it is detached with [`PooledBuffer::into_inner`].
+    pub fn take(&self, len: usize) -> PooledBuffer {
+        let buffer = {
+            let mut buffers = self.lock();
+
+            let best_fit = buffers
+                .iter()
+                .enumerate()
+                .filter(|(_, buffer)| buffer.capacity() >= len)
+                .min_by_key(|(_, buffer)| buffer.capacity())
+                .map(|(index, _)| index);
+
+            best_fit.map(|index| buffers.swap_remove(index))
+        };
+
+        let mut buffer = buffer.unwrap_or_else(|| Vec::with_capacity(len));
+        buffer.clear();
+        buffer.resize(len, 0);
+
+        PooledBuffer {
+            buffer: Some(buffer),
+            pool: self.clone(),
+        }
+    }
+
+    /// Gives a buffer back to the pool. It is dropped if the pool is already full.
+    pub fn recycle(&self, buffer: Vec<u8>) {
+        let mut buffers = self.lock();
+
+        if buffers.len() < self.max_buffers {
+            buffers.push(buffer);
+        }
+    }
+
+    /// Gives the pixel buffer of an `Image` back to the pool.
+    ///
+    /// Only owned `u8` buffers can be recycled, including Arrow buffers that aren't shared;
+    /// borrowed or non-`u8` data is simply dropped.
+    pub fn recycle_image(&self, image: Image) {
+        match image.data {
+            ImageData::U8(Cow::Owned(buffer)) => self.recycle(buffer),
+            #[cfg(feature = "arrow")]
+            ImageData::ArrowBuffer(buffer) => {
+                if let Ok(buffer) = buffer.into_vec::<u8>() {
+                    self.recycle(buffer);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<u8>>> {
+        // A panic while holding the lock can't leave the list of buffers in an invalid state.
+        self.buffers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+/// A buffer borrowed from a [`BufferPool`], returned to it on drop.
+#[derive(Debug)]
+pub struct PooledBuffer {
+    buffer: Option<Vec<u8>>,
+    pool: BufferPool,
+}
+
+impl PooledBuffer {
+    /// Detaches the buffer from the pool, e.g. to move it into an `Image`.
+    pub fn into_inner(mut self) -> Vec<u8> {
+        self.buffer.take().unwrap_or_default()
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        self.buffer.as_ref().expect("buffer is only taken on drop")
+    }
+}
+
+impl DerefMut for PooledBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.buffer.as_mut().expect("buffer is only taken on drop")
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            self.pool.recycle(buffer);
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_pool_reuses_dropped_buffer() {
+        use crate::pool::BufferPool;
+
+        let pool = BufferPool::new(2);
+
+        let buffer = pool.take(27);
+        let original_buffer_address = buffer.as_ptr();
+        drop(buffer);
+
+        assert_eq!(pool.len(), 1);
+
+        let buffer = pool.take(9);
+
+        assert_eq!(original_buffer_address, buffer.as_ptr());
+        assert_eq!(buffer.len(), 9);
+        assert!(buffer.iter().all(|&value| value == 0));
+    }
+
+    #[test]
+    fn test_pool_recycles_image_buffer() {
+        use crate::image::Image;
+        use crate::pool::BufferPool;
+
+        let pool = BufferPool::new(2);
+
+        let buffer = pool.take(27).into_inner();
+        let original_buffer_address = buffer.as_ptr();
+
+        assert!(pool.is_empty());
+
+        let image = Image::new_rgb8(buffer, 3, 3, None).unwrap();
+        let image = image.into_bgr8().unwrap();
+        pool.recycle_image(image);
+
+        let buffer = pool.take(27);
+
+        assert_eq!(original_buffer_address, buffer.as_ptr());
+    }
+
+    #[test]
+    fn test_pool_respects_max_buffers() {
+        use crate::pool::BufferPool;
+
+        let pool = BufferPool::new(1);
+
+        let first = pool.take(3);
+        let second = pool.take(3);
+        drop(first);
+        drop(second);
+
+        assert_eq!(pool.len(), 1);
+    }
+}
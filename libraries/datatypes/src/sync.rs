@@ -0,0 +1,150 @@
+use eyre::{Report, Result};
+
+use std::{collections::VecDeque, time::Duration};
+
+/// Aligns messages from several inputs by timestamp, e.g. to fuse a camera, a lidar and an IMU.
+///
+/// Messages are buffered per input until every input has one, and emitted together when their
+/// timestamps are at most `slop` apart (approximate-time policy). The oldest buffered message
+/// of every input is matched first; a message that can no longer be matched, because another
+/// input is already past it by more than `slop`, is dropped.
+///
+/// Timestamps are expected to increase on every input, e.g. the `timestamp_ns` of the messages'
+/// header.
+///
+/// # Example
+///
+/// ```
+/// use fastformat_datatypes::image::Image;
+/// use fastformat_datatypes::sync::TimeSynchronizer;
+/// use std::time::Duration;
+///
+/// let (camera, lidar) = (0, 1);
+/// let mut synchronizer = TimeSynchronizer::new(2, Duration::from_millis(10));
+///
+/// let image = || Image::new_gray8(vec![0; 4], 2, 2, None).unwrap();
+///
+/// assert!(synchronizer.push(camera, 0, image()).unwrap().is_none());
+/// assert!(synchronizer.push(camera, 33_000_000, image()).unwrap().is_none());
+///
+/// // The first camera frame is too old for this scan, and is dropped.
+/// let aligned = synchronizer.push(lidar, 40_000_000, image()).unwrap();
+///
+/// assert_eq!(aligned.map(|messages| messages.len()), Some(2));
+/// assert_eq!(synchronizer.dropped(), 1);
+/// ```
+#[derive(Debug)]
+pub struct TimeSynchronizer<T> {
+    queues: Vec<VecDeque<(u64, T)>>,
+    slop_ns: u64,
+    queue_size: usize,
+    dropped: usize,
+}
+
+impl<T> TimeSynchronizer<T> {
+    /// Creates a synchronizer of `inputs` inputs, matching messages at most `slop` apart. Each
+    /// input buffers up to 16 messages, see [`TimeSynchronizer::with_queue_size`].
+    pub fn new(inputs: usize, slop: Duration) -> Self {
+        Self {
+            queues: (0..inputs).map(|_| VecDeque::new()).collect(),
+            slop_ns: slop.as_nanos().try_into().unwrap_or(u64::MAX),
+            queue_size: 16,
+            dropped: 0,
+        }
+    }
+
+    /// Sets the number of messages buffered per input. When an input is full, its oldest
+    /// message is dropped.
+    pub fn with_queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size.max(1);
+
+        self
+    }
+
+    /// Number of messages dropped so far, either unmatched or evicted from a full queue.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Buffers `message` of `input`, and returns the aligned messages of every input, in input
+    /// order, if this message completes a match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` isn't one of the synchronizer's inputs.
+    pub fn push(&mut self, input: usize, timestamp_ns: u64, message: T) -> Result<Option<Vec<T>>> {
+        let inputs = self.queues.len();
+        let queue = self.queues.get_mut(input).ok_or_else(|| {
+            Report::msg(format!(
+                "Input {} out of range, the synchronizer has {} inputs",
+                input, inputs
+            ))
+        })?;
+
+        if queue.len() == self.queue_size {
+            queue.pop_front();
+            self.dropped += 1;
+        }
+        queue.push_back((timestamp_ns, message));
+
+        Ok(self.try_match())
+    }
+
+    fn try_match(&mut self) -> Option<Vec<T>> {
+        loop {
+            let heads = self
+                .queues
+                .iter()
+                .map(|queue| queue.front().map(|(timestamp_ns, _)| *timestamp_ns))
+                .collect::<Option<Vec<u64>>>()?;
+
+            let (oldest, &min) = heads
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, timestamp_ns)| **timestamp_ns)?;
+            let max = *heads.iter().max()?;
+
+            if max - min <= self.slop_ns {
+                return self
+                    .queues
+                    .iter_mut()
+                    .map(|queue| queue.pop_front().map(|(_, message)| message))
+                    .collect();
+            }
+
+            // Every other input is past the oldest message, it will never be matched.
+            self.queues[oldest].pop_front();
+            self.dropped += 1;
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_time_synchronizer() {
+        use crate::sync::TimeSynchronizer;
+        use std::time::Duration;
+
+        const MS: u64 = 1_000_000;
+
+        let (camera, lidar, imu) = (0, 1, 2);
+        let mut synchronizer = TimeSynchronizer::new(3, Duration::from_millis(5));
+
+        let mut aligned = Vec::new();
+        for step in 0..100u64 {
+            let timestamp_ns = step * MS;
+
+            // Camera at 30 Hz, lidar at 10 Hz shifted by 2 ms, IMU at 1 kHz.
+            if step % 33 == 0 {
+                aligned.extend(synchronizer.push(camera, timestamp_ns, "camera").unwrap());
+            }
+            if step % 100 == 2 {
+                aligned.extend(synchronizer.push(lidar, timestamp_ns, "lidar").unwrap());
+            }
+            aligned.extend(synchronizer.push(imu, timestamp_ns, "imu").unwrap());
+        }
+
+        assert_eq!(aligned, [vec!["camera", "lidar", "imu"]]);
+        assert!(synchronizer.push(3, 0, "gps").is_err());
+    }
+}
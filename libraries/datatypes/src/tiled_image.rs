@@ -0,0 +1,357 @@
+use eyre::{Report, Result};
+use fastformat_converter::{accounting, policy};
+
+use crate::{
+    header::Header,
+    image::{Encoding, Image, ImageData},
+};
+
+use std::borrow::Cow;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+/// A very large image (satellite, microscopy, ...) split into a grid of tiles with a buffer
+/// each, so that it never has to be held in a single contiguous allocation and one tile can be
+/// read without touching the others.
+///
+/// Tiles are stored row after row: the tile at column `c` and row `r` of the grid is at index
+/// `r * columns + c`. Tiles of the last column and row are cropped to the size of the image.
+#[derive(Debug)]
+pub struct TiledImage<'a> {
+    pub tiles: Vec<Cow<'a, [u8]>>,
+
+    pub width: u32,
+    pub height: u32,
+
+    pub tile_width: u32,
+    pub tile_height: u32,
+
+    pub encoding: Encoding,
+
+    pub name: Option<String>,
+
+    pub header: Option<Header>,
+}
+
+/// Returns the `[x, y, width, height]` of the tile at `index` of the grid splitting a
+/// `width`x`height` image into `tile_width`x`tile_height` tiles, or `None` if it is outside of
+/// the grid.
+fn grid_rect(
+    (width, height): (u32, u32),
+    (tile_width, tile_height): (u32, u32),
+    index: usize,
+) -> Option<[u32; 4]> {
+    if tile_width == 0 || tile_height == 0 {
+        return None;
+    }
+
+    let (columns, rows) = (width.div_ceil(tile_width), height.div_ceil(tile_height));
+
+    if index >= columns as usize * rows as usize {
+        return None;
+    }
+
+    let (x, y) = (
+        (index % columns as usize) as u32 * tile_width,
+        (index / columns as usize) as u32 * tile_height,
+    );
+
+    Some([x, y, tile_width.min(width - x), tile_height.min(height - y)])
+}
+
+impl<'a> TiledImage<'a> {
+    /// Creates a tiled image from the pixel data of its tiles, row after row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tile size is zero, if there isn't one buffer per tile of the
+    /// grid, or if the length of a buffer doesn't match the size of its tile.
+    pub fn new(
+        tiles: Vec<Cow<'a, [u8]>>,
+        width: u32,
+        height: u32,
+        tile_width: u32,
+        tile_height: u32,
+        encoding: Encoding,
+        name: Option<&str>,
+    ) -> Result<Self> {
+        if tile_width == 0 || tile_height == 0 {
+            return Err(Report::msg("Tile size can't be zero."));
+        }
+
+        let image = Self {
+            tiles,
+            width,
+            height,
+            tile_width,
+            tile_height,
+            encoding,
+            name: name.map(|s| s.to_string()),
+            header: None,
+        };
+
+        if image.tiles.len() != image.columns() as usize * image.rows() as usize {
+            return Err(Report::msg(format!(
+                "Invalid number of tiles: {} for a grid of {}x{} tiles.",
+                image.tiles.len(),
+                image.columns(),
+                image.rows()
+            )));
+        }
+
+        for (index, tile) in image.tiles.iter().enumerate() {
+            let [_, _, width, height] = image.tile_rect(index).unwrap_or_default();
+
+            if encoding.expected_len(width, height) != Some(tile.len()) {
+                return Err(Report::msg(format!(
+                    "Invalid pixel data length: {} values for the {}x{} tile {}.",
+                    tile.len(),
+                    width,
+                    height,
+                    index
+                )));
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Attaches a `Header` to the image, e.g. to timestamp it.
+    pub fn with_header(mut self, header: Header) -> Self {
+        self.header = Some(header);
+
+        self
+    }
+
+    /// Number of columns of the grid of tiles.
+    pub fn columns(&self) -> u32 {
+        self.width.div_ceil(self.tile_width)
+    }
+
+    /// Number of rows of the grid of tiles.
+    pub fn rows(&self) -> u32 {
+        self.height.div_ceil(self.tile_height)
+    }
+
+    /// Number of tiles.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Returns the `[x, y, width, height]` of the tile at `index` in the image, or `None` if
+    /// there is no such tile.
+    pub fn tile_rect(&self, index: usize) -> Option<[u32; 4]> {
+        grid_rect(
+            (self.width, self.height),
+            (self.tile_width, self.tile_height),
+            index,
+        )
+    }
+
+    /// Returns the indices of the tiles overlapping the region of size `width`x`height` at
+    /// `(x, y)`, so that only the part of the image of interest is processed.
+    pub fn tiles_in(&self, x: u32, y: u32, width: u32, height: u32) -> Vec<usize> {
+        let x_end = x.saturating_add(width).min(self.width);
+        let y_end = y.saturating_add(height).min(self.height);
+
+        if x >= x_end || y >= y_end {
+            return Vec::new();
+        }
+
+        let columns = x / self.tile_width..x_end.div_ceil(self.tile_width);
+
+        (y / self.tile_height..y_end.div_ceil(self.tile_height))
+            .flat_map(|row| {
+                columns
+                    .clone()
+                    .map(move |column| (row * self.columns() + column) as usize)
+            })
+            .collect()
+    }
+
+    /// Returns a view of the tile at `index` as an `Image`, without copying its pixel data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such tile.
+    pub fn tile(&self, index: usize) -> Result<Image<'_>> {
+        let (Some(tile), Some([_, _, width, height])) =
+            (self.tiles.get(index), self.tile_rect(index))
+        else {
+            return Err(Report::msg(format!(
+                "Invalid tile {} for a grid of {} tiles.",
+                index,
+                self.len()
+            )));
+        };
+
+        Ok(Image {
+            data: ImageData::from_slice_u8(tile),
+            width,
+            height,
+            encoding: self.encoding,
+            name: self.name.clone(),
+            header: self.header.clone(),
+            capture: None,
+        })
+    }
+
+    /// Splits an image into tiles of `tile_width`x`tile_height`, copying its pixel data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tile size is zero, or if the pixel data doesn't match the image
+    /// size.
+    pub fn from_image(
+        image: &Image,
+        tile_width: u32,
+        tile_height: u32,
+    ) -> Result<TiledImage<'static>> {
+        if tile_width == 0 || tile_height == 0 {
+            return Err(Report::msg("Tile size can't be zero."));
+        }
+
+        let pixels = match image.encoding {
+            Encoding::RGB8 | Encoding::BGR8 | Encoding::GRAY8 => image.data.as_u8()?,
+        };
+
+        if image.encoding.expected_len(image.width, image.height) != Some(pixels.len()) {
+            return Err(Report::msg("Invalid pixel data length."));
+        }
+
+        accounting::record("TiledImage::from_image", pixels.len(), true);
+
+        let channels = image.encoding.channels();
+        let stride = image.width as usize * channels;
+
+        let tiles = (0..)
+            .map_while(|index| {
+                grid_rect(
+                    (image.width, image.height),
+                    (tile_width, tile_height),
+                    index,
+                )
+            })
+            .map(|[x, y, width, height]| {
+                let (start, len) = (x as usize * channels, width as usize * channels);
+
+                Cow::Owned(
+                    pixels
+                        .chunks_exact(stride)
+                        .skip(y as usize)
+                        .take(height as usize)
+                        .flat_map(|row| &row[start..start + len])
+                        .copied()
+                        .collect(),
+                )
+            })
+            .collect();
+
+        TiledImage::new(
+            tiles,
+            image.width,
+            image.height,
+            tile_width,
+            tile_height,
+            image.encoding,
+            image.name.as_deref(),
+        )
+        .map(|tiled| TiledImage {
+            header: image.header.clone(),
+            ..tiled
+        })
+    }
+
+    /// Assembles the tiles into a single `Image`, copying their pixel data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if copying is denied by the copy policy, or if a tile doesn't match its
+    /// size.
+    pub fn into_image(self) -> Result<Image<'static>> {
+        let len = self
+            .encoding
+            .expected_len(self.width, self.height)
+            .ok_or_else(|| Report::msg("Image is too large."))?;
+
+        policy::check("TiledImage::into_image", len)?;
+        accounting::record("TiledImage::into_image", len, true);
+
+        let channels = self.encoding.channels();
+        let stride = self.width as usize * channels;
+        let mut data = vec![0; len];
+
+        for (index, tile) in self.tiles.iter().enumerate() {
+            let [x, y, width, _] = self.tile_rect(index).unwrap_or_default();
+            let (start, tile_stride) = (x as usize * channels, width as usize * channels);
+
+            if tile_stride == 0 {
+                continue;
+            }
+
+            for (row, tile_row) in data
+                .chunks_exact_mut(stride)
+                .skip(y as usize)
+                .zip(tile.chunks_exact(tile_stride))
+            {
+                row[start..start + tile_stride].copy_from_slice(tile_row);
+            }
+        }
+
+        Ok(Image {
+            data: ImageData::from_vec_u8(data),
+            width: self.width,
+            height: self.height,
+            encoding: self.encoding,
+            name: self.name,
+            header: self.header,
+            capture: None,
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_tiled_image() {
+        use crate::{image::Image, tiled_image::TiledImage};
+
+        let data = (0..5 * 3 * 3).map(|value| value as u8).collect::<Vec<_>>();
+        let image = Image::new_rgb8(data.clone(), 5, 3, Some("satellite")).unwrap();
+
+        let tiled = TiledImage::from_image(&image, 2, 2).unwrap();
+
+        assert_eq!((tiled.columns(), tiled.rows(), tiled.len()), (3, 2, 6));
+        assert_eq!(tiled.tile_rect(2), Some([4, 0, 1, 2]));
+        assert_eq!(tiled.tile_rect(5), Some([4, 2, 1, 1]));
+        assert_eq!(tiled.tile_rect(6), None);
+        assert_eq!(tiled.tiles_in(1, 1, 2, 1), vec![0, 1]);
+        assert_eq!(tiled.tiles_in(3, 1, 10, 10), vec![1, 2, 4, 5]);
+        assert!(tiled.tiles_in(5, 0, 1, 1).is_empty());
+
+        let tile = tiled.tile(4).unwrap();
+        assert_eq!((tile.width, tile.height), (2, 1));
+        assert_eq!(tile.data.as_u8().unwrap(), &data[36..42]);
+        assert!(tiled.tile(6).is_err());
+
+        let image = tiled.into_image().unwrap();
+        assert_eq!(image.data.as_u8().unwrap(), &data[..]);
+        assert_eq!(image.name.as_deref(), Some("satellite"));
+
+        assert!(TiledImage::from_image(&image, 0, 2).is_err());
+        assert!(TiledImage::new(
+            vec![vec![0; 3].into()],
+            2,
+            1,
+            1,
+            1,
+            crate::image::Encoding::RGB8,
+            None
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use super::TiledImage;
+use crate::{
+    header,
+    image::Encoding,
+    units::{self, Unit},
+};
+use eyre::{OptionExt, Report, Result};
+use fastformat_converter::{
+    accounting,
+    arrow::{FastFormatArrowBuilder, FastFormatArrowRawData},
+    policy,
+};
+
+use std::{borrow::Cow, sync::OnceLock};
+
+/// Arrow layout of a `TiledImage`, built once and shared by every `into_arrow` call.
+fn union_fields() -> arrow::datatypes::UnionFields {
+    use arrow::datatypes::DataType::{UInt32, UInt64, UInt8, Utf8};
+
+    static UNION_FIELDS: OnceLock<arrow::datatypes::UnionFields> = OnceLock::new();
+
+    UNION_FIELDS
+        .get_or_init(|| {
+            let schema = FastFormatArrowBuilder::schema(
+                &[
+                    &[
+                        ("width", UInt32, false),
+                        ("height", UInt32, false),
+                        ("tile_width", UInt32, false),
+                        ("tile_height", UInt32, false),
+                        ("encoding", Utf8, false),
+                        ("name", Utf8, false),
+                    ][..],
+                    &header::fields(),
+                    &[("offsets", UInt64, false), ("data", UInt8, false)],
+                ]
+                .concat(),
+            );
+
+            units::annotate(
+                schema,
+                &[
+                    &[
+                        ("width", Unit::Pixel),
+                        ("height", Unit::Pixel),
+                        ("tile_width", Unit::Pixel),
+                        ("tile_height", Unit::Pixel),
+                    ][..],
+                    &header::units(),
+                ]
+                .concat(),
+            )
+        })
+        .clone()
+}
+
+impl<'a> TiledImage<'a> {
+    /// Extracts raw data from an Arrow `ArrayData` produced by [`TiledImage::into_arrow`].
+    pub fn raw_data(array_data: arrow::array::ArrayData) -> Result<FastFormatArrowRawData> {
+        use arrow::datatypes::{UInt32Type, UInt64Type, UInt8Type};
+
+        let raw_data = FastFormatArrowRawData::new(array_data)?
+            .load_primitive::<UInt32Type>("width")?
+            .load_primitive::<UInt32Type>("height")?
+            .load_primitive::<UInt32Type>("tile_width")?
+            .load_primitive::<UInt32Type>("tile_height")?
+            .load_utf("encoding")?
+            .load_utf("name")?
+            .load_primitive::<UInt64Type>("offsets")?
+            .load_primitive::<UInt8Type>("data")?;
+
+        header::load(raw_data)
+    }
+
+    /// Creates a read-only view of a `TiledImage` from `FastFormatArrowRawData`. Every tile
+    /// borrows its slice of the Arrow buffer, so tiles that are never read are never touched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the offsets don't fit the pixel data, or if a tile doesn't match its
+    /// size.
+    pub fn view_from_raw_data(raw_data: &'a FastFormatArrowRawData) -> Result<Self> {
+        use arrow::datatypes::{UInt32Type, UInt64Type, UInt8Type};
+
+        let encoding = raw_data
+            .utf8_singleton_view("encoding")?
+            .parse::<Encoding>()?;
+        let name = Some(raw_data.utf8_singleton("name")?).filter(|s| !s.is_empty());
+        let offsets = raw_data.primitive_array_view::<UInt64Type>("offsets")?;
+        let data = raw_data.primitive_array_view::<UInt8Type>("data")?;
+
+        let tiles = offsets
+            .windows(2)
+            .enumerate()
+            .map(|(index, offsets)| {
+                data.get(offsets[0] as usize..offsets[1] as usize)
+                    .map(Cow::Borrowed)
+                    .ok_or_eyre(Report::msg(format!(
+                        "Offsets of tile {} are out of the tiled image data.",
+                        index
+                    )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let image = Self::new(
+            tiles,
+            raw_data.primitive_singleton::<UInt32Type>("width")?,
+            raw_data.primitive_singleton::<UInt32Type>("height")?,
+            raw_data.primitive_singleton::<UInt32Type>("tile_width")?,
+            raw_data.primitive_singleton::<UInt32Type>("tile_height")?,
+            encoding,
+            name.as_deref(),
+        )?;
+
+        accounting::record("TiledImage::view_from_raw_data", data.len(), false);
+
+        Ok(Self {
+            header: header::read(raw_data)?,
+            ..image
+        })
+    }
+
+    /// Converts Arrow `ArrayData` into an owned `TiledImage`, copying every tile into its own
+    /// buffer. Use [`TiledImage::view_from_raw_data`] to read the tiles without copying.
+    pub fn from_arrow(array_data: arrow::array::ArrayData) -> Result<TiledImage<'static>> {
+        let raw_data = Self::raw_data(array_data)?;
+        let image = TiledImage::view_from_raw_data(&raw_data)?;
+
+        let len = image.tiles.iter().map(|tile| tile.len()).sum();
+        policy::check("TiledImage::from_arrow", len)?;
+        accounting::record("TiledImage::from_arrow", len, true);
+
+        Ok(TiledImage {
+            tiles: image
+                .tiles
+                .into_iter()
+                .map(|tile| Cow::Owned(tile.into_owned()))
+                .collect(),
+            width: image.width,
+            height: image.height,
+            tile_width: image.tile_width,
+            tile_height: image.tile_height,
+            encoding: image.encoding,
+            name: image.name,
+            header: image.header,
+        })
+    }
+
+    /// Converts a `TiledImage` into Arrow `ArrayData`. The tiles are concatenated into a single
+    /// buffer, split by an `offsets` array of `N + 1` entries.
+    pub fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        use arrow::datatypes::{
+            DataType::{UInt32, UInt64, UInt8, Utf8},
+            UInt32Type, UInt64Type, UInt8Type,
+        };
+
+        let total_len = self.tiles.iter().map(|tile| tile.len()).sum::<usize>();
+        policy::check("TiledImage::into_arrow", total_len)?;
+
+        let mut offsets = Vec::with_capacity(self.tiles.len() + 1);
+        let mut data = Vec::with_capacity(total_len);
+
+        offsets.push(0);
+        for tile in &self.tiles {
+            data.extend_from_slice(tile);
+            offsets.push(data.len() as u64);
+        }
+
+        accounting::record("TiledImage::into_arrow", data.len(), true);
+
+        let builder = FastFormatArrowBuilder::with_schema(union_fields())
+            .push_primitive_singleton::<UInt32Type>("width", self.width, UInt32, false)
+            .push_primitive_singleton::<UInt32Type>("height", self.height, UInt32, false)
+            .push_primitive_singleton::<UInt32Type>("tile_width", self.tile_width, UInt32, false)
+            .push_primitive_singleton::<UInt32Type>("tile_height", self.tile_height, UInt32, false)
+            .push_utf_singleton("encoding", self.encoding.as_str(), Utf8, false)
+            .push_utf_singleton(
+                "name",
+                self.name.as_deref().unwrap_or_default(),
+                Utf8,
+                false,
+            );
+
+        header::push(builder, self.header.as_ref())
+            .push_primitive_array::<UInt64Type>("offsets", offsets, UInt64, false)
+            .push_primitive_array::<UInt8Type>("data", data, UInt8, false)
+            .into_arrow()
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_tiled_image_arrow() {
+        use crate::{header::Header, image::Image, tiled_image::TiledImage};
+
+        let data = (0..7 * 5).map(|value| value as u8).collect::<Vec<_>>();
+        let image = Image::new_gray8(data.clone(), 7, 5, Some("microscope"))
+            .unwrap()
+            .with_header(Header::new(1_700_000_000_000_000_000, "stage", 3));
+
+        let tiled = TiledImage::from_image(&image, 4, 4).unwrap();
+        let array_data = tiled.into_arrow().unwrap();
+
+        let raw_data = TiledImage::raw_data(array_data.clone()).unwrap();
+        let view = TiledImage::view_from_raw_data(&raw_data).unwrap();
+
+        assert_eq!(view.len(), 4);
+        assert_eq!(view.header, image.header);
+        assert_eq!(raw_data.unit("tile_width").unwrap(), Some("px"));
+
+        let tile = view.tile(3).unwrap();
+        assert_eq!((tile.width, tile.height), (3, 1));
+        assert_eq!(tile.data.as_u8().unwrap(), &data[32..35]);
+
+        let image = TiledImage::from_arrow(array_data)
+            .unwrap()
+            .into_image()
+            .unwrap();
+
+        assert_eq!(image.data.as_u8().unwrap(), &data[..]);
+        assert_eq!(image.name.as_deref(), Some("microscope"));
+    }
+}
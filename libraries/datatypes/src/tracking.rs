@@ -0,0 +1,202 @@
+//! Association of detections with existing tracks, the building block of SORT-style trackers.
+//!
+//! Tracks are given as a [`BBox`] holding the box of every track in the current frame, e.g.
+//! predicted by a motion model, in the same order as the tracker stores them.
+
+use crate::bbox::BBox;
+use eyre::Result;
+
+/// Result of [`associate`], as indices into the detections and the tracks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Association {
+    /// `(detection, track)` pairs, sorted by detection.
+    pub matches: Vec<(usize, usize)>,
+    /// Detections matching no track, that should start new tracks.
+    pub unmatched_detections: Vec<usize>,
+    /// Tracks matching no detection, that were missed in this frame.
+    pub unmatched_tracks: Vec<usize>,
+}
+
+/// Assigns detections to tracks maximizing their total intersection over union, with the
+/// Hungarian algorithm. Pairs overlapping with an IoU below `min_iou` are left unmatched.
+///
+/// # Errors
+///
+/// Returns an error if one set of boxes is normalized and the other isn't.
+pub fn associate(detections: &BBox, tracks: &BBox, min_iou: f32) -> Result<Association> {
+    let iou = detections.iou(tracks)?;
+    let (rows, cols) = (detections.len(), tracks.len());
+
+    // The algorithm needs at most as many rows as columns: solve the transposed problem
+    // otherwise.
+    let assignment = if rows <= cols {
+        hungarian(rows, cols, |row, col| 1.0 - iou[row * cols + col] as f64)
+    } else {
+        let mut assignment = vec![None; rows];
+        for (track, detection) in
+            hungarian(cols, rows, |row, col| 1.0 - iou[col * cols + row] as f64)
+                .into_iter()
+                .enumerate()
+        {
+            if let Some(detection) = detection {
+                assignment[detection] = Some(track);
+            }
+        }
+
+        assignment
+    };
+
+    let mut association = Association::default();
+    let mut matched_tracks = vec![false; cols];
+
+    for (detection, track) in assignment.into_iter().enumerate() {
+        match track {
+            Some(track) if iou[detection * cols + track] >= min_iou => {
+                association.matches.push((detection, track));
+                matched_tracks[track] = true;
+            }
+            _ => association.unmatched_detections.push(detection),
+        }
+    }
+
+    association.unmatched_tracks = (0..cols).filter(|&track| !matched_tracks[track]).collect();
+
+    Ok(association)
+}
+
+/// Minimum cost assignment of `rows` rows to `cols >= rows` columns: returns the column
+/// assigned to every row.
+///
+/// This is the O(rows² · cols) shortest augmenting path variant, keeping potentials `u` and `v`
+/// for rows and columns. Index 0 is a virtual column, rows and columns are 1-indexed.
+fn hungarian(rows: usize, cols: usize, cost: impl Fn(usize, usize) -> f64) -> Vec<Option<usize>> {
+    let mut u = vec![0.0; rows + 1];
+    let mut v = vec![0.0; cols + 1];
+    // Row assigned to every column, 0 if none.
+    let mut row_of = vec![0; cols + 1];
+    let mut way = vec![0; cols + 1];
+
+    for row in 1..=rows {
+        row_of[0] = row;
+
+        let mut col = 0;
+        let mut min_v = vec![f64::INFINITY; cols + 1];
+        let mut used = vec![false; cols + 1];
+
+        loop {
+            used[col] = true;
+
+            let current_row = row_of[col];
+            let mut delta = f64::INFINITY;
+            let mut next_col = 0;
+
+            for other in 1..=cols {
+                if used[other] {
+                    continue;
+                }
+
+                let reduced = cost(current_row - 1, other - 1) - u[current_row] - v[other];
+                if reduced < min_v[other] {
+                    min_v[other] = reduced;
+                    way[other] = col;
+                }
+                if min_v[other] < delta {
+                    delta = min_v[other];
+                    next_col = other;
+                }
+            }
+
+            for other in 0..=cols {
+                if used[other] {
+                    u[row_of[other]] += delta;
+                    v[other] -= delta;
+                } else {
+                    min_v[other] -= delta;
+                }
+            }
+
+            col = next_col;
+            if row_of[col] == 0 {
+                break;
+            }
+        }
+
+        // Flip the augmenting path.
+        while col != 0 {
+            let previous = way[col];
+            row_of[col] = row_of[previous];
+            col = previous;
+        }
+    }
+
+    let mut assignment = vec![None; rows];
+    for (col, &row) in row_of.iter().enumerate().skip(1) {
+        if row != 0 {
+            assignment[row - 1] = Some(col - 1);
+        }
+    }
+
+    assignment
+}
+
+mod tests {
+    #[test]
+    fn test_associate() {
+        use crate::{
+            bbox::BBox,
+            tracking::{associate, Association},
+        };
+
+        let boxes = |xs: &[f32]| {
+            BBox::new_xyxy(
+                xs.iter().flat_map(|&x| [x, 0.0, x + 10.0, 10.0]).collect(),
+                vec![0.9; xs.len()],
+                vec!["car".to_string(); xs.len()],
+            )
+            .unwrap()
+        };
+
+        let tracks = boxes(&[0.0, 5.0]);
+
+        // Greedy matching would pair the first detection with the first track, its best
+        // overlap, and leave the second detection unmatched.
+        let detections = boxes(&[2.0, -3.0, 100.0]);
+
+        assert_eq!(
+            associate(&detections, &tracks, 0.3).unwrap(),
+            Association {
+                matches: vec![(0, 1), (1, 0)],
+                unmatched_detections: vec![2],
+                unmatched_tracks: vec![],
+            }
+        );
+
+        // Fewer detections than tracks, and a pair below the IoU threshold.
+        let detections = boxes(&[8.0]);
+
+        assert_eq!(
+            associate(&detections, &tracks, 0.3).unwrap(),
+            Association {
+                matches: vec![(0, 1)],
+                unmatched_detections: vec![],
+                unmatched_tracks: vec![0],
+            }
+        );
+        assert_eq!(
+            associate(&detections, &tracks, 0.9).unwrap(),
+            Association {
+                matches: vec![],
+                unmatched_detections: vec![0],
+                unmatched_tracks: vec![0, 1],
+            }
+        );
+
+        assert_eq!(
+            associate(&boxes(&[]), &tracks, 0.3)
+                .unwrap()
+                .unmatched_tracks,
+            vec![0, 1]
+        );
+        assert!(associate(&detections, &tracks.into_normalized(640, 480).unwrap(), 0.3).is_err());
+    }
+}
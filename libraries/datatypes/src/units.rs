@@ -0,0 +1,87 @@
+//! Units of the numeric fields of datatypes, see [`Unit`].
+
+pub use fastformat_core::units::*;
+
+#[cfg(feature = "arrow")]
+pub use arrow::{annotate, check};
+
+#[cfg(feature = "arrow")]
+mod arrow {
+    use super::Unit;
+    use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+
+    use eyre::{Report, Result};
+
+    /// Annotates the fields of a datatype schema with their unit.
+    pub fn annotate(
+        schema: arrow::datatypes::UnionFields,
+        units: &[(&str, Unit)],
+    ) -> arrow::datatypes::UnionFields {
+        let units = units
+            .iter()
+            .map(|(field, unit)| (*field, unit.as_str()))
+            .collect::<Vec<_>>();
+
+        FastFormatArrowBuilder::annotate_units(schema, &units)
+    }
+
+    /// Checks that the field `field` of a received message is in `expected`, so that values in
+    /// another unit are rejected instead of silently misread. Fields without a unit, e.g. from
+    /// older versions, are accepted.
+    pub fn check(raw_data: &FastFormatArrowRawData, field: &str, expected: Unit) -> Result<()> {
+        match raw_data.unit(field)? {
+            Some(unit) if unit.parse::<Unit>()? != expected => Err(Report::msg(format!(
+                "Field {} is in {}, expected {}",
+                field, unit, expected
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn test_units_in_schema() {
+        use crate::{
+            image::{CaptureMetadata, Image},
+            units::{check, Unit},
+        };
+        use fastformat_converter::arrow::{FastFormatArrowBuilder, FastFormatArrowRawData};
+
+        let image = Image::new_gray8(vec![0; 4], 2, 2, None)
+            .unwrap()
+            .with_capture(CaptureMetadata {
+                exposure_time_ns: Some(10_000_000),
+                ..Default::default()
+            });
+        let raw_data = Image::raw_data(image.into_arrow().unwrap()).unwrap();
+
+        assert_eq!(raw_data.unit("width").unwrap(), Some("px"));
+        assert_eq!(raw_data.unit("exposure_time_ns").unwrap(), Some("ns"));
+        assert_eq!(raw_data.unit("encoding").unwrap(), None);
+
+        assert!(check(&raw_data, "exposure_time_ns", Unit::Nanosecond).is_ok());
+        assert!(check(&raw_data, "exposure_time_ns", Unit::Second).is_err());
+
+        // A sender annotating the exposure time in seconds is rejected.
+        use arrow::datatypes::{DataType::UInt64, UInt64Type};
+
+        let schema = FastFormatArrowBuilder::annotate_units(
+            FastFormatArrowBuilder::schema(&[("exposure_time_ns", UInt64, false)]),
+            &[("exposure_time_ns", "s")],
+        );
+        let array_data = FastFormatArrowBuilder::with_schema(schema)
+            .push_primitive_array::<UInt64Type>("exposure_time_ns", vec![1], UInt64, false)
+            .into_arrow()
+            .unwrap();
+        let raw_data = FastFormatArrowRawData::new(array_data).unwrap();
+
+        assert_eq!(
+            check(&raw_data, "exposure_time_ns", Unit::Nanosecond)
+                .unwrap_err()
+                .to_string(),
+            "Field exposure_time_ns is in s, expected ns"
+        );
+    }
+}
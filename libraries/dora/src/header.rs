@@ -0,0 +1,88 @@
+use dora_node_api::{dora_core::config::DataId, uhlc, MetadataParameters, Parameter};
+use fastformat_datatypes::header::Header;
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime},
+};
+
+/// Metadata parameter holding the sequence number of a message sent with [`crate::send`].
+pub const SEQUENCE_PARAMETER: &str = "fastformat.sequence";
+
+/// Current time in nanoseconds since the UNIX epoch, the clock dora timestamps are based on.
+pub(crate) fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+/// Converts a dora timestamp to nanoseconds since the UNIX epoch.
+pub fn timestamp_ns(timestamp: &uhlc::Timestamp) -> u64 {
+    timestamp.get_time().to_duration().as_nanos() as u64
+}
+
+/// Returns the sequence number of the next message sent on `output_id` by this process, i.e.
+/// the number of messages sent on it before.
+pub(crate) fn next_sequence(output_id: &DataId) -> u64 {
+    static SEQUENCES: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+
+    let mut sequences = SEQUENCES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let sequence = sequences.entry(output_id.to_string()).or_default();
+    *sequence += 1;
+
+    *sequence - 1
+}
+
+/// Builds the header of a message received without one from its dora metadata: the time it was
+/// sent, and its sequence number if the sender set it.
+pub(crate) fn from_metadata(
+    timestamp: &uhlc::Timestamp,
+    parameters: &MetadataParameters,
+) -> Header {
+    let sequence = match parameters.get(SEQUENCE_PARAMETER) {
+        Some(Parameter::Integer(sequence)) => *sequence as u64,
+        _ => 0,
+    };
+
+    Header::new(timestamp_ns(timestamp), "", sequence)
+}
+
+/// Time elapsed since `header` was stamped, or `None` if it is in the future, e.g. because
+/// clocks aren't synchronized.
+pub(crate) fn elapsed(header: &Header) -> Option<Duration> {
+    now_ns()
+        .checked_sub(header.timestamp_ns)
+        .map(Duration::from_nanos)
+}
+
+mod tests {
+    #[test]
+    fn test_header_from_metadata() {
+        use crate::header::{from_metadata, next_sequence, SEQUENCE_PARAMETER};
+        use dora_node_api::{dora_core::config::DataId, uhlc, MetadataParameters, Parameter};
+        use std::time::Duration;
+
+        let output_id = DataId::from("test_header_from_metadata".to_string());
+        assert_eq!(next_sequence(&output_id), 0);
+        assert_eq!(next_sequence(&output_id), 1);
+
+        let timestamp = uhlc::Timestamp::new(
+            uhlc::NTP64::from(Duration::from_secs(1_700_000_000)),
+            uhlc::ID::try_from([1]).unwrap(),
+        );
+        let mut parameters = MetadataParameters::new();
+
+        let header = from_metadata(&timestamp, &parameters);
+        assert_eq!(header.timestamp_ns, 1_700_000_000_000_000_000);
+        assert_eq!(header.sequence, 0);
+
+        parameters.insert(SEQUENCE_PARAMETER.to_string(), Parameter::Integer(42));
+        assert_eq!(from_metadata(&timestamp, &parameters).sequence, 42);
+    }
+}
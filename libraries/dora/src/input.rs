@@ -0,0 +1,149 @@
+use eyre::{Context, Result};
+
+use dora_node_api::{ArrowData, Metadata};
+use fastformat_datatypes::{header::Header, owned_view::OwnedView};
+
+use crate::{header, Datatype};
+
+use std::time::Duration;
+
+/// The data of a dora input, decoded as the fastformat datatype `T`.
+///
+/// The input is viewed without copying its buffers; [`TypedInput::into_owned`] converts it to an
+/// owned `T` when it has to outlive the input or be modified.
+pub struct TypedInput<T: Datatype> {
+    view: OwnedView<T>,
+    array_data: arrow::array::ArrayData,
+    /// Header of the input if the message has none, from its dora metadata.
+    metadata_header: Option<Header>,
+}
+
+impl<T: Datatype> TypedInput<T> {
+    /// Decodes the data of a dora `Event::Input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data isn't a `T`.
+    pub fn new(data: ArrowData) -> Result<Self> {
+        let array_data = data.0.to_data();
+        let view = OwnedView::from_arrow(array_data.clone())
+            .wrap_err_with(|| format!("Input isn't a valid {}", T::NAME))?;
+
+        Ok(Self {
+            view,
+            array_data,
+            metadata_header: None,
+        })
+    }
+
+    /// Decodes the data of a dora `Event::Input` like [`TypedInput::new`]. If the message has no
+    /// header, the one of the input is built from `metadata`: the time the message was sent, and
+    /// its sequence number if the sender set it.
+    pub fn with_metadata(data: ArrowData, metadata: &Metadata) -> Result<Self> {
+        let mut input = Self::new(data)?;
+
+        if T::view_header(input.view()).is_none() {
+            input.metadata_header = Some(header::from_metadata(
+                &metadata.timestamp(),
+                &metadata.parameters,
+            ));
+        }
+
+        Ok(input)
+    }
+
+    /// Returns the header of the message, or the one built from the dora metadata.
+    pub fn header(&self) -> Option<&Header> {
+        T::view_header(self.view()).or(self.metadata_header.as_ref())
+    }
+
+    /// Time elapsed since the message was stamped, i.e. since its acquisition or since it was
+    /// sent. Returns `None` if the input has no header or its timestamp is in the future.
+    pub fn latency(&self) -> Option<Duration> {
+        self.header().and_then(header::elapsed)
+    }
+
+    /// Returns a zero-copy view of the input.
+    pub fn view(&self) -> &T::View<'_> {
+        self.view.get()
+    }
+
+    /// Converts the input to an owned `T`, copying the buffers that are still shared. A header
+    /// built from the dora metadata is kept.
+    pub fn into_owned(self) -> Result<T> {
+        let Self {
+            view,
+            array_data,
+            metadata_header,
+        } = self;
+
+        // Release the buffers of the view, so that they can be taken without copying if the
+        // input is their last owner.
+        drop(view);
+
+        let mut data = T::from_arrow(array_data)?;
+        if let Some(metadata_header) = metadata_header {
+            data.header_mut().get_or_insert(metadata_header);
+        }
+
+        Ok(data)
+    }
+}
+
+impl<T: Datatype> TryFrom<ArrowData> for TypedInput<T> {
+    type Error = eyre::Report;
+
+    fn try_from(data: ArrowData) -> Result<Self> {
+        Self::new(data)
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_typed_input() {
+        use crate::TypedInput;
+        use dora_node_api::ArrowData;
+        use fastformat_datatypes::{bbox::BBox, header::Header, image::Image};
+        use std::time::Duration;
+
+        let data = vec![0; 27];
+        let original_buffer_address = data.as_ptr() as *const u64;
+
+        let image = Image::new_rgb8(data, 3, 3, Some("camera.test")).unwrap();
+        let input = ArrowData(arrow::array::make_array(image.into_arrow().unwrap()));
+
+        let input = TypedInput::<Image>::new(input).unwrap();
+
+        assert_eq!(input.view().data.as_ptr(), original_buffer_address);
+        assert_eq!(input.view().name.as_deref(), Some("camera.test"));
+
+        assert_eq!(input.header(), None);
+        assert_eq!(input.latency(), None);
+
+        let image = input.into_owned().unwrap();
+
+        assert_eq!((image.width, image.height), (3, 3));
+
+        let header = Header::new(1_700_000_000_000_000_000, "camera_front", 42);
+        let image = image.with_header(header.clone());
+        let input = ArrowData(arrow::array::make_array(image.into_arrow().unwrap()));
+
+        let input = TypedInput::<Image>::new(input).unwrap();
+
+        assert_eq!(input.header(), Some(&header));
+        assert!(input.latency().unwrap() > Duration::from_secs(1));
+        assert_eq!(input.into_owned().unwrap().header, Some(header));
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0],
+            vec![0.98],
+            vec!["cat".to_string()],
+        )
+        .unwrap();
+        let input = ArrowData(arrow::array::make_array(bbox.into_arrow().unwrap()));
+
+        let error = TypedInput::<Image>::try_from(input).err().unwrap();
+
+        assert_eq!(error.to_string(), "Input isn't a valid Image");
+    }
+}
@@ -0,0 +1,150 @@
+//! Typed helpers to send and receive fastformat datatypes in dora nodes, so that node authors
+//! never handle `ArrayData` directly.
+//!
+//! ```no_run
+//! use dora_node_api::{DoraNode, Event};
+//! use fastformat_datatypes::image::Image;
+//! use fastformat_dora::{send, TypedInput};
+//!
+//! let (mut node, mut events) = DoraNode::init_from_env()?;
+//!
+//! while let Some(event) = events.recv() {
+//!     if let Event::Input { data, metadata, .. } = event {
+//!         let input = TypedInput::<Image>::with_metadata(data, &metadata)?;
+//!         let image = input.view();
+//!
+//!         let gray = Image::new_gray8(vec![0; 4], 2, 2, image.name.as_deref())?;
+//!         send(&mut node, "gray".to_string().into(), gray)?;
+//!     }
+//! }
+//! # Ok::<(), eyre::Report>(())
+//! ```
+//!
+//! Messages sent without a [`Header`] get one stamped with the send time and a per-output
+//! sequence number, and received messages without one get it from their dora metadata, so that
+//! latencies can be measured and streams synchronized without bookkeeping in the nodes.
+
+use eyre::Result;
+
+use dora_node_api::{dora_core::config::DataId, DoraNode, MetadataParameters};
+use fastformat_datatypes::{
+    bbox::BBox,
+    header::{HasHeader, Header},
+    image::Image,
+    key_value_map::KeyValueMap,
+    owned_view::Viewable,
+    tiled_image::TiledImage,
+};
+
+mod header;
+mod input;
+
+pub use header::{timestamp_ns, SEQUENCE_PARAMETER};
+pub use input::TypedInput;
+
+/// A fastformat datatype that can be sent as a dora output and received as a dora input.
+pub trait Datatype: Viewable + HasHeader + Sized {
+    /// Name of the datatype, used in error messages.
+    const NAME: &'static str;
+
+    fn into_arrow(self) -> Result<arrow::array::ArrayData>;
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self>;
+
+    fn view_header<'a>(view: &'a Self::View<'_>) -> Option<&'a Header>;
+}
+
+impl Datatype for Image<'_> {
+    const NAME: &'static str = "Image";
+
+    fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        Image::into_arrow(self)
+    }
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        Image::from_arrow(array_data)
+    }
+
+    fn view_header<'a>(view: &'a Image<'_>) -> Option<&'a Header> {
+        view.header()
+    }
+}
+
+impl Datatype for BBox<'_> {
+    const NAME: &'static str = "BBox";
+
+    fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        BBox::into_arrow(self)
+    }
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        BBox::from_arrow(array_data)
+    }
+
+    fn view_header<'a>(view: &'a BBox<'_>) -> Option<&'a Header> {
+        view.header()
+    }
+}
+
+impl Datatype for TiledImage<'_> {
+    const NAME: &'static str = "TiledImage";
+
+    fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        TiledImage::into_arrow(self)
+    }
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        TiledImage::from_arrow(array_data)
+    }
+
+    fn view_header<'a>(view: &'a TiledImage<'_>) -> Option<&'a Header> {
+        view.header()
+    }
+}
+
+impl Datatype for KeyValueMap {
+    const NAME: &'static str = "KeyValueMap";
+
+    fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        KeyValueMap::into_arrow(self)
+    }
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        KeyValueMap::from_arrow(array_data)
+    }
+
+    fn view_header(view: &KeyValueMap) -> Option<&Header> {
+        view.header()
+    }
+}
+
+/// Sends `data` on the output `output_id` of `node`, with default metadata parameters.
+pub fn send<T: Datatype>(node: &mut DoraNode, output_id: DataId, data: T) -> Result<()> {
+    send_with_parameters(node, output_id, MetadataParameters::default(), data)
+}
+
+/// Sends `data` on the output `output_id` of `node`, with the given metadata parameters.
+///
+/// The sequence number of the message on `output_id` is added to the parameters as
+/// [`SEQUENCE_PARAMETER`]. If `data` has no header, it gets one with the current time and that
+/// sequence number.
+pub fn send_with_parameters<T: Datatype>(
+    node: &mut DoraNode,
+    output_id: DataId,
+    mut parameters: MetadataParameters,
+    mut data: T,
+) -> Result<()> {
+    let sequence = header::next_sequence(&output_id);
+
+    parameters
+        .entry(SEQUENCE_PARAMETER.to_string())
+        .or_insert(dora_node_api::Parameter::Integer(sequence as i64));
+    data.header_mut()
+        .get_or_insert_with(|| Header::new(header::now_ns(), "", sequence));
+
+    node.send_output(
+        output_id,
+        parameters,
+        arrow::array::make_array(data.into_arrow()?),
+    )
+}
@@ -1,8 +1,30 @@
 pub use fastformat_datatypes::image;
 pub use fastformat_datatypes::image::Image;
+pub use fastformat_datatypes::units;
+
+pub use fastformat_converter::accounting;
+pub use fastformat_converter::policy;
 
 #[cfg(feature = "arrow")]
 pub use fastformat_converter::arrow;
 
+#[cfg(feature = "arrow")]
+pub use fastformat_converter::compression;
+
+#[cfg(feature = "arrow")]
+pub use fastformat_converter::inspect;
+
+#[cfg(feature = "arrow")]
+pub use fastformat_converter::ipc;
+
 #[cfg(feature = "ndarray")]
 pub use fastformat_converter::ndarray;
+
+#[cfg(feature = "mcap")]
+pub use fastformat_converter::recording;
+
+#[cfg(feature = "ed25519-dalek")]
+pub use fastformat_converter::signing;
+
+#[cfg(feature = "chacha20poly1305")]
+pub use fastformat_converter::encryption;
@@ -0,0 +1,237 @@
+//! WebAssembly bindings of fastformat, for browser dashboards.
+//!
+//! Build with `wasm-pack build libraries/wasm --target web`. Messages are exchanged as Arrow IPC
+//! streams holding a single record batch with a single column, the fastformat `UnionArray`:
+//! that's what a bridge node forwarding dora messages over a WebSocket sends.
+//!
+//! ```js
+//! import init, { Image } from "./pkg/fastformat_wasm.js";
+//!
+//! await init();
+//! socket.onmessage = async (event) => {
+//!     const image = Image.fromIpc(new Uint8Array(await event.data.arrayBuffer()));
+//!     context.putImageData(
+//!         new ImageData(image.toRgba(), image.width, image.height),
+//!         0,
+//!         0,
+//!     );
+//! };
+//! ```
+
+use fastformat_converter::ipc::{decode_ipc, encode_ipc};
+use fastformat_datatypes::{
+    bbox::BBox as DatatypeBBox,
+    image::{Encoding, Image as DatatypeImage},
+};
+
+use eyre::Report;
+use wasm_bindgen::prelude::*;
+
+/// Converts pixels to the RGBA layout of the canvas `ImageData`.
+fn to_rgba(encoding: Encoding, data: &[u8]) -> Vec<u8> {
+    match encoding {
+        Encoding::RGB8 => data
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], u8::MAX])
+            .collect(),
+        Encoding::BGR8 => data
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], u8::MAX])
+            .collect(),
+        Encoding::GRAY8 => data
+            .iter()
+            .flat_map(|&value| [value, value, value, u8::MAX])
+            .collect(),
+    }
+}
+
+fn js_error(error: Report) -> JsError {
+    JsError::new(&format!("{:#}", error))
+}
+
+/// An image, see `fastformat_datatypes::image::Image`.
+#[wasm_bindgen]
+pub struct Image {
+    image: DatatypeImage<'static>,
+}
+
+#[wasm_bindgen]
+impl Image {
+    /// Decodes an image from an Arrow IPC stream.
+    #[wasm_bindgen(js_name = fromIpc)]
+    pub fn from_ipc(bytes: &[u8]) -> Result<Image, JsError> {
+        decode_ipc(bytes)
+            .and_then(DatatypeImage::from_arrow)
+            .map(|image| Image { image })
+            .map_err(js_error)
+    }
+
+    /// Creates an image from a copy of its pixel data.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        data: &[u8],
+        width: u32,
+        height: u32,
+        encoding: &str,
+        name: Option<String>,
+    ) -> Result<Image, JsError> {
+        let encoding = encoding
+            .parse::<Encoding>()
+            .map_err(|error| JsError::new(&error.to_string()))?;
+
+        DatatypeImage::builder()
+            .width(width)
+            .height(height)
+            .encoding(encoding)
+            .data(data.to_vec())
+            .build()
+            .map(|image| Image {
+                image: match name {
+                    Some(name) => DatatypeImage {
+                        name: Some(name),
+                        ..image
+                    },
+                    None => image,
+                },
+            })
+            .map_err(js_error)
+    }
+
+    /// Encodes the image as an Arrow IPC stream.
+    #[wasm_bindgen(js_name = toIpc)]
+    pub fn to_ipc(self) -> Result<Vec<u8>, JsError> {
+        self.image
+            .into_arrow()
+            .and_then(encode_ipc)
+            .map_err(js_error)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.image.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.image.height
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn encoding(&self) -> String {
+        self.image.encoding.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.image.name.clone()
+    }
+
+    /// Returns a copy of the pixel data.
+    pub fn data(&self) -> Result<js_sys::Uint8Array, JsError> {
+        self.image
+            .data
+            .as_u8()
+            .map(js_sys::Uint8Array::from)
+            .map_err(js_error)
+    }
+
+    /// Returns a view of the pixel data in the WebAssembly memory, without copying. The view is
+    /// invalidated by any allocation that grows the memory, so it must be used right away.
+    #[wasm_bindgen(js_name = dataView)]
+    pub fn data_view(&self) -> Result<js_sys::Uint8Array, JsError> {
+        let data = self.image.data.as_u8().map_err(js_error)?;
+
+        // SAFETY: the view is documented to be used before the memory is reallocated, and the
+        // data lives as long as the image.
+        Ok(unsafe { js_sys::Uint8Array::view(data) })
+    }
+
+    /// Returns the pixels in RGBA, ready for a canvas `ImageData`.
+    #[wasm_bindgen(js_name = toRgba)]
+    pub fn to_rgba(&self) -> Result<js_sys::Uint8ClampedArray, JsError> {
+        let data = self.image.data.as_u8().map_err(js_error)?;
+
+        Ok(js_sys::Uint8ClampedArray::from(
+            &to_rgba(self.image.encoding, data)[..],
+        ))
+    }
+}
+
+/// Bounding boxes, see `fastformat_datatypes::bbox::BBox`.
+#[wasm_bindgen]
+pub struct BBox {
+    bbox: DatatypeBBox<'static>,
+}
+
+#[wasm_bindgen]
+impl BBox {
+    /// Decodes bounding boxes from an Arrow IPC stream.
+    #[wasm_bindgen(js_name = fromIpc)]
+    pub fn from_ipc(bytes: &[u8]) -> Result<BBox, JsError> {
+        decode_ipc(bytes)
+            .and_then(DatatypeBBox::from_arrow)
+            .map(|bbox| BBox { bbox })
+            .map_err(js_error)
+    }
+
+    /// Encodes the boxes as an Arrow IPC stream.
+    #[wasm_bindgen(js_name = toIpc)]
+    pub fn to_ipc(self) -> Result<Vec<u8>, JsError> {
+        self.bbox
+            .into_arrow()
+            .and_then(encode_ipc)
+            .map_err(js_error)
+    }
+
+    /// Number of boxes.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.bbox.len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn encoding(&self) -> String {
+        self.bbox.encoding.to_string()
+    }
+
+    /// Returns a copy of the `4 * length` coordinates of the boxes.
+    pub fn data(&self) -> js_sys::Float32Array {
+        js_sys::Float32Array::from(&self.bbox.data[..])
+    }
+
+    /// Returns a copy of the confidences of the boxes.
+    pub fn confidence(&self) -> js_sys::Float32Array {
+        js_sys::Float32Array::from(&self.bbox.confidence[..])
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        self.bbox.label.clone()
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_ipc_round_trip() {
+        use fastformat_converter::ipc::{decode_ipc, encode_ipc};
+        use fastformat_datatypes::image::Image;
+
+        let flat_image = (0..27).collect::<Vec<u8>>();
+        let image = Image::new_bgr8(flat_image.clone(), 3, 3, Some("camera.test")).unwrap();
+
+        let bytes = encode_ipc(image.into_arrow().unwrap()).unwrap();
+        let image = Image::from_arrow(decode_ipc(&bytes).unwrap()).unwrap();
+
+        assert_eq!(image.name.as_deref(), Some("camera.test"));
+        assert_eq!(image.data.as_u8().unwrap(), &flat_image);
+        assert!(decode_ipc(&bytes[..bytes.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn test_to_rgba() {
+        use crate::to_rgba;
+        use fastformat_datatypes::image::Encoding;
+
+        assert_eq!(to_rgba(Encoding::BGR8, &[1, 2, 3]), vec![3, 2, 1, 255]);
+        assert_eq!(to_rgba(Encoding::GRAY8, &[7]), vec![7, 7, 7, 255]);
+    }
+}
@@ -0,0 +1,177 @@
+use eyre::Result;
+
+use fastformat_datatypes::{
+    bbox::BBox,
+    image::{Encoding, Image},
+};
+
+use crate::measure::{measure, Measurement, Message};
+
+/// Image sizes, from SD to 4K.
+pub const IMAGE_SIZES: [(u32, u32); 4] = [(720, 480), (1280, 720), (1920, 1080), (3840, 2160)];
+
+/// Numbers of boxes of the bounding box cases.
+pub const BBOX_COUNTS: [usize; 4] = [10, 100, 1_000, 10_000];
+
+/// One datatype at one size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Case {
+    Image {
+        encoding: Encoding,
+        width: u32,
+        height: u32,
+    },
+    BBox {
+        count: usize,
+    },
+}
+
+impl Message for Image<'static> {
+    fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        Image::into_arrow(self)
+    }
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        Image::from_arrow(array_data)
+    }
+
+    fn view(array_data: arrow::array::ArrayData) -> Result<usize> {
+        let raw_data = Image::raw_data(array_data)?;
+
+        Ok(Image::view_from_raw_data(&raw_data)?.data.len())
+    }
+}
+
+impl Message for BBox<'static> {
+    fn into_arrow(self) -> Result<arrow::array::ArrayData> {
+        BBox::into_arrow(self)
+    }
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self> {
+        BBox::from_arrow(array_data)
+    }
+
+    fn view(array_data: arrow::array::ArrayData) -> Result<usize> {
+        let raw_data = BBox::raw_data(array_data)?;
+
+        Ok(BBox::view_from_raw_data(&raw_data)?.data.len())
+    }
+}
+
+impl Case {
+    /// Every datatype at every size.
+    pub fn all() -> Vec<Case> {
+        let images = [Encoding::RGB8, Encoding::BGR8, Encoding::GRAY8]
+            .into_iter()
+            .flat_map(|encoding| {
+                IMAGE_SIZES
+                    .into_iter()
+                    .map(move |(width, height)| Case::Image {
+                        encoding,
+                        width,
+                        height,
+                    })
+            });
+        let bboxes = BBOX_COUNTS.into_iter().map(|count| Case::BBox { count });
+
+        images.chain(bboxes).collect()
+    }
+
+    pub fn datatype(&self) -> String {
+        match self {
+            Case::Image { encoding, .. } => format!("Image {}", encoding.as_str()),
+            Case::BBox { .. } => "BBox XYXY".to_string(),
+        }
+    }
+
+    pub fn size(&self) -> String {
+        match self {
+            Case::Image { width, height, .. } => format!("{}x{}", width, height),
+            Case::BBox { count } => format!("{} boxes", count),
+        }
+    }
+
+    /// Size of the payload of the messages: the pixels of images, the coordinates and
+    /// confidences of boxes.
+    pub fn bytes(&self) -> usize {
+        match self {
+            Case::Image {
+                encoding,
+                width,
+                height,
+            } => *width as usize * *height as usize * encoding.channels(),
+            Case::BBox { count } => count * 5 * std::mem::size_of::<f32>(),
+        }
+    }
+
+    fn image(&self) -> Result<Image<'static>> {
+        let Case::Image {
+            encoding,
+            width,
+            height,
+        } = *self
+        else {
+            eyre::bail!("{} isn't an image", self.datatype());
+        };
+
+        let data = (0..self.bytes()).map(|index| index as u8).collect();
+
+        match encoding {
+            Encoding::RGB8 => Image::new_rgb8(data, width, height, Some("bench")),
+            Encoding::BGR8 => Image::new_bgr8(data, width, height, Some("bench")),
+            Encoding::GRAY8 => Image::new_gray8(data, width, height, Some("bench")),
+        }
+    }
+
+    fn bbox(&self) -> Result<BBox<'static>> {
+        let Case::BBox { count } = *self else {
+            eyre::bail!("{} isn't a bounding box", self.datatype());
+        };
+
+        BBox::new_xyxy(
+            (0..count * 4).map(|index| index as f32).collect(),
+            vec![0.5; count],
+            (0..count)
+                .map(|index| format!("class {}", index % 80))
+                .collect(),
+        )
+    }
+
+    /// Measures the conversions of this case, timing each of them `iterations` times.
+    pub fn measure(&self, iterations: usize) -> Result<Measurement> {
+        let (datatype, size, bytes) = (self.datatype(), self.size(), self.bytes());
+
+        match self {
+            Case::Image { .. } => measure(datatype, size, bytes, iterations, || self.image()),
+            Case::BBox { .. } => measure(datatype, size, bytes, iterations, || self.bbox()),
+        }
+    }
+}
+
+mod tests {
+    #[test]
+    fn test_cases() {
+        use crate::cases::Case;
+        use fastformat_datatypes::image::Encoding;
+
+        assert_eq!(Case::all().len(), 16);
+
+        let image = Case::Image {
+            encoding: Encoding::BGR8,
+            width: 4,
+            height: 2,
+        }
+        .measure(3)
+        .unwrap();
+
+        assert_eq!(image.datatype, "Image BGR8");
+        assert_eq!(image.size, "4x2");
+        assert_eq!(image.bytes, 24);
+        assert_eq!((image.copies, image.copied_bytes), (0, 0));
+
+        let bbox = Case::BBox { count: 3 }.measure(3).unwrap();
+
+        assert_eq!(bbox.size, "3 boxes");
+        assert_eq!(bbox.bytes, 60);
+    }
+}
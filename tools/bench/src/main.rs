@@ -0,0 +1,62 @@
+//! Measures the serialization latency, conversion throughput and copies of every fastformat
+//! datatype at several sizes, and prints them as a table.
+//!
+//! ```bash
+//! cargo run --release -p fastformat-bench
+//! cargo run --release -p fastformat-bench -- --datatype image --json results.json
+//! ```
+
+mod cases;
+mod measure;
+mod report;
+
+use clap::Parser;
+use eyre::{Context, Result};
+
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Number of timed runs of every conversion; the median is reported.
+    #[clap(long, default_value_t = 100)]
+    iterations: usize,
+
+    /// Only runs the datatypes whose name contains this text, ignoring case, e.g. `bgr8`.
+    #[clap(long)]
+    datatype: Option<String>,
+
+    /// Also writes the measurements to this file as JSON.
+    #[clap(long)]
+    json: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let filter = args.datatype.map(|datatype| datatype.to_lowercase());
+    let measurements = cases::Case::all()
+        .into_iter()
+        .filter(|case| match &filter {
+            Some(filter) => case.datatype().to_lowercase().contains(filter),
+            None => true,
+        })
+        .map(|case| {
+            eprintln!("Measuring {} {}", case.datatype(), case.size());
+
+            case.measure(args.iterations)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    print!("{}", report::table(&measurements));
+
+    if let Some(path) = args.json {
+        let json = serde_json::to_string_pretty(&measurements)
+            .wrap_err("Failed to serialize the measurements")?;
+
+        std::fs::write(&path, json)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
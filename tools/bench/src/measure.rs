@@ -0,0 +1,122 @@
+use eyre::Result;
+
+use fastformat_converter::accounting;
+
+use std::time::{Duration, Instant};
+
+/// A datatype whose conversions are benchmarked.
+pub trait Message: Sized {
+    fn into_arrow(self) -> Result<arrow::array::ArrayData>;
+
+    fn from_arrow(array_data: arrow::array::ArrayData) -> Result<Self>;
+
+    /// Views the message without taking ownership of its buffers, returning its payload length
+    /// so that the view can't be optimized out.
+    fn view(array_data: arrow::array::ArrayData) -> Result<usize>;
+}
+
+/// Results of one benchmark case, as printed in the table and written to the JSON output.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Measurement {
+    pub datatype: String,
+    pub size: String,
+    /// Size of the payload of the message in bytes.
+    pub bytes: usize,
+
+    /// Median latency of serializing the message to Arrow.
+    pub into_arrow_ns: u64,
+    /// Median latency of viewing the Arrow message without copying.
+    pub view_ns: u64,
+    /// Payload throughput of a conversion to Arrow and back to an owned message.
+    pub round_trip_mib_per_s: f64,
+
+    /// Copies made by one conversion to Arrow, view and conversion back, from the copy accounting.
+    pub copies: u64,
+    pub copied_bytes: u64,
+}
+
+/// Returns the median duration of `run` over `iterations` runs. `setup` prepares the input of
+/// every run and isn't timed, neither is dropping the output.
+pub fn median<S, T>(
+    iterations: usize,
+    mut setup: impl FnMut() -> Result<S>,
+    mut run: impl FnMut(S) -> Result<T>,
+) -> Result<Duration> {
+    let mut durations = (0..iterations.max(1))
+        .map(|_| {
+            let input = setup()?;
+
+            let start = Instant::now();
+            let output = std::hint::black_box(run(std::hint::black_box(input))?);
+            let elapsed = start.elapsed();
+
+            drop(output);
+
+            Ok(elapsed)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    durations.sort_unstable();
+
+    Ok(durations[durations.len() / 2])
+}
+
+/// Measures the conversions of messages built by `make`, whose payload is `bytes` long.
+pub fn measure<M: Message>(
+    datatype: String,
+    size: String,
+    bytes: usize,
+    iterations: usize,
+    make: impl Fn() -> Result<M>,
+) -> Result<Measurement> {
+    let into_arrow = median(iterations, &make, M::into_arrow)?;
+
+    let array_data = make()?.into_arrow()?;
+    let view = median(iterations, || Ok(array_data.clone()), M::view)?;
+
+    let round_trip = median(iterations, &make, |message| {
+        M::from_arrow(message.into_arrow()?)
+    })?;
+
+    let message = make()?;
+    accounting::reset();
+
+    let array_data = message.into_arrow()?;
+    M::view(array_data.clone())?;
+    M::from_arrow(array_data)?;
+
+    let copies = accounting::total();
+
+    Ok(Measurement {
+        datatype,
+        size,
+        bytes,
+        into_arrow_ns: into_arrow.as_nanos() as u64,
+        view_ns: view.as_nanos() as u64,
+        round_trip_mib_per_s: bytes as f64 / round_trip.as_secs_f64().max(1e-9) / (1 << 20) as f64,
+        copies: copies.copies,
+        copied_bytes: copies.copied_bytes,
+    })
+}
+
+mod tests {
+    #[test]
+    fn test_median() {
+        use crate::measure::median;
+        use std::time::Duration;
+
+        let mut sleeps = [10, 1, 100].into_iter();
+        let median = median(
+            3,
+            || Ok(sleeps.next().unwrap()),
+            |millis| {
+                std::thread::sleep(Duration::from_millis(millis));
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert!(median >= Duration::from_millis(10));
+        assert!(median < Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::measure::Measurement;
+
+use std::fmt::Write;
+
+const HEADERS: [&str; 7] = [
+    "datatype",
+    "size",
+    "payload",
+    "into_arrow",
+    "view",
+    "round trip",
+    "copies",
+];
+
+/// Formats a duration in nanoseconds with a unit fitting its magnitude, e.g. `12.3 µs`.
+pub fn duration(nanos: u64) -> String {
+    match nanos {
+        0..=999 => format!("{} ns", nanos),
+        1_000..=999_999 => format!("{:.1} µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1} ms", nanos as f64 / 1e6),
+        _ => format!("{:.2} s", nanos as f64 / 1e9),
+    }
+}
+
+/// Formats a size in bytes with a binary unit fitting its magnitude, e.g. `2.6 MiB`.
+pub fn bytes(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Renders the measurements as a table, one line per case, with columns aligned.
+pub fn table(measurements: &[Measurement]) -> String {
+    let rows = measurements
+        .iter()
+        .map(|measurement| {
+            [
+                measurement.datatype.clone(),
+                measurement.size.clone(),
+                bytes(measurement.bytes),
+                duration(measurement.into_arrow_ns),
+                duration(measurement.view_ns),
+                format!("{:.0} MiB/s", measurement.round_trip_mib_per_s),
+                format!(
+                    "{} ({})",
+                    measurement.copies,
+                    bytes(measurement.copied_bytes as usize)
+                ),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..HEADERS.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([HEADERS[column].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+    let headers = HEADERS.map(str::to_string);
+
+    for row in std::iter::once(&headers).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                // Text columns are left aligned, numbers right aligned.
+                0 | 1 => format!("{:<width$}", cell),
+                _ => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        // Writing to a `String` can't fail.
+        let _ = writeln!(table, "{}", line.trim_end());
+    }
+
+    table
+}
+
+mod tests {
+    #[test]
+    fn test_table() {
+        use crate::{
+            measure::Measurement,
+            report::{bytes, duration, table},
+        };
+
+        assert_eq!(duration(999), "999 ns");
+        assert_eq!(duration(12_345), "12.3 µs");
+        assert_eq!(duration(2_500_000_000), "2.50 s");
+
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1_036_800), "1012.5 KiB");
+        assert_eq!(bytes(6_220_800), "5.9 MiB");
+
+        let measurement = Measurement {
+            datatype: "Image BGR8".to_string(),
+            size: "1280x720".to_string(),
+            bytes: 2_764_800,
+            into_arrow_ns: 1_200,
+            view_ns: 850,
+            round_trip_mib_per_s: 1234.4,
+            copies: 1,
+            copied_bytes: 2_764_800,
+        };
+
+        assert_eq!(
+            table(&[measurement]),
+            "datatype    size      payload  into_arrow    view  round trip       copies\n\
+             Image BGR8  1280x720  2.6 MiB      1.2 µs  850 ns  1234 MiB/s  1 (2.6 MiB)\n"
+        );
+    }
+}
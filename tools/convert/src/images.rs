@@ -0,0 +1,183 @@
+use eyre::{Context, OptionExt, Report, Result};
+
+use fastformat_datatypes::image::{Encoding, Image};
+
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Name of the column of the Arrow IPC streams written by [`images_to_arrow`].
+pub const COLUMN: &str = "image";
+
+const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+
+fn from_dynamic(
+    decoded: image::DynamicImage,
+    encoding: Encoding,
+    name: Option<&str>,
+) -> Result<Image<'static>> {
+    let (width, height) = (decoded.width(), decoded.height());
+
+    match encoding {
+        Encoding::RGB8 => Image::new_rgb8(decoded.into_rgb8().into_raw(), width, height, name),
+        Encoding::BGR8 => from_dynamic(decoded, Encoding::RGB8, name)?.into_bgr8(),
+        Encoding::GRAY8 => Image::new_gray8(decoded.into_luma8().into_raw(), width, height, name),
+    }
+}
+
+fn to_dynamic(image: Image) -> Result<image::DynamicImage> {
+    let (width, height) = (image.width, image.height);
+    let invalid = || Report::msg("Pixel data doesn't match the image size");
+
+    match image.encoding {
+        Encoding::RGB8 => Ok(
+            image::RgbImage::from_raw(width, height, image.data.into_u8()?)
+                .ok_or_else(invalid)?
+                .into(),
+        ),
+        Encoding::BGR8 => to_dynamic(image.into_rgb8()?),
+        Encoding::GRAY8 => Ok(
+            image::GrayImage::from_raw(width, height, image.data.into_u8()?)
+                .ok_or_else(invalid)?
+                .into(),
+        ),
+    }
+}
+
+/// Decodes the image files at `inputs` (PNG or JPEG) and writes them to `output` as an Arrow IPC
+/// stream of `Image` messages in `encoding`, one record batch per image. Images are named after
+/// their file stem.
+pub fn images_to_arrow(inputs: &[PathBuf], output: &Path, encoding: Encoding) -> Result<()> {
+    let file =
+        File::create(output).wrap_err_with(|| format!("Failed to create {}", output.display()))?;
+    let mut file = Some(BufWriter::new(file));
+    let mut writer = None;
+
+    for path in inputs {
+        let decoded =
+            image::open(path).wrap_err_with(|| format!("Failed to decode {}", path.display()))?;
+        let name = path.file_stem().map(|stem| stem.to_string_lossy());
+
+        let array = arrow::array::make_array(
+            from_dynamic(decoded, encoding, name.as_deref())?.into_arrow()?,
+        );
+        let schema = Arc::new(arrow::datatypes::Schema::new(vec![
+            arrow::datatypes::Field::new(COLUMN, array.data_type().clone(), false),
+        ]));
+        let batch = arrow::record_batch::RecordBatch::try_new(schema.clone(), vec![array])
+            .wrap_err("Failed to create the Arrow record batch")?;
+
+        // Every image has the same schema, known once the first one is converted.
+        let writer = match &mut writer {
+            Some(writer) => writer,
+            None => writer.insert(
+                arrow::ipc::writer::StreamWriter::try_new(
+                    file.take().ok_or_eyre("Arrow IPC stream already started")?,
+                    &schema,
+                )
+                .wrap_err("Failed to create the Arrow IPC stream")?,
+            ),
+        };
+
+        writer
+            .write(&batch)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    writer
+        .ok_or_eyre("No image to convert")?
+        .finish()
+        .wrap_err("Failed to finish the Arrow IPC stream")
+}
+
+/// Writes every `Image` message of the Arrow IPC file or stream `input` to `output_dir` as an
+/// image file of format `extension`, e.g. `"png"`. Files are named after the images, or after
+/// their index if they have no name.
+///
+/// Returns the paths of the written files.
+pub fn arrow_to_images(input: &Path, output_dir: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+    let bytes =
+        std::fs::read(input).wrap_err_with(|| format!("Failed to read {}", input.display()))?;
+    let cursor = std::io::Cursor::new(bytes.as_slice());
+
+    let batches = if bytes.starts_with(ARROW_FILE_MAGIC) {
+        arrow::ipc::reader::FileReader::try_new(cursor, None)
+            .wrap_err("Failed to open the Arrow IPC file")?
+            .collect::<Result<Vec<_>, _>>()
+    } else {
+        arrow::ipc::reader::StreamReader::try_new(cursor, None)
+            .wrap_err("Failed to open the Arrow IPC stream")?
+            .collect::<Result<Vec<_>, _>>()
+    }
+    .wrap_err("Failed to read the Arrow record batches")?;
+
+    std::fs::create_dir_all(output_dir)
+        .wrap_err_with(|| format!("Failed to create {}", output_dir.display()))?;
+
+    batches
+        .iter()
+        .flat_map(|batch| batch.columns())
+        .enumerate()
+        .map(|(index, column)| {
+            let image = Image::from_arrow(column.to_data())
+                .wrap_err_with(|| format!("Message {} isn't an Image", index))?;
+
+            let stem = image
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{:05}", index));
+            let path = output_dir.join(format!("{}.{}", stem, extension));
+
+            to_dynamic(image)?
+                .save(&path)
+                .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+mod tests {
+    #[test]
+    fn test_images_round_trip() {
+        use crate::images::{arrow_to_images, images_to_arrow};
+        use fastformat_datatypes::image::Encoding;
+
+        let dir = std::env::temp_dir().join(format!(
+            "fastformat-test-images-round-trip-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let pixels = (0..4 * 3 * 3)
+            .map(|value| value as u8 * 7)
+            .collect::<Vec<u8>>();
+        let input = dir.join("frame.png");
+        image::RgbImage::from_raw(4, 3, pixels.clone())
+            .unwrap()
+            .save(&input)
+            .unwrap();
+
+        let stream = dir.join("frames.arrow");
+        images_to_arrow(&[input.clone(), input.clone()], &stream, Encoding::BGR8).unwrap();
+
+        let paths = arrow_to_images(&stream, &dir.join("out"), "png").unwrap();
+        assert_eq!(
+            paths,
+            [dir.join("out/frame.png"), dir.join("out/frame.png")]
+        );
+
+        // Through BGR8 and back.
+        assert_eq!(
+            image::open(&paths[0]).unwrap().into_rgb8().into_raw(),
+            pixels
+        );
+
+        assert!(images_to_arrow(&[], &stream, Encoding::RGB8).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+//! Converts datasets between container formats, so that they can be prepared for replay
+//! without writing a custom program each time.
+//!
+//! ```bash
+//! fastformat-convert images-to-arrow frames/*.png -o frames.arrow
+//! fastformat-convert arrow-to-images frames.arrow -o frames
+//! fastformat-convert mcap-to-parquet recording.mcap -o recording.parquet
+//! fastformat-convert parquet-to-mcap recording.parquet -o recording.mcap
+//! ```
+
+mod images;
+mod parquet;
+
+use clap::{Parser, Subcommand};
+use eyre::Result;
+
+use fastformat_datatypes::image::Encoding;
+
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Decodes image files (PNG, JPEG) into an Arrow IPC stream of Image messages.
+    ImagesToArrow {
+        #[clap(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Arrow IPC stream to write.
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// Encoding of the Image messages: RGB8, BGR8 or GRAY8.
+        #[clap(long, default_value = "RGB8")]
+        encoding: String,
+    },
+    /// Writes the Image messages of an Arrow IPC file or stream as image files.
+    ArrowToImages {
+        input: PathBuf,
+
+        /// Directory to write the images to.
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// Image file format, e.g. png or jpg.
+        #[clap(long, default_value = "png")]
+        format: String,
+    },
+    /// Converts an MCAP recording to a Parquet file, one row per message.
+    McapToParquet {
+        input: PathBuf,
+
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    /// Converts a Parquet file written by mcap-to-parquet back to an MCAP recording.
+    ParquetToMcap {
+        input: PathBuf,
+
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    match Args::parse().command {
+        Command::ImagesToArrow {
+            inputs,
+            output,
+            encoding,
+        } => {
+            images::images_to_arrow(&inputs, &output, encoding.parse::<Encoding>()?)?;
+
+            println!("Wrote {} images to {}", inputs.len(), output.display());
+        }
+        Command::ArrowToImages {
+            input,
+            output,
+            format,
+        } => {
+            let paths = images::arrow_to_images(&input, &output, &format)?;
+
+            println!("Wrote {} images to {}", paths.len(), output.display());
+        }
+        Command::McapToParquet { input, output } => {
+            let count = parquet::mcap_to_parquet(&input, &output)?;
+
+            println!("Wrote {} messages to {}", count, output.display());
+        }
+        Command::ParquetToMcap { input, output } => {
+            let count = parquet::parquet_to_mcap(&input, &output)?;
+
+            println!("Wrote {} messages to {}", count, output.display());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use eyre::{Context, OptionExt, Result};
+
+use fastformat_converter::{
+    ipc::{decode_ipc, encode_ipc},
+    recording::{Player, Recorder, MESSAGE_ENCODING},
+};
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+
+use std::{collections::HashMap, fs::File, path::Path, sync::Arc};
+
+/// Number of messages per Parquet row group batch.
+const BATCH_SIZE: usize = 64;
+
+/// Parquet schema of recordings: one row per message, holding its topic, its timestamp and the
+/// message serialized as an Arrow IPC stream, as Parquet can't store union arrays.
+fn schema() -> arrow::datatypes::SchemaRef {
+    use arrow::datatypes::{
+        DataType::{Binary, UInt64, Utf8},
+        Field,
+    };
+
+    Arc::new(arrow::datatypes::Schema::new(vec![
+        Field::new("topic", Utf8, false),
+        Field::new("timestamp_ns", UInt64, false),
+        Field::new("message", Binary, false).with_metadata(HashMap::from([(
+            "encoding".to_string(),
+            MESSAGE_ENCODING.to_string(),
+        )])),
+    ]))
+}
+
+/// Converts the MCAP recording `input` to the Parquet file `output`, e.g. to query a dataset
+/// with dataframe tools. Returns the number of converted messages.
+pub fn mcap_to_parquet(input: &Path, output: &Path) -> Result<usize> {
+    let mut messages = Player::open(input)?.messages()?;
+    let mut count = 0;
+
+    let file =
+        File::create(output).wrap_err_with(|| format!("Failed to create {}", output.display()))?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::default()))
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema(), Some(properties))
+        .wrap_err("Failed to create the Parquet writer")?;
+
+    loop {
+        let chunk = messages
+            .by_ref()
+            .take(BATCH_SIZE)
+            .collect::<Result<Vec<_>>>()?;
+        if chunk.is_empty() {
+            break;
+        }
+        count += chunk.len();
+
+        let topics = arrow::array::StringArray::from_iter_values(
+            chunk.iter().map(|message| message.topic.as_str()),
+        );
+        let timestamps = arrow::array::UInt64Array::from_iter_values(
+            chunk.iter().map(|message| message.timestamp_ns),
+        );
+        let payloads = chunk
+            .iter()
+            .map(|message| encode_ipc(message.array_data.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        let payloads = arrow::array::BinaryArray::from_iter_values(payloads);
+
+        let batch = arrow::record_batch::RecordBatch::try_new(
+            schema(),
+            vec![Arc::new(topics), Arc::new(timestamps), Arc::new(payloads)],
+        )
+        .wrap_err("Failed to create the Arrow record batch")?;
+
+        writer
+            .write(&batch)
+            .wrap_err("Failed to write the Parquet file")?;
+    }
+
+    writer
+        .close()
+        .wrap_err("Failed to finish the Parquet file")?;
+
+    Ok(count)
+}
+
+fn column<'a, T: 'static>(
+    batch: &'a arrow::record_batch::RecordBatch,
+    name: &str,
+) -> Result<&'a T> {
+    batch
+        .column_by_name(name)
+        .and_then(|column| column.as_any().downcast_ref::<T>())
+        .ok_or_eyre(format!(
+            "The Parquet file has no {} column of the recording schema",
+            name
+        ))
+}
+
+/// Converts a Parquet file written by [`mcap_to_parquet`] back to the MCAP recording `output`,
+/// e.g. for replay. Returns the number of converted messages.
+pub fn parquet_to_mcap(input: &Path, output: &Path) -> Result<usize> {
+    let file = File::open(input).wrap_err_with(|| format!("Failed to open {}", input.display()))?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|builder| builder.build())
+        .wrap_err("Failed to read the Parquet file")?;
+
+    let mut recorder = Recorder::create(output)?;
+    let mut count = 0;
+
+    for batch in reader {
+        let batch = batch.wrap_err("Failed to read a Parquet record batch")?;
+
+        let topics = column::<arrow::array::StringArray>(&batch, "topic")?;
+        let timestamps = column::<arrow::array::UInt64Array>(&batch, "timestamp_ns")?;
+        let payloads = column::<arrow::array::BinaryArray>(&batch, "message")?;
+
+        for row in 0..batch.num_rows() {
+            recorder.record(
+                topics.value(row),
+                timestamps.value(row),
+                decode_ipc(payloads.value(row))?,
+            )?;
+        }
+
+        count += batch.num_rows();
+    }
+
+    recorder.finish()?;
+
+    Ok(count)
+}
+
+mod tests {
+    #[test]
+    fn test_parquet_round_trip() {
+        use crate::parquet::{mcap_to_parquet, parquet_to_mcap};
+        use arrow::datatypes::{DataType::UInt8, UInt8Type};
+        use fastformat_converter::{
+            arrow::FastFormatArrowBuilder,
+            recording::{Player, Recorder},
+        };
+
+        let dir = std::env::temp_dir().join(format!(
+            "fastformat-test-parquet-round-trip-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let message = |value: u8| {
+            FastFormatArrowBuilder::new()
+                .push_primitive_array::<UInt8Type>("data", vec![value; 27], UInt8, false)
+                .into_arrow()
+                .unwrap()
+        };
+
+        let mut recorder = Recorder::create(dir.join("input.mcap")).unwrap();
+        for index in 0..100 {
+            let topic = match index % 2 {
+                0 => "camera/image",
+                _ => "lidar/points",
+            };
+
+            recorder
+                .record(topic, index as u64 * 1_000, message(index))
+                .unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let count =
+            mcap_to_parquet(&dir.join("input.mcap"), &dir.join("recording.parquet")).unwrap();
+        assert_eq!(count, 100);
+
+        let count =
+            parquet_to_mcap(&dir.join("recording.parquet"), &dir.join("output.mcap")).unwrap();
+        assert_eq!(count, 100);
+
+        let messages = |path| {
+            Player::open(path)
+                .unwrap()
+                .messages()
+                .unwrap()
+                .collect::<eyre::Result<Vec<_>>>()
+                .unwrap()
+        };
+        let input = messages(dir.join("input.mcap"));
+        let output = messages(dir.join("output.mcap"));
+
+        assert_eq!(input.len(), output.len());
+        for (input, output) in input.iter().zip(&output) {
+            assert_eq!(input.topic, output.topic);
+            assert_eq!(input.timestamp_ns, output.timestamp_ns);
+            assert_eq!(input.array_data, output.array_data);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,165 @@
+//! Pretty-prints the fastformat messages of an Arrow IPC file or stream, or of an MCAP
+//! recording, e.g. to check what a dora node actually emitted.
+//!
+//! ```bash
+//! fastformat-inspect recording.mcap
+//! cat messages.arrow | fastformat-inspect --thumbnail-width 0
+//! ```
+
+mod read;
+mod render;
+
+use clap::Parser;
+use eyre::{Context, Result};
+
+use fastformat_converter::inspect;
+use fastformat_datatypes::{
+    any::AnyDatatype,
+    bbox::BBox,
+    header::Header,
+    image::Image,
+    key_value_map::{KeyValueMap, Value},
+    tiled_image::TiledImage,
+};
+
+use std::{io::Read, path::PathBuf};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Arrow IPC file or stream, or MCAP recording. Reads stdin if omitted.
+    path: Option<PathBuf>,
+
+    /// Number of values shown per field.
+    #[clap(long, default_value_t = inspect::DEFAULT_MAX_VALUES)]
+    max_values: usize,
+
+    /// Width of the ASCII thumbnails of images, in characters. 0 disables them.
+    #[clap(long, default_value_t = 64)]
+    thumbnail_width: usize,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let bytes = match &args.path {
+        Some(path) => {
+            std::fs::read(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?
+        }
+        None => {
+            let mut bytes = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut bytes)
+                .wrap_err("Failed to read stdin")?;
+
+            bytes
+        }
+    };
+
+    for message in read::read_messages(&bytes)? {
+        println!("=== {}", message.name);
+
+        // Keep going on malformed messages: finding them is the point of inspecting a file.
+        match inspect::inspect_with(&message.array_data, args.max_values) {
+            Ok(summary) => print!("{}", summary),
+            Err(error) => {
+                println!("{:?}\n", error);
+                continue;
+            }
+        }
+
+        match AnyDatatype::from_arrow(message.array_data) {
+            Ok(AnyDatatype::Image(image)) => print_image(&image, args.thumbnail_width)?,
+            Ok(AnyDatatype::ImageBatch(images)) => {
+                for (index, image) in images.iter().enumerate() {
+                    println!("--- image {}", index);
+                    print_image(image, args.thumbnail_width)?;
+                }
+            }
+            Ok(AnyDatatype::BBox(bbox)) => print_bbox(&bbox),
+            Ok(AnyDatatype::TiledImage(image)) => print_tiled_image(&image),
+            Ok(AnyDatatype::KeyValueMap(map)) => print_key_value_map(&map),
+            Err(error) => println!("{:?}", error),
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+fn print_header(header: &Option<Header>) {
+    if let Some(header) = header {
+        println!(
+            "header: frame {:?}, timestamp {} ns, sequence {}",
+            header.frame_id, header.timestamp_ns, header.sequence
+        );
+    }
+}
+
+fn print_image(image: &Image, thumbnail_width: usize) -> Result<()> {
+    println!(
+        "Image {}x{} {}{}",
+        image.width,
+        image.height,
+        image.encoding,
+        image
+            .name
+            .as_ref()
+            .map(|name| format!(" {:?}", name))
+            .unwrap_or_default()
+    );
+    print_header(&image.header);
+
+    for stats in render::channel_stats(image)? {
+        println!(
+            "{:>5}: min {:>3}, max {:>3}, mean {:.1}",
+            stats.name, stats.min, stats.max, stats.mean
+        );
+    }
+
+    print!("{}", render::thumbnail(image, thumbnail_width)?);
+
+    Ok(())
+}
+
+fn print_bbox(bbox: &BBox) {
+    println!("BBox, {} boxes", bbox.confidence.len());
+    print_header(&bbox.header);
+
+    print!("{}", render::bbox_table(bbox));
+}
+
+/// Tiled images are far too large for a thumbnail: only their layout is printed.
+fn print_tiled_image(image: &TiledImage) {
+    println!(
+        "TiledImage {}x{} {}{}, {}x{} tiles of {}x{}",
+        image.width,
+        image.height,
+        image.encoding,
+        image
+            .name
+            .as_ref()
+            .map(|name| format!(" {:?}", name))
+            .unwrap_or_default(),
+        image.columns(),
+        image.rows(),
+        image.tile_width,
+        image.tile_height
+    );
+    print_header(&image.header);
+}
+
+fn print_key_value_map(map: &KeyValueMap) {
+    println!("KeyValueMap, {} entries", map.len());
+    print_header(&map.header);
+
+    for (key, value) in map.iter() {
+        match value {
+            Value::Int(value) => println!("{}: {}", key, value),
+            Value::Float(value) => println!("{}: {}", key, value),
+            Value::String(value) => println!("{}: {:?}", key, value),
+            Value::Bytes(value) => println!("{}: {} bytes", key, value.len()),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use eyre::{Context, Result};
+
+use fastformat_converter::recording::Player;
+
+/// A message read from the input, named after its MCAP topic or its Arrow column.
+pub struct Message {
+    pub name: String,
+    pub array_data: arrow::array::ArrayData,
+}
+
+const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+const MCAP_MAGIC: &[u8] = b"\x89MCAP0\r\n";
+
+/// Reads every message of an Arrow IPC file or stream, or of an MCAP recording, detected from
+/// the first bytes of `bytes`.
+///
+/// Every column of every record batch of an Arrow input is one message.
+pub fn read_messages(bytes: &[u8]) -> Result<Vec<Message>> {
+    if bytes.starts_with(MCAP_MAGIC) {
+        return Player::from_bytes(bytes)?
+            .messages()?
+            .map(|message| {
+                message.map(|message| Message {
+                    name: format!("{} @ {} ns", message.topic, message.timestamp_ns),
+                    array_data: message.array_data,
+                })
+            })
+            .collect();
+    }
+
+    let cursor = std::io::Cursor::new(bytes);
+    let batches = if bytes.starts_with(ARROW_FILE_MAGIC) {
+        arrow::ipc::reader::FileReader::try_new(cursor, None)
+            .wrap_err("Failed to open the Arrow IPC file")?
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("Failed to read the Arrow IPC file")?
+    } else {
+        arrow::ipc::reader::StreamReader::try_new(cursor, None)
+            .wrap_err("Failed to open the Arrow IPC stream")?
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("Failed to read the Arrow IPC stream")?
+    };
+
+    let messages = batches
+        .iter()
+        .enumerate()
+        .flat_map(|(index, batch)| {
+            let schema = batch.schema();
+
+            batch
+                .columns()
+                .iter()
+                .zip(schema.fields().iter())
+                .map(move |(column, field)| Message {
+                    name: format!("batch {} / {}", index, field.name()),
+                    array_data: column.to_data(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Ok(messages)
+}
+
+mod tests {
+    #[test]
+    fn test_read_messages() {
+        use crate::read::read_messages;
+        use arrow::datatypes::{DataType::UInt8, UInt8Type};
+        use fastformat_converter::{arrow::FastFormatArrowBuilder, recording::Recorder};
+        use std::sync::Arc;
+
+        let array_data = FastFormatArrowBuilder::new()
+            .push_primitive_array::<UInt8Type>("data", vec![0; 27], UInt8, false)
+            .into_arrow()
+            .unwrap();
+
+        let array = arrow::array::make_array(array_data.clone());
+        let schema = Arc::new(arrow::datatypes::Schema::new(vec![
+            arrow::datatypes::Field::new("image", array.data_type().clone(), false),
+        ]));
+        let batch = arrow::record_batch::RecordBatch::try_new(schema.clone(), vec![array]).unwrap();
+
+        let mut stream = Vec::new();
+        let mut writer = arrow::ipc::writer::StreamWriter::try_new(&mut stream, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        let mut file = Vec::new();
+        let mut writer = arrow::ipc::writer::FileWriter::try_new(&mut file, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        let mut recorder = Recorder::new(std::io::Cursor::new(Vec::new())).unwrap();
+        recorder.record("camera/image", 1_000, array_data).unwrap();
+        let recording = recorder.finish().unwrap().into_inner();
+
+        let names = |bytes: &[u8]| {
+            read_messages(bytes)
+                .unwrap()
+                .into_iter()
+                .map(|message| message.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&stream), ["batch 0 / image", "batch 1 / image"]);
+        assert_eq!(names(&file), ["batch 0 / image"]);
+        assert_eq!(names(&recording), ["camera/image @ 1000 ns"]);
+
+        assert!(read_messages(b"not arrow").is_err());
+    }
+}
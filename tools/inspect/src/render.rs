@@ -0,0 +1,212 @@
+use eyre::{OptionExt, Result};
+
+use fastformat_datatypes::{
+    bbox::BBox,
+    image::{Encoding, Image},
+};
+
+use std::fmt::Write;
+
+/// Characters of the ASCII thumbnails, from darkest to brightest.
+const RAMP: &[u8] = b" .:-=+*#%@";
+
+/// Value statistics of one channel of an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelStats {
+    pub name: &'static str,
+    pub min: u8,
+    pub max: u8,
+    pub mean: f64,
+}
+
+fn channel_names(encoding: Encoding) -> &'static [&'static str] {
+    match encoding {
+        Encoding::RGB8 => &["R", "G", "B"],
+        Encoding::BGR8 => &["B", "G", "R"],
+        Encoding::GRAY8 => &["gray"],
+    }
+}
+
+/// Returns the minimum, maximum and mean value of every channel of `image`, in encoding order.
+pub fn channel_stats(image: &Image) -> Result<Vec<ChannelStats>> {
+    let data = image.data.as_u8()?;
+    let channels = image.encoding.channels();
+
+    let stats = channel_names(image.encoding)
+        .iter()
+        .enumerate()
+        .map(|(channel, name)| {
+            let (min, max, sum, count) = data.iter().skip(channel).step_by(channels).fold(
+                (u8::MAX, u8::MIN, 0u64, 0u64),
+                |(min, max, sum, count), &value| {
+                    (
+                        min.min(value),
+                        max.max(value),
+                        sum + value as u64,
+                        count + 1,
+                    )
+                },
+            );
+
+            match count {
+                0 => ChannelStats {
+                    name,
+                    min: 0,
+                    max: 0,
+                    mean: 0.0,
+                },
+                count => ChannelStats {
+                    name,
+                    min,
+                    max,
+                    mean: sum as f64 / count as f64,
+                },
+            }
+        })
+        .collect();
+
+    Ok(stats)
+}
+
+/// Renders `image` as ASCII art at most `columns` characters wide, one line per row of
+/// characters. Characters are about twice as tall as wide, so a row covers two rows of pixels
+/// for one column.
+pub fn thumbnail(image: &Image, columns: usize) -> Result<String> {
+    let data = image.data.as_u8()?;
+    let (width, height) = (image.width as usize, image.height as usize);
+    let channels = image.encoding.channels();
+
+    let columns = columns.min(width);
+    if columns == 0 || height == 0 {
+        return Ok(String::new());
+    }
+    let rows = ((height * columns) as f64 / width as f64 / 2.0)
+        .round()
+        .max(1.0) as usize;
+
+    let mut thumbnail = String::with_capacity((columns + 1) * rows);
+    for row in 0..rows {
+        let y = ((2 * row + 1) * height / (2 * rows)).min(height - 1);
+
+        for column in 0..columns {
+            let x = ((2 * column + 1) * width / (2 * columns)).min(width - 1);
+
+            let index = (y * width + x) * channels;
+            let pixel = data
+                .get(index..index + channels)
+                .ok_or_eyre("Pixel data too short for the image size")?;
+
+            let luminance = match image.encoding {
+                Encoding::RGB8 => {
+                    0.299 * pixel[0] as f64 + 0.587 * pixel[1] as f64 + 0.114 * pixel[2] as f64
+                }
+                Encoding::BGR8 => {
+                    0.114 * pixel[0] as f64 + 0.587 * pixel[1] as f64 + 0.299 * pixel[2] as f64
+                }
+                Encoding::GRAY8 => pixel[0] as f64,
+            };
+
+            let level = (luminance / 255.0 * (RAMP.len() - 1) as f64).round() as usize;
+            thumbnail.push(RAMP[level.min(RAMP.len() - 1)] as char);
+        }
+
+        thumbnail.push('\n');
+    }
+
+    Ok(thumbnail)
+}
+
+/// Renders the boxes of `bbox` as a table, one line per box.
+pub fn bbox_table(bbox: &BBox) -> String {
+    let label_width = bbox
+        .label
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("label".len());
+
+    let mut table = format!(
+        "{:>4}  {:<label_width$}  {:>5}  {:>10}  {}\n",
+        "#", "label", "class", "confidence", bbox.encoding,
+    );
+
+    for (index, (coordinates, confidence)) in bbox
+        .data
+        .chunks_exact(4)
+        .zip(bbox.confidence.iter())
+        .enumerate()
+    {
+        let label = bbox.label.get(index).map(String::as_str).unwrap_or("");
+        let class_id = match bbox
+            .class_id
+            .as_deref()
+            .and_then(|class_id| class_id.get(index))
+        {
+            Some(class_id) => class_id.to_string(),
+            None => "-".to_string(),
+        };
+
+        // Writing to a `String` can't fail.
+        let _ = writeln!(
+            table,
+            "{:>4}  {:<label_width$}  {:>5}  {:>10.3}  [{:.3}, {:.3}, {:.3}, {:.3}]",
+            index,
+            label,
+            class_id,
+            confidence,
+            coordinates[0],
+            coordinates[1],
+            coordinates[2],
+            coordinates[3],
+        );
+    }
+
+    table
+}
+
+mod tests {
+    #[test]
+    fn test_render() {
+        use crate::render::{bbox_table, channel_stats, thumbnail, ChannelStats};
+        use fastformat_datatypes::{bbox::BBox, image::Image};
+
+        // Black left half, white right half.
+        let data = (0..8 * 4)
+            .flat_map(|index| match index % 8 < 4 {
+                true => [0, 0, 10],
+                false => [255, 255, 255],
+            })
+            .collect::<Vec<u8>>();
+        let image = Image::new_rgb8(data, 8, 4, None).unwrap();
+
+        assert_eq!(thumbnail(&image, 4).unwrap(), "  @@\n");
+        assert_eq!(thumbnail(&image, 100).unwrap().lines().count(), 2);
+
+        assert_eq!(
+            channel_stats(&image).unwrap()[2],
+            ChannelStats {
+                name: "B",
+                min: 10,
+                max: 255,
+                mean: 132.5,
+            }
+        );
+
+        let bbox = BBox::new_xyxy(
+            vec![1.0, 1.0, 2.0, 2.0, 0.5, 0.5, 4.0, 4.0],
+            vec![0.98, 0.5],
+            vec!["cat".to_string(), "traffic light".to_string()],
+        )
+        .unwrap()
+        .with_class_id(vec![15, 9])
+        .unwrap();
+
+        assert_eq!(
+            bbox_table(&bbox),
+            "   #  label          class  confidence  XYXY\n   \
+                0  cat               15       0.980  [1.000, 1.000, 2.000, 2.000]\n   \
+                1  traffic light      9       0.500  [0.500, 0.500, 4.000, 4.000]\n"
+        );
+    }
+}